@@ -0,0 +1,54 @@
+#!/bin/sh
+# Times the build of a generated crate of COUNT structures, each annotated with
+# `#[assert_size(16)]`, against the same crate without the attribute.
+#
+# Usage: benches/compile_time.sh [COUNT] [CRATE]
+#
+# COUNT defaults to 3000, CRATE to the checkout the script is in; pass another
+# checkout, e.g. a `git worktree` of an older revision, to compare the two.
+# Dependencies are built first, so only the generated crate is timed, in a
+# non-incremental debug build.
+set -eu
+
+count=${1:-3000}
+crate=$(cd "${2:-$(dirname "$0")/..}" && pwd)
+work=$(mktemp -d)
+trap 'rm -rf "$work"' EXIT
+
+for variant in plain checked; do
+    mkdir -p "$work/$variant/src"
+    cat >"$work/$variant/Cargo.toml" <<TOML
+[package]
+name = "bench-$variant"
+version = "0.0.0"
+edition = "2021"
+publish = false
+
+[dependencies]
+pakr-assert-size = {path="$crate"}
+
+[workspace]
+TOML
+    attribute=
+    if [ "$variant" = checked ]; then
+        attribute='#[pakr_assert_size::assert_size(16)]'
+    fi
+    awk -v count="$count" -v attribute="$attribute" 'BEGIN {
+        for (i = 0; i < count; i++) {
+            printf "%s\n#[repr(C)]\npub struct S%d {\n    pub a: u64,\n    pub b: u32,\n}\n\n", attribute, i
+        }
+    }' >"$work/$variant/src/lib.rs"
+done
+
+export CARGO_TARGET_DIR="$work/target" CARGO_INCREMENTAL=0
+for variant in plain checked; do
+    cargo build -q --manifest-path "$work/$variant/Cargo.toml"
+done
+
+for variant in plain checked; do
+    cargo clean -q -p "bench-$variant" --manifest-path "$work/$variant/Cargo.toml"
+    start=$(date +%s%N)
+    cargo build -q --manifest-path "$work/$variant/Cargo.toml"
+    end=$(date +%s%N)
+    echo "$variant: $count structures in $(((end - start) / 1000000)) ms"
+done
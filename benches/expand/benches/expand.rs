@@ -0,0 +1,47 @@
+//! Expansion of `#[assert_size(16)]` on generated structures, through
+//! `expand_size` of the macros with their entry points stripped.
+
+#![allow(dead_code)]
+
+extern crate proc_macro;
+
+include!(concat!(env!("OUT_DIR"), "/macros.rs"));
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::hint::black_box;
+
+/// Returns `count` structures of 16 bytes, `S0`, `S1`, ...
+fn structures(count: usize) -> Vec<Item> {
+    (0..count)
+        .map(|index| {
+            let id = Ident::new(&format!("S{}", index), Span::call_site());
+            syn::parse_quote! {
+                #[repr(C)]
+                pub struct #id {
+                    pub a: u64,
+                    pub b: u32,
+                }
+            }
+        })
+        .collect()
+}
+
+fn expansion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("expand_size");
+    for count in [100, 1000, 3000] {
+        let items = structures(count);
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(count), &items, |b, items| {
+            b.iter(|| {
+                for item in items {
+                    let checks = expand_size(quote!(16), item.clone(), Comparison::Eq);
+                    black_box(checks.unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, expansion);
+criterion_main!(benches);
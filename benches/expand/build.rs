@@ -0,0 +1,24 @@
+//! Copies the source of `pakr-assert-size-macros` without its macro entry point
+//! attributes and unit tests, which only compile in a proc-macro crate, so the
+//! benchmarks can call the expansion functions directly.
+
+use std::{env, fs, path::Path};
+
+fn main() {
+    let source = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../macros/src/lib.rs");
+    println!("cargo:rerun-if-changed={}", source.display());
+
+    let source = fs::read_to_string(&source).expect("source of the macros");
+    let source = match source.find("\n#[cfg(test)]\nmod tests {") {
+        Some(tests) => &source[..tests],
+        None => &source,
+    };
+    let stripped = source
+        .lines()
+        .filter(|line| !line.starts_with("#[proc_macro"))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("macros.rs");
+    fs::write(out, stripped).expect("copy of the macros");
+}
@@ -0,0 +1,102 @@
+//! Checks shared by the crates of each edition, compiled with the edition of the
+//! including crate. The generated code has to resolve the same in all of them,
+//! so compiling is the test; the `#[test]`s only confirm the generated items.
+
+use pakr_assert_size::*;
+
+#[assert_size(16, export = FRAME_SIZE)]
+#[assert_size_fits(32, warn_at = 24)]
+#[assert_size(16, audit, test_only)]
+#[assert_size_at_least(8)]
+#[assert_align(8)]
+#[assert_no_padding]
+#[assert_offsets_ascending]
+#[assert_field_offset(tag = 0, len = 4, id = 8)]
+#[assert_no_overlap(len, id)]
+#[assert_layout(size = 16, align = 8)]
+#[derive(AssertLayout, AssertSized)]
+#[repr(C)]
+pub struct Frame {
+    #[layout(offset = 0, size = 4)]
+    pub tag: u32,
+    pub len: u32,
+    pub id: u64,
+}
+
+#[assert_size(target_pointer_width = "64" => 16, target_pointer_width = "32" => 8)]
+#[assert_size(2ptr, doc, abi = "1.0")]
+#[repr(C)]
+pub struct Slice {
+    pub ptr: *const u8,
+    pub len: usize,
+}
+
+#[assert_size(4 | 8)]
+#[assert_size_eq_discriminant_type]
+#[repr(u32)]
+pub enum Message {
+    Ping = 1,
+    Pong = 2,
+}
+
+#[assert_size(lock)]
+#[repr(C)]
+pub struct Header {
+    pub tag: u32,
+    pub len: u32,
+}
+
+pub const MAGIC: [u8; 4] = *b"\x7fELF";
+
+pub struct Wrapper;
+
+#[assert_size(Self = 0)]
+impl Wrapper {
+    pub const NAME: &'static str = "wrapper";
+}
+
+assert_type_size!(Frame, 16);
+assert_size_of_val!(MAGIC, 4);
+assert_type_size!(u64 <= 8);
+assert_size_const!(Header, 8);
+layout_assert!(Frame: size == 16, align == 8, offset(id) == 8, no_padding(tag, len, id));
+
+// The tests of `runtime-assert` are not run for items in a function.
+#[allow(unnameable_test_items)]
+pub fn checked_len(frame: &Frame) -> u32 {
+    #[assert_size(4)]
+    #[repr(C)]
+    struct Word(u32);
+
+    assert_type_size!(Word == 4);
+    Word(frame.len).0
+}
+
+pub fn bounded<T>() -> usize {
+    assert_size_bound!(T: <= 64);
+    core::mem::size_of::<T>()
+}
+
+#[test]
+fn generated_items_resolve() {
+    let frame = Frame {
+        tag: 1,
+        len: 2,
+        id: 3,
+    };
+    assert_eq!(FRAME_SIZE, 16);
+    assert_eq!(HEADER_SIZE, 8);
+    assert_eq!(<Frame as AssertSized>::SIZE, 16);
+    assert_eq!(checked_len(&frame) + frame.tag, 3);
+    assert_eq!(frame.id, 3);
+    assert_eq!(bounded::<Frame>(), 16);
+    assert_eq!(Wrapper::NAME, "wrapper");
+    assert_eq!(Message::Ping as u32 + Message::Pong as u32, 3);
+    let header = Header { tag: 4, len: 5 };
+    assert_eq!(header.tag + header.len, 9);
+    let slice = Slice {
+        ptr: core::ptr::null(),
+        len: 0,
+    };
+    assert!(slice.ptr.is_null() && slice.len == 0);
+}
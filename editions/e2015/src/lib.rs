@@ -0,0 +1,11 @@
+//! Checks of `pakr-assert-size` under the 2015 edition.
+
+extern crate pakr_assert_size;
+
+// A module named like a crate the generated code refers to must not be picked
+// up in its place.
+#[allow(dead_code)]
+mod core {}
+
+#[path = "../../checks.rs"]
+pub mod checks;
@@ -0,0 +1,11775 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Delimiter, Literal, Spacing, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use std::fmt;
+use syn::ext::IdentExt;
+use syn::parse::{Nothing, Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, Attribute, BinOp, Expr, ExprLit, Fields, GenericArgument, GenericParam,
+    Generics, Ident, ImplItem, Index, Item, ItemEnum, ItemImpl, ItemStruct, Lit, LitByteStr,
+    LitFloat, LitInt, LitStr, Member, Meta, NestedMeta, Token, Type,
+};
+
+/// Size (or alignment, or offset) given as a macro argument.
+///
+/// Integer literals are evaluated at expansion time, any other expression is
+/// spliced verbatim into the generated check and evaluated in const context.
+///
+/// Integer literals may carry a unit suffix, `KB`/`MB`/`GB` for powers of 1000
+/// or `KiB`/`MiB`/`GiB` for powers of 1024, e.g. `4KiB`. The `ptr` suffix counts
+/// pointers of the target, so it is turned into `N * size_of::<usize>()`.
+struct SizeExpr {
+    value: Option<usize>,
+    expr: Expr,
+}
+
+impl Parse for SizeExpr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut expr = input.parse::<Expr>()?;
+        // Metavariables of `macro_rules!`, e.g. `$size:expr`, arrive wrapped in
+        // invisible groups, which would hide literals.
+        while let Expr::Group(group) = expr {
+            expr = *group.expr;
+        }
+        match &expr {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(lit), ..
+            }) if lit.suffix() == "ptr" => {
+                let count = LitInt::new(lit.base10_digits(), lit.span());
+                // Located at the literal, resolved like the rest of the generated
+                // code, see `emit_assertion`.
+                let span = Span::call_site().located_at(lit.span());
+                let expr = syn::parse_quote_spanned! {span=>
+                    #count * ::core::mem::size_of::<usize>()
+                };
+                Ok(SizeExpr { value: None, expr })
+            }
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(lit), ..
+            }) => Ok(SizeExpr {
+                value: Some(lit_size(lit)?),
+                expr,
+            }),
+            _ => Ok(SizeExpr { value: None, expr }),
+        }
+    }
+}
+
+/// Largest size of any type, `isize::MAX` of a 64-bit target.
+///
+/// The macros run on the host, which does not know the target, so sizes are
+/// bounded by the largest target rather than by the `usize` of the host.
+const MAX_SIZE: u128 = i64::MAX as u128;
+
+/// Evaluates an integer literal, applying its unit suffix, if any.
+///
+/// An integer type suffix, e.g. `16u32`, is ignored, as long as the value fits
+/// the type, as rustc would reject the literal otherwise.
+///
+/// The value is computed in `u128`, so a literal beyond `MAX_SIZE` is rejected
+/// with the limit instead of silently wrapping or failing on overflow.
+fn lit_size(lit: &LitInt) -> syn::Result<usize> {
+    let type_max = match lit.suffix() {
+        "u8" => Some(u8::MAX as u128),
+        "u16" => Some(u16::MAX as u128),
+        "u32" => Some(u32::MAX as u128),
+        "u64" => Some(u64::MAX as u128),
+        "i8" => Some(i8::MAX as u128),
+        "i16" => Some(i16::MAX as u128),
+        "i32" => Some(i32::MAX as u128),
+        "i64" => Some(i64::MAX as u128),
+        "u128" | "i128" | "usize" | "isize" => Some(u128::MAX),
+        _ => None,
+    };
+    if let Some(max) = type_max {
+        if lit.base10_parse::<u128>().is_ok_and(|value| value > max) {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!(
+                    "size `{}` is out of range for `{}`, drop the type suffix",
+                    lit,
+                    lit.suffix()
+                ),
+            ));
+        }
+    }
+    let unit: u128 = match lit.suffix() {
+        _ if type_max.is_some() => 1,
+        "" => 1,
+        "KB" => 1000,
+        "MB" => 1000 * 1000,
+        "GB" => 1000 * 1000 * 1000,
+        "KiB" => 1 << 10,
+        "MiB" => 1 << 20,
+        "GiB" => 1 << 30,
+        "ptr" => {
+            return Err(syn::Error::new(
+                lit.span(),
+                "size in pointers depends on the target and is not supported here",
+            ))
+        }
+        suffix => {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!(
+                "unknown size suffix `{}`, expected an integer type or one of `KB`, `MB`, `GB`, `KiB`, `MiB`, `GiB`, `ptr`",
+                suffix
+            ),
+            ))
+        }
+    };
+    // `base10_parse` honors the radix of the literal, as syn normalizes digits of
+    // hexadecimal, octal and binary literals to base 10.
+    lit.base10_parse::<u128>()
+        .ok()
+        .and_then(|value| value.checked_mul(unit))
+        .filter(|&value| value <= MAX_SIZE)
+        .and_then(|value| usize::try_from(value).ok())
+        .ok_or_else(|| {
+            syn::Error::new(
+                lit.span(),
+                format!(
+                    "size `{}` is too large, no type takes more than `isize::MAX` ({}) bytes",
+                    lit, MAX_SIZE
+                ),
+            )
+        })
+}
+
+impl SizeExpr {
+    /// Returns the span of the expression as written, failures are reported at.
+    fn span(&self) -> Span {
+        self.expr.span()
+    }
+}
+
+impl ToTokens for SizeExpr {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self.value {
+            Some(value) => Literal::usize_unsuffixed(value).to_tokens(tokens),
+            None => {
+                let expr = &self.expr;
+                tokens.extend(quote!((#expr)));
+            }
+        }
+    }
+}
+
+impl fmt::Display for SizeExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.value {
+            Some(value) => write!(f, "{}", value),
+            None => f.write_str(&tokens_to_string(&self.expr)),
+        }
+    }
+}
+
+/// Expected size (or alignment) given as an attribute argument.
+///
+/// The size may be followed by `, msg = "..."` replacing the default failure
+/// message.
+struct ExpSize {
+    size: SizeExpr,
+    msg: Option<LitStr>,
+}
+
+impl Parse for ExpSize {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let size = input.parse::<SizeExpr>()?;
+
+        let mut msg = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<Ident>()?;
+            if key != "msg" {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("unknown key `{}`, expected `msg`", key),
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            msg = Some(input.parse::<LitStr>()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok(ExpSize { size, msg })
+    }
+}
+
+impl ExpSize {
+    /// Returns the failure message: the custom one, if given, or `default` otherwise.
+    fn message(&self, default: Vec<MessagePart>) -> Vec<MessagePart> {
+        match &self.msg {
+            Some(msg) => vec![MessagePart::Text(msg.value())],
+            None => default,
+        }
+    }
+
+    /// Returns the span of the expected size as written.
+    fn span(&self) -> Span {
+        self.size.span()
+    }
+}
+
+impl ToTokens for ExpSize {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.size.to_tokens(tokens);
+    }
+}
+
+impl fmt::Display for ExpSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.size.fmt(f)
+    }
+}
+
+/// Maximum size of `#[assert_size_fits]`, optionally followed by `msg = "..."`,
+/// `warn_at = USIZE`, a soft budget producing a warning when exceeded, and
+/// `align = USIZE`, the maximum alignment.
+struct FitsSize {
+    size: ExpSize,
+    warn_at: Option<SizeExpr>,
+    align: Option<SizeExpr>,
+}
+
+impl Parse for FitsSize {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let size = input.parse::<SizeExpr>()?;
+
+        let mut msg = None;
+        let mut warn_at = None;
+        let mut align = None;
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            if key == "msg" {
+                msg = Some(input.parse::<LitStr>()?);
+            } else if key == "warn_at" {
+                warn_at = Some(input.parse::<SizeExpr>()?);
+            } else if key == "align" {
+                align = Some(input.parse::<SizeExpr>()?);
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!(
+                        "unknown key `{}`, expected `msg`, `warn_at` or `align`",
+                        key
+                    ),
+                ));
+            }
+        }
+
+        if let Some(warn_at) = &warn_at {
+            if let (Some(max), Some(warn)) = (size.value, warn_at.value) {
+                if warn > max {
+                    return Err(syn::Error::new_spanned(
+                        &warn_at.expr,
+                        format!("warning threshold {} exceeds maximum size {}", warn, max),
+                    ));
+                }
+            }
+        }
+
+        Ok(FitsSize {
+            size: ExpSize { size, msg },
+            warn_at,
+            align,
+        })
+    }
+}
+
+/// Expected size depending on the compilation target, `cfg-predicate => USIZE`.
+struct CfgArm {
+    cfg: Meta,
+    size: SizeExpr,
+}
+
+/// Parses a `cfg` predicate followed by `=>`, e.g. `unix`, `target_os = "linux"`
+/// or `any(...)`.
+///
+/// A bare option is parsed as a path, as `Meta` would take the `=` of the
+/// following `=>` for a name-value pair.
+fn parse_cfg_predicate(input: ParseStream) -> syn::Result<Meta> {
+    if input.peek(Ident) && input.peek2(Token![=>]) {
+        Ok(Meta::Path(input.parse::<Ident>()?.into()))
+    } else {
+        input.parse::<Meta>()
+    }
+}
+
+impl Parse for CfgArm {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let cfg = input.call(parse_cfg_predicate)?;
+        input.parse::<Token![=>]>()?;
+        let size = input.parse::<SizeExpr>()?;
+        Ok(CfgArm { cfg, size })
+    }
+}
+
+/// Span of the arm `cfg-predicate => value`, or of its value where the compiler
+/// cannot join spans, so a failing arm of a multi-line attribute is reported
+/// on its own line.
+fn arm_span(cfg: &Meta, value: Span) -> Span {
+    cfg.span().join(value).unwrap_or(value)
+}
+
+/// Comma-separated `cfg`-keyed arms, `cfg-predicate => USIZE, ...`, of the
+/// size and alignment attributes.
+struct CfgArms {
+    arms: Punctuated<CfgArm, Token![,]>,
+}
+
+impl CfgArms {
+    /// Returns `true` if the next argument is a `cfg`-keyed arm.
+    fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+        fork.call(parse_cfg_predicate).is_ok() && fork.peek(Token![=>])
+    }
+
+    /// Parses the arms, with flags among them consumed by `flag`, which returns
+    /// `false` if the next argument is not one of its flags.
+    fn parse_with(
+        input: ParseStream,
+        mut flag: impl FnMut(ParseStream) -> syn::Result<bool>,
+    ) -> syn::Result<Self> {
+        let mut arms = Punctuated::new();
+        while !input.is_empty() {
+            if !flag(input)? {
+                arms.push_value(input.parse::<CfgArm>()?);
+            }
+            if input.is_empty() {
+                break;
+            }
+            // Flags take no place among the arms, only their commas do.
+            let comma = input.parse::<Token![,]>()?;
+            if !arms.empty_or_trailing() {
+                arms.push_punct(comma);
+            }
+        }
+        Ok(CfgArms { arms })
+    }
+
+    /// Returns the expected values paired with their predicates.
+    fn into_sizes(self) -> Vec<(Option<Meta>, ExpSize)> {
+        self.arms
+            .into_iter()
+            .map(|CfgArm { cfg, size }| (Some(cfg), ExpSize { size, msg: None }))
+            .collect()
+    }
+}
+
+impl Parse for CfgArms {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        CfgArms::parse_with(input, |_| Ok(false))
+    }
+}
+
+/// Expected alignment of `#[assert_align]`, given either as a single value,
+/// optionally followed by `msg = "..."`, or as `cfg`-keyed arms, like the size
+/// of `#[assert_size]`.
+enum ExpAlign {
+    Single(Box<ExpSize>),
+    Arms(CfgArms),
+}
+
+impl Parse for ExpAlign {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if CfgArms::peek(input) {
+            Ok(ExpAlign::Arms(input.parse()?))
+        } else {
+            Ok(ExpAlign::Single(Box::new(input.parse()?)))
+        }
+    }
+}
+
+/// Expected size given either as a single value, or as comma-separated
+/// `cfg`-keyed arms, each checked only when its predicate holds, or `lock`,
+/// recording the current size instead of checking it.
+enum CfgSize {
+    Single(Box<ExpSize>),
+    Arms(CfgArms),
+    Lock,
+}
+
+/// Arguments of `#[assert_size]`: the expected size, optionally preceded by the
+/// name of the annotated type, `Header = USIZE` or `self = USIZE`, and followed
+/// by the `allow_default_repr` flag, silencing the warning about a missing
+/// `#[repr]`, the `test_only` flag, building the check for tests only, the
+/// `audit` flag, turning failures into warnings, the `doc` flag, documenting
+/// the expected size on the type, by the instantiation of a generic type,
+/// `phantom_fill`, by the name of a const exporting the expected size,
+/// `export = NAME`, by the version of the ABI the size belongs to,
+/// `abi = "VERSION"`, by the `stable_across_opt` flag, documenting that the
+/// size is relied on in every optimization level, and by the `paranoid` flag,
+/// repeating the check at runtime in a `#[test]`.
+struct ExactSize {
+    name: Option<Ident>,
+    size: CfgSize,
+    allow_default_repr: bool,
+    test_only: bool,
+    audit: bool,
+    doc: bool,
+    stable_across_opt: bool,
+    paranoid: bool,
+    abi: Option<LitStr>,
+    phantom_fill: Option<PhantomFill>,
+    export: Option<Ident>,
+}
+
+/// Consumes `export = NAME`, if it is the next argument.
+fn parse_export(input: ParseStream) -> syn::Result<Option<Ident>> {
+    let fork = input.fork();
+    let is_export = fork.parse::<Ident>().is_ok_and(|ident| ident == "export")
+        && fork.parse::<Token![=]>().is_ok()
+        && fork.parse::<Ident>().is_ok()
+        && (fork.is_empty() || fork.peek(Token![,]));
+    if !is_export {
+        return Ok(None);
+    }
+    input.parse::<Ident>()?;
+    input.parse::<Token![=]>()?;
+    Ok(Some(input.parse::<Ident>()?))
+}
+
+/// Consumes `abi = "VERSION"`, if it is the next argument.
+fn parse_abi(input: ParseStream) -> syn::Result<Option<LitStr>> {
+    let fork = input.fork();
+    let is_abi = fork.parse::<Ident>().is_ok_and(|ident| ident == "abi") && fork.peek(Token![=]);
+    if !is_abi {
+        return Ok(None);
+    }
+    input.parse::<Ident>()?;
+    input.parse::<Token![=]>()?;
+    Ok(Some(input.parse::<LitStr>()?))
+}
+
+/// Substitutions of the parameters of a generic annotated type:
+/// `phantom_fill = "TYPE"` for every type parameter, or
+/// `phantom_fill(T = TYPE, N = VALUE, ...)` for each parameter separately.
+enum PhantomFill {
+    All(Box<Type>),
+    Each(Punctuated<FillArg, Token![,]>),
+}
+
+/// Type or const parameter paired with its substitution, `T = u64` or `N = 4`.
+struct FillArg {
+    name: Ident,
+    value: GenericArgument,
+}
+
+impl Parse for FillArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<Ident>()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse::<GenericArgument>()?;
+        Ok(FillArg { name, value })
+    }
+}
+
+/// Consumes `phantom_fill = "TYPE"` or `phantom_fill(...)`, if it is the next
+/// argument.
+fn parse_phantom_fill(input: ParseStream) -> syn::Result<Option<PhantomFill>> {
+    let fork = input.fork();
+    if !fork
+        .parse::<Ident>()
+        .is_ok_and(|ident| ident == "phantom_fill")
+    {
+        return Ok(None);
+    }
+    input.parse::<Ident>()?;
+    if input.parse::<Option<Token![=]>>()?.is_some() {
+        let ty = input.parse::<LitStr>()?;
+        return Ok(Some(PhantomFill::All(Box::new(ty.parse()?))));
+    }
+    let content;
+    syn::parenthesized!(content in input);
+    let args = Punctuated::<FillArg, Token![,]>::parse_terminated(&content)?;
+    if args.is_empty() {
+        return Err(content.error("expected at least one `PARAM = VALUE` pair"));
+    }
+    Ok(Some(PhantomFill::Each(args)))
+}
+
+/// Consumes the bare flag `name`, if it is the next argument.
+fn parse_flag(input: ParseStream, name: &str) -> syn::Result<bool> {
+    let fork = input.fork();
+    let is_flag = fork.parse::<Ident>().is_ok_and(|ident| ident == name)
+        && (fork.is_empty() || fork.peek(Token![,]));
+    if is_flag {
+        input.parse::<Ident>()?;
+    }
+    Ok(is_flag)
+}
+
+impl ExactSize {
+    /// Returns `true` if the arguments `attr` start with an expected size in one
+    /// of the forms of `#[assert_size]`, so an error of parsing them concerns the
+    /// flags and keys following the size, not the form.
+    fn has_form(attr: TokenStream2) -> bool {
+        let parser = |input: ParseStream| {
+            let form = Self::starts_with_size(input);
+            input.parse::<TokenStream2>()?;
+            Ok(form)
+        };
+        parser.parse2(attr).unwrap_or(false)
+    }
+
+    fn starts_with_size(input: ParseStream) -> bool {
+        if CfgArms::peek(input) {
+            return true;
+        }
+        let fork = input.fork();
+        let named = fork.fork();
+        if named.call(Ident::parse_any).is_ok()
+            && !named.peek(Token![=>])
+            && !named.peek(Token![==])
+            && named.peek(Token![=])
+        {
+            let _ = fork.call(Ident::parse_any);
+            let _ = fork.parse::<Token![=]>();
+        }
+        // Only the syntax is matched, an invalid size is reported as it is.
+        fork.parse::<Expr>().is_ok() && (fork.is_empty() || fork.peek(Token![,]))
+    }
+}
+
+/// Flags and keyed options of `#[assert_size]` given after the size or among
+/// the `cfg`-keyed arms, see `ExactSize`.
+#[derive(Default)]
+struct SizeFlags {
+    allow_default_repr: bool,
+    test_only: bool,
+    audit: bool,
+    doc: bool,
+    stable_across_opt: bool,
+    paranoid: bool,
+    abi: Option<LitStr>,
+    phantom_fill: Option<PhantomFill>,
+    export: Option<Ident>,
+}
+
+impl SizeFlags {
+    /// Consumes a flag or a keyed option, if it is the next argument, returning
+    /// if it was. A flag or an option given twice is an error.
+    fn parse_next(&mut self, input: ParseStream) -> syn::Result<bool> {
+        // The span of the key, the next token.
+        let span = input.span();
+        let duplicate = |key: &str| syn::Error::new(span, format!("duplicate key `{}`", key));
+        for (flag, set) in [
+            ("allow_default_repr", &mut self.allow_default_repr),
+            ("test_only", &mut self.test_only),
+            ("audit", &mut self.audit),
+            ("doc", &mut self.doc),
+            ("stable_across_opt", &mut self.stable_across_opt),
+            ("paranoid", &mut self.paranoid),
+        ] {
+            if parse_flag(input, flag)? {
+                if *set {
+                    return Err(duplicate(flag));
+                }
+                *set = true;
+                return Ok(true);
+            }
+        }
+        if let Some(fill) = parse_phantom_fill(input)? {
+            if self.phantom_fill.is_some() {
+                return Err(duplicate("phantom_fill"));
+            }
+            self.phantom_fill = Some(fill);
+        } else if let Some(name) = parse_export(input)? {
+            if self.export.is_some() {
+                return Err(duplicate("export"));
+            }
+            self.export = Some(name);
+        } else if let Some(version) = parse_abi(input)? {
+            if self.abi.is_some() {
+                return Err(duplicate("abi"));
+            }
+            self.abi = Some(version);
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+}
+
+impl Parse for ExactSize {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lock = input.fork();
+        let mut flags = SizeFlags::default();
+        let mut name = None;
+        let size = if lock.parse::<Ident>().is_ok_and(|ident| ident == "lock") && lock.is_empty() {
+            input.parse::<Ident>()?;
+            CfgSize::Lock
+        } else if CfgArms::peek(input) {
+            CfgSize::Arms(CfgArms::parse_with(input, |input| flags.parse_next(input))?)
+        } else {
+            let named = input.fork();
+            if named.call(Ident::parse_any).is_ok()
+                && !named.peek(Token![=>])
+                && !named.peek(Token![==])
+                && named.peek(Token![=])
+            {
+                name = Some(input.call(Ident::parse_any)?);
+                input.parse::<Token![=]>()?;
+            }
+            let size = input.parse::<SizeExpr>()?;
+            let mut msg = None;
+            while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+                if flags.parse_next(input)? {
+                    continue;
+                }
+                let key = input.parse::<Ident>()?;
+                if key != "msg" {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!(
+                            "unknown key `{}`, expected `msg`, `allow_default_repr`, \
+                             `test_only`, `audit`, `doc`, `stable_across_opt`, `paranoid`, \
+                             `phantom_fill`, `export` or `abi`",
+                            key
+                        ),
+                    ));
+                }
+                if msg.is_some() {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("duplicate key `{}`", key),
+                    ));
+                }
+                input.parse::<Token![=]>()?;
+                msg = Some(input.parse::<LitStr>()?);
+            }
+            CfgSize::Single(Box::new(ExpSize { size, msg }))
+        };
+        let SizeFlags {
+            allow_default_repr,
+            test_only,
+            audit,
+            doc,
+            stable_across_opt,
+            paranoid,
+            abi,
+            phantom_fill,
+            export,
+        } = flags;
+        Ok(ExactSize {
+            name,
+            size,
+            allow_default_repr,
+            test_only,
+            audit,
+            doc,
+            stable_across_opt,
+            paranoid,
+            abi,
+            phantom_fill,
+            export,
+        })
+    }
+}
+
+/// Field of a structure paired with its expected size or offset, `field = USIZE`.
+struct FieldSize {
+    member: Member,
+    size: SizeExpr,
+}
+
+impl Parse for FieldSize {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let member = input.parse::<Member>()?;
+        input.parse::<Token![=]>()?;
+        let size = input.parse::<SizeExpr>()?;
+        Ok(FieldSize { member, size })
+    }
+}
+
+/// Comma-separated list of `field = USIZE` pairs.
+struct FieldSizes {
+    entries: Punctuated<FieldSize, Token![,]>,
+}
+
+impl Parse for FieldSizes {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let entries = Punctuated::parse_terminated(input)?;
+        if entries.is_empty() {
+            return Err(input.error("expected at least one `field = value` pair"));
+        }
+        Ok(FieldSizes { entries })
+    }
+}
+
+/// Path to a field, either a field of the structure, `payload`, or a field of
+/// one of its fields, `inner.payload`. Deeper nesting is not supported.
+struct FieldPath {
+    field: Member,
+    nested: Option<Member>,
+}
+
+impl Parse for FieldPath {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // `0.1` of tuple structures is a single float literal.
+        let (field, nested) = if input.peek(LitFloat) {
+            let lit = input.parse::<LitFloat>()?;
+            let index = |digits: &str| {
+                digits.parse::<u32>().map(|index| {
+                    Member::Unnamed(Index {
+                        index,
+                        span: lit.span(),
+                    })
+                })
+            };
+            match lit.base10_digits().split_once('.') {
+                Some((field, nested)) if lit.suffix().is_empty() => {
+                    match (index(field), index(nested)) {
+                        (Ok(field), Ok(nested)) => (field, Some(nested)),
+                        _ => return Err(syn::Error::new(lit.span(), "expected a field path")),
+                    }
+                }
+                _ => return Err(syn::Error::new(lit.span(), "expected a field path")),
+            }
+        } else {
+            let field = input.parse::<Member>()?;
+            let nested = match input.parse::<Option<Token![.]>>()? {
+                Some(_) => Some(input.parse::<Member>()?),
+                None => None,
+            };
+            (field, nested)
+        };
+        if input.peek(Token![.]) {
+            return Err(input.error("only one level of nesting is supported, e.g. `inner.payload`"));
+        }
+        Ok(FieldPath { field, nested })
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", tokens_to_string(&self.field))?;
+        if let Some(nested) = &self.nested {
+            write!(f, ".{}", tokens_to_string(nested))?;
+        }
+        Ok(())
+    }
+}
+
+/// Field path of a structure paired with its expected size or offset,
+/// `inner.field = USIZE`.
+struct FieldPathSize {
+    path: FieldPath,
+    size: SizeExpr,
+}
+
+impl Parse for FieldPathSize {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse::<FieldPath>()?;
+        input.parse::<Token![=]>()?;
+        let size = input.parse::<SizeExpr>()?;
+        Ok(FieldPathSize { path, size })
+    }
+}
+
+/// Comma-separated list of `inner.field = USIZE` pairs.
+struct FieldPathSizes {
+    entries: Punctuated<FieldPathSize, Token![,]>,
+}
+
+impl Parse for FieldPathSizes {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let entries = Punctuated::parse_terminated(input)?;
+        if entries.is_empty() {
+            return Err(input.error("expected at least one `field = value` pair"));
+        }
+        Ok(FieldPathSizes { entries })
+    }
+}
+
+/// Two distinct fields of a structure or union, `field, field`.
+struct MemberPair {
+    first: Member,
+    second: Member,
+}
+
+impl Parse for MemberPair {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let first = input.parse::<Member>()?;
+        input.parse::<Token![,]>()?;
+        let second = input.parse::<Member>()?;
+        input.parse::<Option<Token![,]>>()?;
+        if first == second {
+            return Err(syn::Error::new_spanned(
+                &second,
+                format!(
+                    "field `{}` always overlaps itself, name two different fields",
+                    tokens_to_string(&second)
+                ),
+            ));
+        }
+        Ok(MemberPair { first, second })
+    }
+}
+
+/// Type whose layout is compared field by field, `TYPE`, optionally followed by
+/// fields named differently in it, `, field = other_field, ...`.
+struct CompatibleType {
+    ty: Type,
+    renames: Vec<(Member, Member)>,
+}
+
+impl Parse for CompatibleType {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse::<Type>()?;
+        let mut renames = Vec::new();
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let field = input.parse::<Member>()?;
+            input.parse::<Token![=]>()?;
+            let other = input.parse::<Member>()?;
+            renames.push((field, other));
+        }
+        Ok(CompatibleType { ty, renames })
+    }
+}
+
+/// Returns the type of field `member` of `struct_item`, or an error spanned
+/// at `member` if there is no such field.
+fn field_type<'a>(struct_item: &'a ItemStruct, member: &Member) -> syn::Result<&'a Type> {
+    member_type("struct", &struct_item.ident, &struct_item.fields, member)
+}
+
+/// Returns the type of field `member` among `fields` of the `kind` item
+/// `ident`, or an error spanned at `member` if there is no such field.
+fn member_type<'a>(
+    kind: &str,
+    ident: &Ident,
+    fields: &'a Fields,
+    member: &Member,
+) -> syn::Result<&'a Type> {
+    let field = match member {
+        Member::Named(name) => fields
+            .iter()
+            .find(|field| field.ident.as_ref() == Some(name)),
+        Member::Unnamed(index) => match fields {
+            Fields::Unnamed(fields) => fields.unnamed.iter().nth(index.index as usize),
+            _ => None,
+        },
+    };
+    match field {
+        Some(field) => Ok(&field.ty),
+        None => Err(syn::Error::new_spanned(
+            member,
+            format!(
+                "no field `{}` in {} `{}`",
+                tokens_to_string(member),
+                kind,
+                ident
+            ),
+        )),
+    }
+}
+
+/// Generates an expression evaluating to the offset of field `member` of `ty`,
+/// in bytes, usable in const context.
+///
+/// It is `core::mem::offset_of!`, stable since Rust 1.77, or with the
+/// `legacy-offset` feature a `const fn` subtracting the address of an
+/// uninitialized value from the address of its field. No value is ever read,
+/// so the fallback is sound for any structure, including packed ones.
+fn emit_offset_of(ty: &impl ToTokens, member: &Member) -> TokenStream2 {
+    if cfg!(feature = "legacy-offset") {
+        quote! {
+            {
+                #[allow(unsafe_code)]
+                const fn offset() -> usize {
+                    let value = ::core::mem::MaybeUninit::<#ty>::uninit();
+                    let base = value.as_ptr();
+                    // SAFETY: only addresses within the allocation of `value`
+                    // are computed, nothing is dereferenced.
+                    unsafe {
+                        let field = ::core::ptr::addr_of!((*base).#member);
+                        (field as *const u8).offset_from(base as *const u8) as usize
+                    }
+                }
+                offset()
+            }
+        }
+    } else {
+        quote!(::core::mem::offset_of!(#ty, #member))
+    }
+}
+
+/// Generates an expression evaluating to the size of field `member` of `ty`, in
+/// bytes, usable in const context where the type of the field is not known,
+/// like in a function-like macro naming the field only.
+///
+/// The type of the field is inferred from a pointer to it, computed from an
+/// uninitialized value as in the `legacy-offset` fallback of `emit_offset_of`.
+fn emit_field_size(ty: &impl ToTokens, member: &Member) -> TokenStream2 {
+    quote! {
+        {
+            #[allow(unsafe_code)]
+            const fn size() -> usize {
+                const fn size_of_pointee<T>(_: *const T) -> usize {
+                    ::core::mem::size_of::<T>()
+                }
+                let value = ::core::mem::MaybeUninit::<#ty>::uninit();
+                let base = value.as_ptr();
+                // SAFETY: only the address of the field is computed, nothing is
+                // dereferenced.
+                size_of_pointee(unsafe { ::core::ptr::addr_of!((*base).#member) })
+            }
+            size()
+        }
+    }
+}
+
+/// Generates expressions evaluating to the offset and the size of the field at
+/// `path` in `struct_item`, checked as `ty`, or returns an error if the first
+/// field of the path does not exist.
+///
+/// A nested field is resolved through the declared type of its parent, so that
+/// type has to be in scope and the nested field visible. Its offset is the sum
+/// of the two offsets and its size is taken as in `emit_field_size`, the type
+/// of the nested field being unknown.
+fn field_path_layout(
+    struct_item: &ItemStruct,
+    ty: &TokenStream2,
+    path: &FieldPath,
+) -> syn::Result<(TokenStream2, TokenStream2)> {
+    let field_ty = static_lifetimes(field_type(struct_item, &path.field)?, &struct_item.generics);
+    let offset = emit_offset_of(ty, &path.field);
+    Ok(match &path.nested {
+        Some(nested) => {
+            let nested_offset = emit_offset_of(&field_ty, nested);
+            (
+                quote!(#offset + #nested_offset),
+                emit_field_size(&field_ty, nested),
+            )
+        }
+        None => (offset, quote!(::core::mem::size_of::<#field_ty>())),
+    })
+}
+
+/// Single `key = USIZE` argument of the `#[layout(...)]` helper attribute.
+struct LayoutArg {
+    key: Ident,
+    value: SizeExpr,
+}
+
+impl Parse for LayoutArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse::<Ident>()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse::<SizeExpr>()?;
+        Ok(LayoutArg { key, value })
+    }
+}
+
+/// Collects arguments of all `#[layout(...)]` helper attributes among `attrs`,
+/// rejecting keys not listed in `allowed` and keys given more than once.
+fn layout_args(attrs: &[Attribute], allowed: &[&str]) -> syn::Result<Vec<LayoutArg>> {
+    let mut args = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("layout")) {
+        let parser = Punctuated::<LayoutArg, Token![,]>::parse_terminated;
+        for arg in attr.parse_args_with(parser)? {
+            push_layout_arg(&mut args, arg, allowed)?;
+        }
+    }
+    Ok(args)
+}
+
+/// Appends `arg` to `args`, unless its key is not listed in `allowed` or is
+/// already present.
+fn push_layout_arg(args: &mut Vec<LayoutArg>, arg: LayoutArg, allowed: &[&str]) -> syn::Result<()> {
+    if !allowed.iter().any(|key| arg.key == key) {
+        let expected = or_list(allowed.iter().map(|key| format!("`{}`", key)));
+        return Err(syn::Error::new(
+            arg.key.span(),
+            format!("unknown key `{}`, expected {}", arg.key, expected),
+        ));
+    }
+    if args.iter().any(|prev| prev.key == arg.key) {
+        return Err(syn::Error::new(
+            arg.key.span(),
+            format!("duplicate key `{}`", arg.key),
+        ));
+    }
+    args.push(arg);
+    Ok(())
+}
+
+/// Appends the accepted forms of an attribute, `usage`, to `error`, an error of
+/// parsing arguments matching none of them.
+fn with_usage(error: syn::Error, usage: &[&str]) -> syn::Error {
+    let mut message = format!("{}; the accepted forms are:", error);
+    for form in usage {
+        message.push_str("\n    ");
+        message.push_str(form);
+    }
+    syn::Error::new(error.span(), message)
+}
+
+/// Accepted forms of the arguments of `#[assert_size]`.
+const ASSERT_SIZE_USAGE: &[&str] = &[
+    "#[assert_size(16)]",
+    "#[assert_size(16, msg = \"...\", allow_default_repr, ...)]",
+    "#[assert_size(Header = 16)]",
+    "#[assert_size(8 | 16)]",
+    "#[assert_size(target_pointer_width = \"64\" => 16, ...)]",
+    "#[assert_size(lock)]",
+];
+
+/// Accepted forms of the arguments of `#[assert_layout]`.
+const ASSERT_LAYOUT_USAGE: &[&str] = &[
+    "#[assert_layout(size = 16, align = 8)]",
+    "#[assert_layout(size_fits = 16, align_fits = 8)]",
+];
+
+/// Joins `items` into a list for a message, e.g. `a, b or c`.
+fn or_list(items: impl IntoIterator<Item = String>) -> String {
+    let mut items = items.into_iter().collect::<Vec<_>>();
+    let last = items.pop().unwrap_or_default();
+    if items.is_empty() {
+        last
+    } else {
+        format!("{} or {}", items.join(", "), last)
+    }
+}
+
+/// Splits an expected size given as `|`-separated alternatives, e.g. `8 | 16`,
+/// into the alternatives, or returns `None` for a single size. A bitwise or
+/// meant as the size itself has to be parenthesized, e.g. `(A | B)`.
+fn size_alternatives(size: &SizeExpr) -> syn::Result<Option<Vec<SizeExpr>>> {
+    fn flatten(expr: &Expr, out: &mut Vec<SizeExpr>) -> syn::Result<()> {
+        match expr {
+            Expr::Binary(binary) if matches!(binary.op, BinOp::BitOr(_)) => {
+                flatten(&binary.left, out)?;
+                flatten(&binary.right, out)
+            }
+            expr => {
+                out.push(syn::parse2(expr.to_token_stream())?);
+                Ok(())
+            }
+        }
+    }
+
+    let mut alternatives = Vec::new();
+    flatten(&size.expr, &mut alternatives)?;
+    Ok(if alternatives.len() > 1 {
+        Some(alternatives)
+    } else {
+        None
+    })
+}
+
+/// Renders tokens to a string resembling the way they are usually written by
+/// hand, i.e. `size_of::<u64>()` instead of `size_of :: < u64 > ()`.
+///
+/// Raw identifiers are written without their prefix, e.g. `r#type` as `type`,
+/// as the string is meant for messages, not for code.
+fn tokens_to_string<T: ToTokens>(tokens: &T) -> String {
+    let mut out = String::new();
+    write_tokens(&mut out, tokens.to_token_stream());
+    out
+}
+
+fn write_tokens(out: &mut String, tokens: TokenStream2) {
+    let mut prev: Option<TokenTree> = None;
+    // Set after a prefix operator, e.g. `*` of `*TABLE`, sticking to its operand.
+    let mut prefix = false;
+    for token in tokens {
+        if let Some(prev) = &prev {
+            if !prefix && needs_space(prev, &token) {
+                out.push(' ');
+            }
+        }
+        prefix = matches!(&token, TokenTree::Punct(punct) if matches!(punct.as_char(), '*' | '-'))
+            && matches!(prev, None | Some(TokenTree::Punct(_)));
+        match &token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{ ", " }"),
+                    Delimiter::None => ("", ""),
+                };
+                out.push_str(open);
+                write_tokens(out, group.stream());
+                out.push_str(close);
+            }
+            TokenTree::Ident(ident) => out.push_str(&ident.unraw().to_string()),
+            token => out.push_str(&token.to_string()),
+        }
+        prev = Some(token);
+    }
+}
+
+fn needs_space(prev: &TokenTree, next: &TokenTree) -> bool {
+    if let TokenTree::Punct(punct) = prev {
+        if punct.spacing() == Spacing::Joint
+            || matches!(punct.as_char(), ':' | '<' | '&' | '!' | '.')
+        {
+            return false;
+        }
+    }
+    match next {
+        // Leading `::` of an absolute path, e.g. `2 * ::core::mem::size_of::<usize>()`.
+        TokenTree::Punct(punct)
+            if punct.as_char() == ':'
+                && punct.spacing() == Spacing::Joint
+                && matches!(prev, TokenTree::Punct(prev) if prev.as_char() != '>') =>
+        {
+            true
+        }
+        TokenTree::Punct(punct) => {
+            !matches!(punct.as_char(), ',' | ';' | ':' | '.' | '<' | '>' | '?')
+        }
+        TokenTree::Group(group) => match (group.delimiter(), prev) {
+            (Delimiter::Parenthesis | Delimiter::Bracket, TokenTree::Ident(_)) => false,
+            (Delimiter::Parenthesis | Delimiter::Bracket, TokenTree::Punct(punct)) => {
+                punct.as_char() != '>'
+            }
+            _ => true,
+        },
+        _ => true,
+    }
+}
+
+#[derive(Debug)]
+struct RangeSize {
+    min: usize,
+    max: usize,
+    min_span: Span,
+    max_span: Span,
+}
+
+impl Parse for RangeSize {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let min_lit = input.parse::<LitInt>()?;
+        input.parse::<Token![,]>()?;
+        let max_lit = input.parse::<LitInt>()?;
+        input.parse::<Option<Token![,]>>()?;
+
+        let min = lit_size(&min_lit)?;
+        let max = lit_size(&max_lit)?;
+        if min > max {
+            return Err(syn::Error::new(
+                max_lit.span(),
+                format!("maximum size {} is less than minimum size {}", max, min),
+            ));
+        }
+
+        Ok(RangeSize {
+            min,
+            max,
+            min_span: min_lit.span(),
+            max_span: max_lit.span(),
+        })
+    }
+}
+
+/// Intended change of the size, `base = USIZE, delta = ISIZE`, in any order.
+///
+/// The base is a size expression, e.g. a const of the old size, the delta a
+/// literal, negative for a shrink.
+struct SizeDelta {
+    base: SizeExpr,
+    delta: LitInt,
+    /// Amount of bytes of `delta`, without its sign.
+    bytes: usize,
+    shrink: bool,
+}
+
+impl Parse for SizeDelta {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut base = None;
+        let mut delta = None;
+        while !input.is_empty() {
+            let key = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            if key == "base" {
+                base = Some(input.parse::<SizeExpr>()?);
+            } else if key == "delta" {
+                let shrink = input.parse::<Option<Token![-]>>()?.is_some();
+                let lit = input.parse::<LitInt>()?;
+                let bytes = lit_size(&lit)?;
+                if bytes == 0 {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "size does not change by 0 bytes, check it with `#[assert_size(...)]` instead",
+                    ));
+                }
+                delta = Some((lit, bytes, shrink));
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("unknown key `{}`, expected `base` or `delta`", key),
+                ));
+            }
+            if input.parse::<Option<Token![,]>>()?.is_none() {
+                break;
+            }
+        }
+        if !input.is_empty() {
+            return Err(input.error("expected `,`"));
+        }
+
+        let base = base.ok_or_else(|| input.error("expected `base = USIZE`, the old size"))?;
+        let (delta, bytes, shrink) =
+            delta.ok_or_else(|| input.error("expected `delta = ISIZE`, the change of the size"))?;
+        Ok(SizeDelta {
+            base,
+            delta,
+            bytes,
+            shrink,
+        })
+    }
+}
+
+/// Comparison of the real size of a type to the expected one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    /// Size must be equal, `==`.
+    Eq,
+    /// Size must not be bigger, `<=`.
+    Le,
+    /// Size must not be smaller, `>=`.
+    Ge,
+}
+
+impl Parse for Comparison {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(Token![==]) {
+            input.parse::<Token![==]>()?;
+            Ok(Comparison::Eq)
+        } else if lookahead.peek(Token![<=]) {
+            input.parse::<Token![<=]>()?;
+            Ok(Comparison::Le)
+        } else if lookahead.peek(Token![>=]) {
+            input.parse::<Token![>=]>()?;
+            Ok(Comparison::Ge)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+impl ToTokens for Comparison {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        tokens.extend(match self {
+            Comparison::Eq => quote!(==),
+            Comparison::Le => quote!(<=),
+            Comparison::Ge => quote!(>=),
+        });
+    }
+}
+
+/// Returns `bytes` in the largest binary unit reaching it, `KiB`, `MiB` or `GiB`,
+/// with at most two decimals, prefixed with `~` if rounded, e.g. `1 MiB` or
+/// `~1.33 KiB`; `None` below 1 KiB.
+fn friendly_size(bytes: u64) -> Option<String> {
+    let (unit, shift) = [("GiB", 30), ("MiB", 20), ("KiB", 10)]
+        .into_iter()
+        .find(|&(_, shift)| bytes >> shift != 0)?;
+    let hundredths = u128::from(bytes) * 100;
+    let scale = 1u128 << shift;
+    let rounded = (hundredths + scale / 2) / scale;
+    let value = format!("{}.{:02}", rounded / 100, rounded % 100);
+    let value = value.trim_end_matches('0').trim_end_matches('.');
+    let approx = if hundredths % scale == 0 { "" } else { "~" };
+    Some(format!("{}{} {}", approx, value, unit))
+}
+
+impl Comparison {
+    /// Returns the phrase describing a failed comparison of a quantity other
+    /// than the size, e.g. `alignment of 'Header' exceeds 8 bytes`.
+    fn verb(self) -> &'static str {
+        match self {
+            Comparison::Eq => "is not",
+            Comparison::Le => "exceeds",
+            Comparison::Ge => "is less than",
+        }
+    }
+
+    /// Returns the failure message of the comparison of size of `name` to `size`,
+    /// with `target` describing the compilation target the check applies to, if
+    /// not all.
+    ///
+    /// The message of the maximum check also reports by how many bytes the
+    /// maximum is exceeded. A literal size of at least 1 KiB is followed by its
+    /// value in binary units, e.g. `1048576 bytes (1 MiB)`.
+    fn message<S: fmt::Display + ToTokens>(
+        self,
+        name: &str,
+        size: &S,
+        target: &str,
+        actual: TokenStream2,
+    ) -> Vec<MessagePart> {
+        // Sizes are displayed as their value if known, as written otherwise.
+        let expected = match size.to_string().parse().ok().and_then(friendly_size) {
+            Some(friendly) => format!("{} bytes ({})", size, friendly),
+            None => format!("{} bytes", size),
+        };
+        let text = match self {
+            Comparison::Eq => format!("size of '{}' is not {}", name, expected),
+            Comparison::Le => format!("'{}' does not fit in {}", name, expected),
+            Comparison::Ge => format!("'{}' is smaller than {}", name, expected),
+        };
+        let mut message = vec![
+            MessagePart::Text(format!("{}{} (actual size: ", text, target)),
+            MessagePart::Value(actual.clone()),
+            MessagePart::Text(" bytes".into()),
+        ];
+        if self == Comparison::Le {
+            message.extend([
+                MessagePart::Text(", ".into()),
+                MessagePart::Value(quote!(#actual.saturating_sub(#size))),
+                MessagePart::Text(" bytes over".into()),
+            ]);
+        }
+        if self != Comparison::Ge {
+            // Hint at the allocator bucket or cache footprint of the real size.
+            message.extend([
+                MessagePart::Text(", next power of two: ".into()),
+                MessagePart::Value(quote!(#actual.next_power_of_two())),
+                MessagePart::Text(" bytes".into()),
+            ]);
+        }
+        message.push(MessagePart::Text(")".into()));
+        message
+    }
+}
+
+/// Parses the type checked by a function-like size macro, rejecting a bare
+/// trait object, which has no size, in favour of a pointer to it.
+fn parse_sized_type(input: ParseStream) -> syn::Result<Type> {
+    let ty = input.parse::<Type>()?;
+    let mut inner = &ty;
+    loop {
+        inner = match inner {
+            Type::Paren(paren) => &paren.elem,
+            Type::Group(group) => &group.elem,
+            _ => break,
+        };
+    }
+    if let Type::TraitObject(object) = inner {
+        let object = tokens_to_string(object);
+        // `&dyn A + B` does not parse, the bounds are parenthesized.
+        let pointee = match object.contains('+') {
+            true => format!("({})", object),
+            false => object.clone(),
+        };
+        return Err(syn::Error::new_spanned(
+            &ty,
+            format!(
+                "`{}` has no size known at compile time, check the size of a pointer \
+                 to it instead, e.g. `&{}` or `Box<{}>`",
+                object, pointee, object
+            ),
+        ));
+    }
+    Ok(ty)
+}
+
+/// Arguments of a function-like macro: checked type, comparison and expected
+/// size, either as `TYPE, USIZE` (exact size) or `TYPE op USIZE`.
+struct TypeSize {
+    ty: Type,
+    op: Comparison,
+    size: ExpSize,
+}
+
+impl Parse for TypeSize {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.call(parse_sized_type)?;
+        let op = if input.parse::<Option<Token![,]>>()?.is_some() {
+            Comparison::Eq
+        } else {
+            input.parse::<Comparison>()?
+        };
+        let size = input.parse::<ExpSize>()?;
+        Ok(TypeSize { ty, op, size })
+    }
+}
+
+/// Arguments of `assert_types_size!`: parenthesized list of checked types,
+/// comparison and expected size, `(TYPE, ...), USIZE` or `(TYPE, ...) op USIZE`.
+struct TypesSize {
+    types: Punctuated<Type, Token![,]>,
+    op: Comparison,
+    size: ExpSize,
+}
+
+impl Parse for TypesSize {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        let paren = syn::parenthesized!(content in input);
+        let types = Punctuated::parse_terminated_with(&content, parse_sized_type)?;
+        if types.is_empty() {
+            return Err(syn::Error::new(paren.span, "expected at least one type"));
+        }
+        let op = if input.parse::<Option<Token![,]>>()?.is_some() {
+            Comparison::Eq
+        } else {
+            input.parse::<Comparison>()?
+        };
+        let size = input.parse::<ExpSize>()?;
+        Ok(TypesSize { types, op, size })
+    }
+}
+
+/// Arguments of `assert_size_bound!`: checked type, comparison and expected
+/// size, `TYPE: op USIZE`.
+struct TypeBound {
+    ty: Type,
+    op: Comparison,
+    size: ExpSize,
+}
+
+impl Parse for TypeBound {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse::<Type>()?;
+        input.parse::<Token![:]>()?;
+        let op = input.parse::<Comparison>()?;
+        let size = input.parse::<ExpSize>()?;
+        Ok(TypeBound { ty, op, size })
+    }
+}
+
+/// Arguments of `assert_size_of_val!`: constant expression and its expected
+/// size, `EXPR, USIZE`.
+struct ValueSize {
+    expr: Expr,
+    size: ExpSize,
+}
+
+impl Parse for ValueSize {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let expr = input.parse::<Expr>()?;
+        input.parse::<Token![,]>()?;
+        let size = input.parse::<ExpSize>()?;
+        Ok(ValueSize { expr, size })
+    }
+}
+
+/// Single clause of `layout_assert!`.
+enum LayoutClause {
+    /// `size OP USIZE`.
+    Size(Comparison, SizeExpr),
+    /// `align OP USIZE`.
+    Align(Comparison, SizeExpr),
+    /// `offset(FIELD) OP USIZE`.
+    Offset(Member, Comparison, SizeExpr),
+    /// `no_padding(FIELD, ...)`, listing all fields of the type.
+    NoPadding(Punctuated<Member, Token![,]>),
+}
+
+impl Parse for LayoutClause {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse::<Ident>()?;
+        if key == "size" {
+            Ok(LayoutClause::Size(input.parse()?, input.parse()?))
+        } else if key == "align" {
+            Ok(LayoutClause::Align(input.parse()?, input.parse()?))
+        } else if key == "offset" {
+            let content;
+            syn::parenthesized!(content in input);
+            let member = content.parse::<Member>()?;
+            content.parse::<Nothing>()?;
+            Ok(LayoutClause::Offset(member, input.parse()?, input.parse()?))
+        } else if key == "no_padding" {
+            if !input.peek(syn::token::Paren) {
+                // Unlike the attribute, the macro does not see the definition.
+                return Err(syn::Error::new(
+                    key.span(),
+                    "`no_padding` needs the fields of the type, list them as \
+                     `no_padding(field, ...)` or use `#[assert_no_padding]` on the definition",
+                ));
+            }
+            let content;
+            let paren = syn::parenthesized!(content in input);
+            let fields = Punctuated::<Member, Token![,]>::parse_terminated(&content)?;
+            if fields.is_empty() {
+                return Err(syn::Error::new(paren.span, "expected at least one field"));
+            }
+            Ok(LayoutClause::NoPadding(fields))
+        } else {
+            Err(syn::Error::new(
+                key.span(),
+                format!(
+                    "unknown clause `{}`, expected `size`, `align`, `offset` or `no_padding`",
+                    key
+                ),
+            ))
+        }
+    }
+}
+
+/// Arguments of `layout_assert!`: checked type and comma-separated clauses,
+/// `TYPE: CLAUSE, ...`.
+struct LayoutAssert {
+    ty: Type,
+    clauses: Punctuated<LayoutClause, Token![,]>,
+}
+
+impl Parse for LayoutAssert {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse::<Type>()?;
+        input.parse::<Token![:]>()?;
+        let clauses = Punctuated::<LayoutClause, Token![,]>::parse_terminated(input)?;
+        if clauses.is_empty() {
+            return Err(input.error("expected at least one clause"));
+        }
+        Ok(LayoutAssert { ty, clauses })
+    }
+}
+
+/// Type in the scope of an `impl` block with its expected size, e.g.
+/// `Self::Output = 4`.
+struct ImplTypeSize {
+    ty: Type,
+    size: ExpSize,
+}
+
+impl Parse for ImplTypeSize {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse::<Type>()?;
+        input.parse::<Token![=]>()?;
+        let size = input.parse::<ExpSize>()?;
+        Ok(ImplTypeSize { ty, size })
+    }
+}
+
+/// Size (or alignment) in words, optionally followed by `word = USIZE`,
+/// overriding the word size of 8 bytes, and `msg = "..."`.
+struct WordCount {
+    count: SizeExpr,
+    word: Option<SizeExpr>,
+    msg: Option<LitStr>,
+}
+
+impl Parse for WordCount {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let count = input.parse::<SizeExpr>()?;
+
+        let mut word = None;
+        let mut msg = None;
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            if key == "word" {
+                let size = input.parse::<SizeExpr>()?;
+                if size.value == Some(0) {
+                    return Err(syn::Error::new_spanned(
+                        &size.expr,
+                        "word size cannot be zero",
+                    ));
+                }
+                word = Some(size);
+            } else if key == "msg" {
+                msg = Some(input.parse::<LitStr>()?);
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("unknown key `{}`, expected `word` or `msg`", key),
+                ));
+            }
+        }
+
+        Ok(WordCount { count, word, msg })
+    }
+}
+
+/// Type given as an attribute argument, the checked type is compared against.
+struct ExpType {
+    ty: Type,
+}
+
+impl Parse for ExpType {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse::<Type>()?;
+        Ok(ExpType { ty })
+    }
+}
+
+/// Arguments of `#[assert_size_ratio]`: the other type, followed by the
+/// multiplier of its size, `times = USIZE`, and the amount of bytes added to it,
+/// `plus = USIZE`, in any order; at least one of them.
+struct SizeRatio {
+    other: Type,
+    times: Option<SizeExpr>,
+    plus: Option<SizeExpr>,
+}
+
+impl Parse for SizeRatio {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let other = input.parse::<Type>()?;
+        let mut times = None;
+        let mut plus = None;
+        while input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<Ident>()?;
+            let slot = if key == "times" {
+                &mut times
+            } else if key == "plus" {
+                &mut plus
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("unknown key `{}`, expected `times` or `plus`", key),
+                ));
+            };
+            if slot.is_some() {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("duplicate key `{}`", key),
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            *slot = Some(input.parse::<SizeExpr>()?);
+        }
+        if !input.is_empty() {
+            return Err(input.error("expected `,`"));
+        }
+        if times.is_none() && plus.is_none() {
+            return Err(syn::Error::new_spanned(
+                &other,
+                "expected `times = USIZE` or `plus = USIZE`, check equal sizes with \
+                 `#[assert_size_eq_type(...)]` instead",
+            ));
+        }
+        Ok(SizeRatio { other, times, plus })
+    }
+}
+
+/// Arguments of `#[assert_array_size]`: the number of elements, `count = USIZE`,
+/// and the expected size of the array, `total = USIZE`, in any order.
+struct ArraySize {
+    count: SizeExpr,
+    total: SizeExpr,
+}
+
+impl Parse for ArraySize {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut count = None;
+        let mut total = None;
+        while !input.is_empty() {
+            let key = input.parse::<Ident>()?;
+            let slot = if key == "count" {
+                &mut count
+            } else if key == "total" {
+                &mut total
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("unknown key `{}`, expected `count` or `total`", key),
+                ));
+            };
+            if slot.is_some() {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("duplicate key `{}`", key),
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            *slot = Some(input.parse::<SizeExpr>()?);
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        match (count, total) {
+            (Some(count), Some(total)) => Ok(ArraySize { count, total }),
+            (None, _) => Err(input.error("expected `count = USIZE`")),
+            (_, None) => Err(input.error("expected `total = USIZE`")),
+        }
+    }
+}
+
+/// Comma-separated list of representation hints, e.g. `"C", "packed"`.
+struct ReprNames {
+    names: Punctuated<LitStr, Token![,]>,
+}
+
+impl Parse for ReprNames {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let names = Punctuated::parse_terminated(input)?;
+        if names.is_empty() {
+            return Err(input.error("expected at least one representation, e.g. `\"C\"`"));
+        }
+        Ok(ReprNames { names })
+    }
+}
+
+/// Comma-separated list of types given as an attribute argument.
+struct ExpTypes {
+    types: Punctuated<Type, Token![,]>,
+}
+
+impl Parse for ExpTypes {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let types = Punctuated::parse_terminated(input)?;
+        if types.is_empty() {
+            return Err(input.error("expected at least one type"));
+        }
+        Ok(ExpTypes { types })
+    }
+}
+
+/// Type paired with its expected size, `TYPE = USIZE`.
+struct TypeEq {
+    ty: Type,
+    size: SizeExpr,
+}
+
+impl Parse for TypeEq {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.call(parse_sized_type)?;
+        input.parse::<Token![=]>()?;
+        let size = input.parse::<SizeExpr>()?;
+        Ok(TypeEq { ty, size })
+    }
+}
+
+/// Entry of `size_assert_all!`, a type compared with its expected size,
+/// `TYPE = USIZE`, `TYPE <= USIZE` or `TYPE >= USIZE`; `==` is accepted for `=`.
+struct TypeEntry {
+    ty: Type,
+    op: Comparison,
+    size: SizeExpr,
+}
+
+impl Parse for TypeEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.call(parse_sized_type)?;
+        let op = if input.peek(Token![=]) && !input.peek(Token![==]) {
+            input.parse::<Token![=]>()?;
+            Comparison::Eq
+        } else {
+            input.parse::<Comparison>()?
+        };
+        let size = input.parse::<SizeExpr>()?;
+        Ok(TypeEntry { ty, op, size })
+    }
+}
+
+/// Arguments of `const_assert_size_eq!`: checked type and its exact size,
+/// `TYPE, USIZE`, with an optional trailing comma.
+struct TypeAndSize {
+    ty: Type,
+    size: SizeExpr,
+}
+
+impl Parse for TypeAndSize {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.call(parse_sized_type)?;
+        input.parse::<Token![,]>()?;
+        let size = input.parse::<SizeExpr>()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(TypeAndSize { ty, size })
+    }
+}
+
+/// Checked type and the type bounding its size, `TYPE, TYPE`.
+struct TypePair {
+    ty: Type,
+    other: Type,
+}
+
+impl Parse for TypePair {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse::<Type>()?;
+        input.parse::<Token![,]>()?;
+        let other = input.parse::<Type>()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(TypePair { ty, other })
+    }
+}
+
+/// Fragment of a failure message of a generated check.
+#[derive(Clone)]
+enum MessagePart {
+    /// Text known at expansion time.
+    Text(String),
+    /// Const expression of type `usize`, formatted in decimal at compile time.
+    Value(TokenStream2),
+}
+
+/// Stands for the path of the module of a generated check in message texts,
+/// see `qualified`.
+const MODULE_PATH: char = '\u{1}';
+
+/// Returns `name` of an item declared next to the generated check, written in
+/// the failure message with the path of its module, as `my_crate::net::Foo`.
+///
+/// The path is only known once the check is expanded, so it is spliced in with
+/// `module_path!()` in the place of the `MODULE_PATH` marker. Like every name
+/// in messages, it is written as in `tokens_to_string`, e.g. `r#type` as `type`.
+fn qualified(name: &impl ToTokens) -> String {
+    format!("{}{}", MODULE_PATH, tokens_to_string(name))
+}
+
+/// Generates an anonymous const item asserting `condition`.
+///
+/// On failure, compilation is aborted with the concatenation of `message`
+/// parts, reported at `span`, usually the one of the expected value. As `panic!`
+/// in const context cannot format integers, the message is assembled by a
+/// helper of the facade crate into a byte array. It is only assembled on the
+/// failure path and its capacity is a literal, so that passing checks, the vast
+/// majority, evaluate nothing but the condition.
+///
+/// The parts are listed in a single call and the condition is not nested in a
+/// macro invocation, so the expansion stays readable when pretty-printed, e.g.
+/// by `cargo expand`.
+fn emit_check(span: Span, condition: TokenStream2, message: &[MessagePart]) -> TokenStream2 {
+    let assertion = emit_assertion(span, condition, message);
+    quote! {
+        const _: () = #assertion;
+    }
+}
+
+/// Generates the `if` expression asserting `condition` of `emit_check`, for
+/// checks evaluated by a const other than an anonymous one.
+fn emit_assertion(span: Span, condition: TokenStream2, message: &[MessagePart]) -> TokenStream2 {
+    // Every value takes at most 20 digits, the length of `u64::MAX`.
+    let capacity: usize = message
+        .iter()
+        .map(|part| match part {
+            MessagePart::Text(text) => text.len(),
+            MessagePart::Value(_) => 20,
+        })
+        .sum();
+    let modules = message
+        .iter()
+        .map(|part| match part {
+            MessagePart::Text(text) => text.matches(MODULE_PATH).count(),
+            MessagePart::Value(_) => 0,
+        })
+        .sum::<usize>();
+    let capacity = match modules {
+        0 => Literal::usize_unsuffixed(capacity).into_token_stream(),
+        _ => {
+            // Each module path is followed by `::` in place of its marker.
+            let capacity = Literal::usize_unsuffixed(capacity + modules);
+            let modules = Literal::usize_unsuffixed(modules);
+            quote!({ #capacity + #modules * ::core::module_path!().len() })
+        }
+    };
+    // Adjacent texts, e.g. of optional hints, are written as one.
+    let mut merged = Vec::<MessagePart>::new();
+    for part in message {
+        match (merged.last_mut(), part) {
+            (Some(MessagePart::Text(last)), MessagePart::Text(text)) => last.push_str(text),
+            (_, part) => merged.push(part.clone()),
+        }
+    }
+    let mut parts = Vec::new();
+    for part in &merged {
+        match part {
+            MessagePart::Text(text) => {
+                for (index, text) in text.split(MODULE_PATH).enumerate() {
+                    if index > 0 {
+                        parts.push(quote! {
+                            ::pakr_assert_size::__private::Part::Text(
+                                ::core::module_path!().as_bytes()
+                            )
+                        });
+                    }
+                    let text = match index {
+                        0 => text.to_owned(),
+                        _ => format!("::{}", text),
+                    };
+                    if !text.is_empty() {
+                        let bytes = LitByteStr::new(text.as_bytes(), Span::call_site());
+                        parts.push(quote!(::pakr_assert_size::__private::Part::Text(#bytes)));
+                    }
+                }
+            }
+            MessagePart::Value(value) => {
+                parts.push(quote!(::pakr_assert_size::__private::Part::Value(#value)))
+            }
+        }
+    }
+
+    // Under the span of the annotated code, which locates the failure, `::core`
+    // would name an item at the root of a crate of the 2015 edition, so the
+    // panic goes through the facade, like the other paths here.
+    quote_spanned! {span=>
+        if !(#condition) {
+            let (bytes, len) = ::pakr_assert_size::__private::format::<#capacity>(&[#(#parts),*]);
+            let message = ::pakr_assert_size::__private::message(&bytes, len);
+            ::pakr_assert_size::__private::panic!("{}", message);
+        }
+    }
+}
+
+/// Generates an anonymous const item warning about `condition` not holding,
+/// instead of failing, the counterpart of `emit_check` for audits.
+///
+/// The warning is a call of a deprecated function, selected by the value of
+/// `condition` from two impls of a local type. Its note is a literal, so it
+/// holds the text of `message` up to the first value, without the module path
+/// and without the open parenthesis of the values, e.g. `size of 'Foo' is not
+/// 16 bytes`.
+fn emit_audit(span: Span, condition: TokenStream2, message: &[MessagePart]) -> TokenStream2 {
+    let mut note = String::new();
+    for part in message {
+        match part {
+            MessagePart::Text(text) => note.push_str(&text.replace(MODULE_PATH, "")),
+            MessagePart::Value(_) => {
+                if let Some(open) = note.rfind(" (") {
+                    note.truncate(open);
+                }
+                break;
+            }
+        }
+    }
+    let report = quote_spanned! {span=>
+        Audit::<PASSED>::report()
+    };
+
+    quote! {
+        const _: () = {
+            struct Audit<const PASSED: bool>;
+            #[allow(dead_code)]
+            impl Audit<true> {
+                const fn report() {}
+            }
+            #[allow(dead_code)]
+            impl Audit<false> {
+                #[deprecated(note = #note)]
+                const fn report() {}
+            }
+            const PASSED: bool = #condition;
+            #report
+        };
+    }
+}
+
+/// Generates the registration of a layout check for `layout_report()` of the
+/// `json-report` feature, or nothing without the feature.
+///
+/// `actual` is the path of the `const fn` measuring the type, e.g.
+/// `::core::mem::size_of::<T>`, as the report reads it at runtime.
+fn emit_report(
+    cfg: Option<&Meta>,
+    type_name: &TokenStream2,
+    kind: &str,
+    expected: &[&SizeExpr],
+    actual: TokenStream2,
+) -> Option<TokenStream2> {
+    cfg!(feature = "json-report").then(|| {
+        let cfg = cfg.map(|cfg| quote!(#[cfg(#cfg)]));
+        quote! {
+            #cfg
+            ::pakr_assert_size::__private::inventory::submit! {
+                ::pakr_assert_size::LayoutCheck {
+                    type_name: #type_name,
+                    kind: #kind,
+                    expected: {
+                        const EXPECTED: &[usize] = &[#(#expected),*];
+                        EXPECTED
+                    },
+                    actual: #actual,
+                }
+            }
+        }
+    })
+}
+
+/// Generates a runtime assertion of `condition`, the counterpart of
+/// `emit_check` for the `runtime-assert` feature, failing with the same
+/// `message`, formatted with `format_args!`.
+fn emit_runtime_check(condition: TokenStream2, message: &[MessagePart]) -> TokenStream2 {
+    let mut format = String::new();
+    let mut pieces = Vec::new();
+    let mut values = Vec::new();
+    for part in message {
+        match part {
+            MessagePart::Text(text) => {
+                let text = text.replace('{', "{{").replace('}', "}}");
+                for (index, text) in text.split(MODULE_PATH).enumerate() {
+                    if index > 0 {
+                        pieces.push(std::mem::take(&mut format).into_token_stream());
+                        pieces.push(quote!(::core::module_path!()));
+                        format.push_str("::");
+                    }
+                    format.push_str(text);
+                }
+            }
+            MessagePart::Value(value) => {
+                format.push_str("{}");
+                values.push(value);
+            }
+        }
+    }
+    let format = if pieces.is_empty() {
+        format.into_token_stream()
+    } else {
+        quote!(::core::concat!(#(#pieces,)* #format))
+    };
+    quote! {
+        ::core::assert!(#condition, #format #(, #values)*);
+    }
+}
+
+/// Expands an attribute macro attached to `item`.
+///
+/// On error, `item` is emitted unchanged along with the error, so references to
+/// the annotated type still resolve and only the actual problem is reported.
+fn expand_attribute(
+    item: TokenStream,
+    expand: impl FnOnce(TokenStream) -> syn::Result<TokenStream2>,
+) -> TokenStream {
+    match expand(item.clone()) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(err) => {
+            let err = field_macro(&item.clone().into()).unwrap_or(err);
+            let mut expanded = TokenStream::from(err.to_compile_error());
+            expanded.extend(item);
+            expanded
+        }
+    }
+}
+
+/// Finds a macro invoked in place of fields or variants of `item`, like
+/// `include!("fields.rs")` in the body of a generated structure.
+///
+/// Attribute macros see the item before any macro in it is expanded, and Rust
+/// does not expand macros in that position at all, so the item cannot be
+/// parsed. The returned error explains it, instead of the parse error.
+fn field_macro(item: &TokenStream2) -> Option<syn::Error> {
+    let body = item
+        .clone()
+        .into_iter()
+        .skip_while(|token| {
+            !matches!(token, TokenTree::Ident(kind) if kind == "struct" || kind == "enum" || kind == "union")
+        })
+        .find_map(|token| match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => Some(group),
+            _ => None,
+        })?;
+    let mut tokens = body.stream().into_iter().peekable();
+    while tokens.peek().is_some() {
+        let entry: Vec<TokenTree> = tokens
+            .by_ref()
+            .take_while(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == ','))
+            .collect();
+        let mut rest = entry.as_slice();
+        // Attributes and visibility may precede the field.
+        loop {
+            match rest {
+                [TokenTree::Punct(punct), TokenTree::Group(_), tail @ ..]
+                    if punct.as_char() == '#' =>
+                {
+                    rest = tail
+                }
+                [TokenTree::Ident(vis), TokenTree::Group(group), tail @ ..]
+                    if vis == "pub" && group.delimiter() == Delimiter::Parenthesis =>
+                {
+                    rest = tail
+                }
+                [TokenTree::Ident(vis), tail @ ..] if vis == "pub" => rest = tail,
+                _ => break,
+            }
+        }
+        if let [TokenTree::Ident(name), TokenTree::Punct(bang), TokenTree::Group(_), ..] = rest {
+            if bang.as_char() == '!' {
+                return Some(syn::Error::new(
+                    name.span(),
+                    format!(
+                        "fields cannot come from `{}!`, attribute macros see the item before \
+                         macros in it are expanded, and Rust does not expand macros in place \
+                         of fields; generate or include the whole item, with its attributes, \
+                         instead",
+                        name
+                    ),
+                ));
+            }
+        }
+    }
+    None
+}
+
+/// Expands `#[assert_size]`, `#[assert_size_fits]` and `#[assert_size_at_least]`,
+/// comparing the size of `item` to the expected one with `op`.
+///
+/// Arguments are parsed according to `op`: only the exact check accepts
+/// `cfg`-keyed arms and only the maximum check accepts `warn_at` and `align`.
+fn expand_size(attr: TokenStream2, mut item: Item, op: Comparison) -> syn::Result<TokenStream2> {
+    let mut check_repr = false;
+    let mut test_only = false;
+    let mut audit = false;
+    let mut doc = false;
+    let mut stable = false;
+    let mut paranoid = false;
+    let mut abi = None;
+    let mut type_name = None;
+    let mut max_align = None;
+    let mut fill = None;
+    let mut export = None;
+    let (arms, warn_at) = match op {
+        Comparison::Eq => {
+            let ExactSize {
+                name,
+                size,
+                allow_default_repr,
+                test_only: test,
+                audit: soft,
+                doc: documented,
+                stable_across_opt,
+                paranoid: recheck,
+                abi: version,
+                phantom_fill,
+                export: export_name,
+            } = syn::parse2::<ExactSize>(attr.clone()).map_err(|err| match ExactSize::has_form(
+                attr,
+            ) {
+                true => err,
+                false => with_usage(err, ASSERT_SIZE_USAGE),
+            })?;
+            check_repr = !allow_default_repr;
+            test_only = test;
+            audit = soft;
+            doc = documented;
+            stable = stable_across_opt;
+            paranoid = recheck;
+            abi = version;
+            if paranoid && audit {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "`paranoid` repeats the check in a `#[test]`, which an `audit` check must not fail",
+                ));
+            }
+            type_name = name;
+            fill = phantom_fill;
+            export = export_name;
+            match size {
+                CfgSize::Lock => return lock_size(item),
+                CfgSize::Single(size) => (vec![(None, *size)], None),
+                CfgSize::Arms(arms) => (arms.into_sizes(), None),
+            }
+        }
+        Comparison::Le => {
+            let FitsSize {
+                size,
+                warn_at,
+                align,
+            } = syn::parse2::<FitsSize>(attr)?;
+            max_align = align;
+            (vec![(None, size)], warn_at)
+        }
+        Comparison::Ge => (vec![(None, syn::parse2::<ExpSize>(attr)?)], None),
+    };
+
+    if let Item::Type(alias) = &item {
+        let size = match arms.as_slice() {
+            [(None, size)] => size.to_string(),
+            _ => "SIZE".into(),
+        };
+        let form = match op {
+            Comparison::Eq => format!("{}, {}", alias.ident, size),
+            _ => format!("{} {} {}", alias.ident, tokens_to_string(&op), size),
+        };
+        return Err(syn::Error::new(
+            alias.ident.span(),
+            format!(
+                "attribute cannot check the size of type alias `{}`, use `assert_type_size!({})` instead",
+                alias.ident, form
+            ),
+        ));
+    }
+    let (id, ty, name) = match &fill {
+        Some(fill) => {
+            let (id, ty) = instantiated_type(&item, fill)?;
+            let name = qualified(&ty);
+            (id, ty, name)
+        }
+        None => {
+            let (id, ty) = item_type(&item)?;
+            (id, ty, qualified(id))
+        }
+    };
+
+    if let Some(name) = type_name {
+        if name != "self" && name != "Self" && name != *id {
+            return Err(syn::Error::new_spanned(
+                &name,
+                format!(
+                    "`{}` is not the annotated type, expected `{} = ...` or `self = ...`",
+                    name, id
+                ),
+            ));
+        }
+    }
+
+    let actual = quote!(::core::mem::size_of::<#ty>());
+    // Path of the checked type, as registered with `collect` and reported to the
+    // mismatch handler of `runtime-assert`.
+    let type_name = {
+        let name = match &fill {
+            Some(_) => tokens_to_string(&ty),
+            None => id.unraw().to_string(),
+        };
+        quote!(::core::concat!(::core::module_path!(), "::", #name))
+    };
+    // Checks and warnings of `test_only` are skipped by rustc in other builds.
+    let test_only = test_only.then(|| quote!(#[cfg(test)]));
+    // Runtime checks are only built when emitted, the const checks always are.
+    // Audited checks must not fail, neither at compile time nor in tests.
+    let runtime = (cfg!(feature = "runtime-assert") || paranoid) && !audit;
+    let mut checks = TokenStream2::new();
+    let mut runtime_checks = TokenStream2::new();
+    let mut reports = TokenStream2::new();
+    for (cfg, size) in &arms {
+        // Exact sizes are reported at the type, the other bounds at the value,
+        // `cfg`-keyed ones at their arm.
+        let span = match (cfg, op) {
+            (Some(cfg), _) => arm_span(cfg, size.span()),
+            (None, Comparison::Eq) => id.span(),
+            (None, _) => size.span(),
+        };
+        let target = match cfg {
+            Some(cfg) => format!(" on {}", tokens_to_string(cfg)),
+            None => String::new(),
+        };
+        let alternatives = match op {
+            Comparison::Eq => size_alternatives(&size.size)?,
+            _ => None,
+        };
+        let kind = match op {
+            Comparison::Eq => "assert_size",
+            Comparison::Le => "assert_size_fits",
+            Comparison::Ge => "assert_size_at_least",
+        };
+        let accepted = match &alternatives {
+            Some(alternatives) => alternatives.iter().collect::<Vec<_>>(),
+            None => vec![&size.size],
+        };
+        if let Some(report) = emit_report(
+            cfg.as_ref(),
+            &type_name,
+            kind,
+            &accepted,
+            quote!(::core::mem::size_of::<#ty>),
+        ) {
+            reports.extend(quote! {
+                #test_only
+                #report
+            });
+        }
+        // Alternatives have no single expected size to hand over to a handler.
+        let expected = alternatives.is_none().then(|| size.to_token_stream());
+        let (condition, mut message) = match alternatives {
+            Some(alternatives) => (
+                quote!(#(#actual == #alternatives)||*),
+                size.message(vec![
+                    MessagePart::Text(format!(
+                        "size of '{}' is not {} bytes{} (actual size: ",
+                        name,
+                        or_list(alternatives.iter().map(ToString::to_string)),
+                        target
+                    )),
+                    MessagePart::Value(actual.clone()),
+                    MessagePart::Text(" bytes)".into()),
+                ]),
+            ),
+            None => (
+                quote!(#actual #op #size),
+                size.message(op.message(&name, size, &target, actual.clone())),
+            ),
+        };
+        if let Some(abi) = &abi {
+            message.push(MessagePart::Text(format!(
+                ", which violates ABI {} layout",
+                abi.value()
+            )));
+        }
+        let cfg = cfg.as_ref().map(|cfg| quote!(#[cfg(#cfg)]));
+        let check = match audit {
+            true => emit_audit(span, condition.clone(), &message),
+            false => emit_check(span, condition.clone(), &message),
+        };
+        checks.extend(quote! {
+            #test_only
+            #cfg
+            #check
+        });
+        if runtime {
+            // The mismatch handler is a part of the facade with the feature only.
+            let condition = match expected {
+                Some(expected) if cfg!(feature = "runtime-assert") => quote! {
+                    #condition
+                        || ::pakr_assert_size::__private::reported(#type_name, #expected, #actual)
+                },
+                _ => condition,
+            };
+            let runtime_check = emit_runtime_check(condition, &message);
+            runtime_checks.extend(quote! {
+                #cfg
+                #runtime_check
+            });
+        }
+    }
+
+    if let Some(align) = &max_align {
+        // Only given for `#[assert_size_fits]`, which has a single arm.
+        let (_, size) = &arms[0];
+        let actual = quote!(::core::mem::align_of::<#ty>());
+        let condition = quote!(#actual <= #align);
+        let message = size.message(vec![
+            MessagePart::Text(format!(
+                "alignment of '{}' exceeds {} bytes (actual alignment: ",
+                name, align
+            )),
+            MessagePart::Value(actual.clone()),
+            MessagePart::Text(" bytes)".into()),
+        ]);
+        checks.extend(emit_check(align.span(), condition.clone(), &message));
+        if runtime {
+            runtime_checks.extend(emit_runtime_check(condition, &message));
+        }
+    }
+
+    let runtime = runtime.then(|| {
+        let attribute = match op {
+            Comparison::Eq => "assert_size",
+            Comparison::Le => "assert_size_fits",
+            Comparison::Ge => "assert_size_at_least",
+        };
+        // The expected sizes keep the names of several checks of one type apart.
+        let sizes = arms
+            .iter()
+            .map(|(_, size)| size.to_string())
+            .collect::<Vec<_>>()
+            .join("_");
+        let mut name = format!(
+            "{}_{}_{}",
+            screaming_snake_case(&id.unraw().to_string()).to_lowercase(),
+            attribute,
+            snake_case_words(&sizes)
+        );
+        // Attributes are expanded top to bottom, so the ones of the same kind
+        // still below this one number the checks stacked with the same sizes.
+        let below = item_attrs(&item)
+            .iter()
+            .filter(|attr| {
+                attr.path
+                    .segments
+                    .last()
+                    .is_some_and(|last| last.ident == attribute)
+            })
+            .count();
+        if below > 0 {
+            name.push_str(&format!("_stacked_{}", below));
+        }
+        let name = Ident::new(&name, Span::call_site());
+        // A test of an item in a function body is never run. rustc warns about
+        // it with `unnameable_test_items`, so it is not reported as unused too.
+        quote! {
+            #[cfg(all(test, debug_assertions))]
+            #[allow(dead_code)]
+            #[test]
+            fn #name() {
+                #runtime_checks
+            }
+        }
+    });
+
+    // Like the soft budget below, the warning is a call of a deprecated function.
+    let repr_warning = match &item {
+        Item::Struct(struct_item)
+            if check_repr
+                && !struct_item
+                    .attrs
+                    .iter()
+                    .any(|attr| attr.path.is_ident("repr")) =>
+        {
+            let note = format!(
+                "'{}' has no `#[repr(...)]`, so its layout may change between compiler \
+                 versions; add `#[repr(C)]` or `#[repr(Rust)]`, or `allow_default_repr` to \
+                 `#[assert_size]`",
+                id.unraw()
+            );
+            Some(quote! {
+                #test_only
+                const _: () = {
+                    #[deprecated(note = #note)]
+                    const fn default_repr() {}
+                    default_repr()
+                };
+            })
+        }
+        _ => None,
+    };
+
+    // Only zero-sized types fit in no bytes, which `#[assert_zst]` says plainly.
+    let zero_warning = arms
+        .iter()
+        .filter(|(_, size)| op == Comparison::Le && size.size.value == Some(0))
+        .map(|(_, size)| {
+            let note = format!(
+                "only zero-sized types fit in 0 bytes; check '{}' with `#[assert_zst]` instead",
+                id.unraw()
+            );
+            quote_spanned! {size.span()=>
+                const _: () = {
+                    #[deprecated(note = #note)]
+                    const fn fits_in_zero() {}
+                    fits_in_zero()
+                };
+            }
+        })
+        .collect::<TokenStream2>();
+
+    let warning = warn_at.map(|warn_at| {
+        // Only given for `#[assert_size_fits]`, which has a single arm.
+        let (_, size) = &arms[0];
+        let note = format!(
+            "'{}' exceeds the soft size budget of {} bytes (hard limit: {} bytes)",
+            id.unraw(),
+            warn_at,
+            size
+        );
+        quote! {
+            const _: () = {
+                struct SizeBudget<const EXCEEDED: bool>;
+                impl SizeBudget<true> {
+                    #[deprecated(note = #note)]
+                    const fn check() {}
+                }
+                impl SizeBudget<false> {
+                    const fn check() {}
+                }
+                SizeBudget::<{ #actual > #warn_at }>::check()
+            };
+        }
+    });
+
+    // The expected size, not the real one, so the const agrees with the check.
+    let mut exported = TokenStream2::new();
+    if let Some(export) = &export {
+        let vis = match &item {
+            Item::Struct(item) => &item.vis,
+            Item::Enum(item) => &item.vis,
+            Item::Union(item) => &item.vis,
+            _ => unreachable!("checked by `item_type`"),
+        };
+        let doc = format!("Size of [`{}`] in bytes, checked by `#[assert_size]`.", id);
+        for (cfg, size) in &arms {
+            if size_alternatives(&size.size)?.is_some() {
+                return Err(syn::Error::new(
+                    size.span(),
+                    "cannot export alternative sizes, give a single size with `export`",
+                ));
+            }
+            let value = match size.size.value {
+                Some(value) => Literal::usize_unsuffixed(value).into_token_stream(),
+                None => size.size.expr.to_token_stream(),
+            };
+            let cfg = cfg.as_ref().map(|cfg| quote!(#[cfg(#cfg)]));
+            exported.extend(quote! {
+                #cfg
+                #[doc = #doc]
+                #vis const #export: usize = #value;
+            });
+        }
+    }
+
+    // Documented under the predicates of the arms, so rustdoc states the size
+    // of the target it documents.
+    if doc {
+        let attrs = match &mut item {
+            Item::Struct(item) => &mut item.attrs,
+            Item::Enum(item) => &mut item.attrs,
+            Item::Union(item) => &mut item.attrs,
+            _ => unreachable!("checked by `item_type`"),
+        };
+        // An empty line separates the size from the documentation of the type.
+        attrs.push(syn::parse_quote!(#[doc = ""]));
+        let abi = abi
+            .as_ref()
+            .map(|abi| format!(" Part of ABI {}.", abi.value()))
+            .unwrap_or_default();
+        for (cfg, size) in &arms {
+            let text = match size.size.value {
+                Some(value) => {
+                    format!("Size: {} bytes, checked by `#[assert_size]`.{}", value, abi)
+                }
+                None => format!(
+                    "Size: `{}` bytes, checked by `#[assert_size]`.{}",
+                    size, abi
+                ),
+            };
+            attrs.push(match cfg {
+                Some(cfg) => syn::parse_quote!(#[cfg_attr(#cfg, doc = #text)]),
+                None => syn::parse_quote!(#[doc = #text]),
+            });
+        }
+    }
+
+    // Layouts do not depend on the optimization level, neither does the check;
+    // the flag states that the structure relies on it, for the reader and for
+    // a test run in both profiles.
+    if stable {
+        let attrs = match &mut item {
+            Item::Struct(item) => &mut item.attrs,
+            Item::Enum(item) => &mut item.attrs,
+            Item::Union(item) => &mut item.attrs,
+            _ => unreachable!("checked by `item_type`"),
+        };
+        attrs.push(syn::parse_quote!(#[doc = ""]));
+        attrs.push(syn::parse_quote!(
+            #[doc = "Size is the same in debug and release builds, checked by `#[assert_size]` in both."]
+        ));
+    }
+
+    let collect = (cfg!(feature = "collect") && op == Comparison::Eq).then(|| {
+        let cfgs = arms
+            .iter()
+            .filter_map(|(cfg, _)| cfg.as_ref())
+            .collect::<Vec<_>>();
+        let cfg = (!cfgs.is_empty()).then(|| quote!(#[cfg(any(#(#cfgs),*))]));
+        quote! {
+            #cfg
+            ::pakr_assert_size::__private::inventory::submit! {
+                ::pakr_assert_size::AssertedSize {
+                    type_name: #type_name,
+                    size: #actual,
+                }
+            }
+        }
+    });
+
+    let checker = quote! {
+        #checks
+        #exported
+        #repr_warning
+        #zero_warning
+        #warning
+        #runtime
+        #collect
+        #reports
+        #item
+    };
+
+    Ok(checker)
+}
+
+/// Expands `#[assert_size]` attached to an `impl` block, checking the size of a
+/// type in its scope. `Self` and `Self::Assoc` are resolved to the implementing
+/// type and to the associated type defined in the block.
+fn impl_size(attr: TokenStream, item: ItemImpl) -> syn::Result<TokenStream2> {
+    let ImplTypeSize { ty, size } = syn::parse::<ImplTypeSize>(attr)?;
+
+    if let Some(param) = item
+        .generics
+        .params
+        .iter()
+        .find(|param| !matches!(param, GenericParam::Lifetime(_)))
+    {
+        return Err(syn::Error::new_spanned(
+            param,
+            "cannot check sizes in a generic impl, check its instantiations with \
+             `assert_size_for!(TYPE = USIZE)` instead",
+        ));
+    }
+
+    let self_ty = &item.self_ty;
+    let (name, resolved) = match &ty {
+        Type::Path(path) if path.qself.is_none() && path.path.is_ident("Self") => {
+            (tokens_to_string(self_ty), self_ty.to_token_stream())
+        }
+        Type::Path(path)
+            if path.qself.is_none()
+                && path.path.segments.len() == 2
+                && path.path.segments[0].ident == "Self" =>
+        {
+            let assoc = &path.path.segments[1].ident;
+            let name = match &item.trait_ {
+                Some((_, trait_path, _)) => format!(
+                    "<{} as {}>::{}",
+                    tokens_to_string(self_ty),
+                    tokens_to_string(trait_path),
+                    assoc
+                ),
+                None => format!("{}::{}", tokens_to_string(self_ty), assoc),
+            };
+            let resolved = item
+                .items
+                .iter()
+                .find_map(|impl_item| match impl_item {
+                    ImplItem::Type(impl_type) if impl_type.ident == *assoc => {
+                        Some(impl_type.ty.to_token_stream())
+                    }
+                    _ => None,
+                })
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        assoc,
+                        format!("no associated type `{}` in this impl", assoc),
+                    )
+                })?;
+            (name, resolved)
+        }
+        _ => (tokens_to_string(&ty), ty.to_token_stream()),
+    };
+    let resolved = static_lifetimes(&resolved, &item.generics);
+
+    let check = emit_assert(
+        &name,
+        &resolved,
+        Comparison::Eq,
+        &size.size,
+        size.msg.as_ref(),
+        "",
+    );
+    let checker = quote! {
+        #check
+        #item
+    };
+
+    Ok(checker)
+}
+
+/// Expands `#[assert_size(lock)]`, recording the size of `item` in a const named
+/// after it, e.g. `HEADER_SIZE` for `Header`, with the visibility of `item`.
+fn lock_size(item: Item) -> syn::Result<TokenStream2> {
+    let (id, ty) = item_type(&item)?;
+    let vis = match &item {
+        Item::Struct(item) => &item.vis,
+        Item::Enum(item) => &item.vis,
+        Item::Union(item) => &item.vis,
+        _ => unreachable!("checked by `item_type`"),
+    };
+
+    let name = Ident::new(
+        &format!("{}_SIZE", screaming_snake_case(&id.unraw().to_string())),
+        id.span(),
+    );
+    let doc = format!(
+        "Size of [`{}`] in bytes, recorded by `#[assert_size(lock)]`.",
+        id
+    );
+    let checker = quote! {
+        #[doc = #doc]
+        #vis const #name: usize = ::core::mem::size_of::<#ty>();
+        #item
+    };
+
+    Ok(checker)
+}
+
+/// Converts a `CamelCase` identifier to `SCREAMING_SNAKE_CASE`, keeping acronyms
+/// together, e.g. `HttpHeaderV2` and `HTTPHeaderV2` to `HTTP_HEADER_V2`.
+fn screaming_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_uppercase());
+    }
+    out
+}
+
+/// Turns arbitrary text into lowercase words joined with `_`, usable in an
+/// identifier, e.g. `4 * size_of::<u64>()` into `4_size_of_u64`.
+fn snake_case_words(text: &str) -> String {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Generates a check comparing the size of `ty`, called `name` in the failure
+/// message, to `size` with `op`.
+///
+/// The failure message is `msg`, if given, or the default one of `op`, with
+/// `target` describing the compilation target the check applies to.
+fn emit_assert<T: ToTokens>(
+    name: &str,
+    ty: &T,
+    op: Comparison,
+    size: &SizeExpr,
+    msg: Option<&LitStr>,
+    target: &str,
+) -> TokenStream2 {
+    let actual = quote!(::core::mem::size_of::<#ty>());
+    let message = match msg {
+        Some(msg) => vec![MessagePart::Text(msg.value())],
+        None => op.message(name, size, target, actual.clone()),
+    };
+    emit_check(size.span(), quote!(#actual #op #size), &message)
+}
+
+/// Expands `#[assert_size_in_words]` and, if `align` is set,
+/// `#[assert_align_in_words]`, comparing the size (alignment) of `item` to the
+/// given number of words.
+fn expand_words(attr: TokenStream, item: TokenStream, align: bool) -> syn::Result<TokenStream2> {
+    let WordCount { count, word, msg } = syn::parse::<WordCount>(attr)?;
+
+    let item = syn::parse::<Item>(item)?;
+    let (id, ty) = item_type(&item)?;
+
+    let word = match word {
+        Some(word) => word,
+        None => syn::parse_quote!(8),
+    };
+    let bytes = match (count.value, word.value) {
+        (Some(count), Some(word)) => count
+            .checked_mul(word)
+            .ok_or_else(|| syn::Error::new(Span::call_site(), "size does not fit in `usize`"))?
+            .to_string(),
+        _ => format!("{} * {}", count, word),
+    };
+
+    let (actual, text) = if align {
+        (
+            quote!(::core::mem::align_of::<#ty>()),
+            format!(
+                "alignment of '{}' is not {} words ({} bytes) (actual alignment: ",
+                qualified(id),
+                count,
+                bytes
+            ),
+        )
+    } else {
+        (
+            quote!(::core::mem::size_of::<#ty>()),
+            format!(
+                "'{}' is not {} words ({} bytes) (actual size: ",
+                qualified(id),
+                count,
+                bytes
+            ),
+        )
+    };
+    let span = count.span();
+    let condition = quote!(#actual == #count * #word);
+    let size = ExpSize { size: count, msg };
+    let check = emit_check(
+        span,
+        condition,
+        &size.message(vec![
+            MessagePart::Text(text),
+            MessagePart::Value(actual.clone()),
+            MessagePart::Text(" bytes)".into()),
+        ]),
+    );
+
+    let checker = quote! {
+        #check
+        #item
+    };
+
+    Ok(checker)
+}
+
+/// Extracts the identifier of the type defined by `item` and the type to be
+/// checked, as returned by `self_type`.
+///
+/// Only structures, enumerations and unions are accepted, as these are the only
+/// items that define a type whose size can be taken. Type aliases are pointed
+/// to the function-like `assert_type_size!`, which accepts any type.
+fn item_type(item: &Item) -> syn::Result<(&Ident, TokenStream2)> {
+    let (ident, generics) = match item {
+        Item::Struct(item) => (&item.ident, &item.generics),
+        Item::Enum(item) => (&item.ident, &item.generics),
+        Item::Union(item) => (&item.ident, &item.generics),
+        Item::Type(alias) => {
+            return Err(syn::Error::new(
+                alias.ident.span(),
+                format!(
+                    "expected struct, enum or union, found type alias `{}`; check the aliased type with `assert_type_size!` instead",
+                    alias.ident
+                ),
+            ))
+        }
+        _ => {
+            return Err(syn::Error::new_spanned(
+                item,
+                "expected struct, enum or union",
+            ))
+        }
+    };
+    Ok((ident, self_type(ident, generics)?))
+}
+
+/// Returns the attributes of `item`, for the items checked by the attributes.
+fn item_attrs(item: &Item) -> &[Attribute] {
+    match item {
+        Item::Struct(item) => &item.attrs,
+        Item::Enum(item) => &item.attrs,
+        Item::Union(item) => &item.attrs,
+        _ => &[],
+    }
+}
+
+/// Returns the identifier of the generic `item` and its instantiation with the
+/// parameters substituted by `fill`, lifetimes set to `'static`.
+///
+/// Every type and const parameter needs a substitution, and every substitution
+/// a parameter.
+fn instantiated_type<'a>(
+    item: &'a Item,
+    fill: &PhantomFill,
+) -> syn::Result<(&'a Ident, TokenStream2)> {
+    let (ident, generics) = match item {
+        Item::Struct(item) => (&item.ident, &item.generics),
+        Item::Enum(item) => (&item.ident, &item.generics),
+        Item::Union(item) => (&item.ident, &item.generics),
+        _ => return item_type(item),
+    };
+    if generics.type_params().next().is_none() && generics.const_params().next().is_none() {
+        return Err(syn::Error::new(
+            ident.span(),
+            format!(
+                "`phantom_fill` instantiates type or const parameters, `{}` has none",
+                ident
+            ),
+        ));
+    }
+
+    if let PhantomFill::Each(fills) = fill {
+        for arg in fills {
+            let known = generics.type_params().any(|param| param.ident == arg.name)
+                || generics.const_params().any(|param| param.ident == arg.name);
+            if !known {
+                return Err(syn::Error::new(
+                    arg.name.span(),
+                    format!("`{}` is not a parameter of `{}`", arg.name, ident),
+                ));
+            }
+        }
+    }
+
+    let given = |name: &Ident| match fill {
+        PhantomFill::All(_) => None,
+        PhantomFill::Each(args) => args
+            .iter()
+            .find(|arg| arg.name == *name)
+            .map(|arg| arg.value.to_token_stream()),
+    };
+    let mut args = Vec::new();
+    for param in &generics.params {
+        let arg = match param {
+            GenericParam::Lifetime(_) => quote!('static),
+            GenericParam::Type(param) => match (given(&param.ident), fill) {
+                (Some(value), _) => value,
+                (None, PhantomFill::All(ty)) => ty.to_token_stream(),
+                (None, PhantomFill::Each(_)) => {
+                    return Err(syn::Error::new_spanned(
+                        param,
+                        format!(
+                            "no substitution for type parameter `{}` in `phantom_fill`",
+                            param.ident
+                        ),
+                    ))
+                }
+            },
+            GenericParam::Const(param) => given(&param.ident).ok_or_else(|| {
+                syn::Error::new_spanned(
+                    param,
+                    format!(
+                        "no value for const parameter `{}`, give it as `phantom_fill({} = ...)`",
+                        param.ident, param.ident
+                    ),
+                )
+            })?,
+        };
+        args.push(arg);
+    }
+
+    Ok((ident, quote!(#ident<#(#args),*>)))
+}
+
+/// Returns the type `ident` with all its lifetime parameters set to `'static`,
+/// which does not change the layout.
+///
+/// Types with type or const parameters have no layout on their own, so an error
+/// pointing to the explicit-instantiation form is returned instead.
+fn self_type(ident: &Ident, generics: &Generics) -> syn::Result<TokenStream2> {
+    if let Some(param) = generics
+        .params
+        .iter()
+        .find(|param| !matches!(param, GenericParam::Lifetime(_)))
+    {
+        return Err(syn::Error::new_spanned(
+            param,
+            format!(
+                "cannot check generic type `{}`, check its instantiations with \
+                 `assert_size_for!({}<...> = USIZE)` instead",
+                ident, ident
+            ),
+        ));
+    }
+
+    if generics.params.is_empty() {
+        Ok(quote!(#ident))
+    } else {
+        let lifetimes = generics.params.iter().map(|_| quote!('static));
+        Ok(quote!(#ident<#(#lifetimes),*>))
+    }
+}
+
+/// Replaces lifetime parameters of `generics` in `tokens` (e.g. a field type
+/// `&'a u8`) with `'static`, so they can be used outside of the item.
+fn static_lifetimes<T: ToTokens>(tokens: &T, generics: &Generics) -> TokenStream2 {
+    fn replace(tokens: TokenStream2, generics: &Generics) -> TokenStream2 {
+        let mut out = TokenStream2::new();
+        let mut iter = tokens.into_iter().peekable();
+        while let Some(token) = iter.next() {
+            match token {
+                TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                    let is_param = match iter.peek() {
+                        Some(TokenTree::Ident(name)) => generics
+                            .lifetimes()
+                            .any(|param| param.lifetime.ident == *name),
+                        _ => false,
+                    };
+                    if is_param {
+                        iter.next();
+                        out.extend(quote!('static));
+                    } else {
+                        out.extend(Some(TokenTree::Punct(punct)));
+                    }
+                }
+                TokenTree::Group(group) => {
+                    let mut replaced = proc_macro2::Group::new(
+                        group.delimiter(),
+                        replace(group.stream(), generics),
+                    );
+                    replaced.set_span(group.span());
+                    out.extend(Some(TokenTree::Group(replaced)));
+                }
+                token => out.extend(Some(token)),
+            }
+        }
+        out
+    }
+
+    replace(tokens.to_token_stream(), generics)
+}
+
+/// Returns the representation hints given in `#[repr(...)]` among `attrs`.
+fn reprs(attrs: &[Attribute]) -> impl Iterator<Item = NestedMeta> + '_ {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("repr"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+}
+
+/// Returns representation hint `nested` in a form comparable regardless of how
+/// it is written: its name and its integer argument, if any, e.g. `align(0x10)`
+/// as `("align", Some(16))`. Bare `packed` stands for `packed(1)`.
+fn repr_hint(nested: &NestedMeta) -> (String, Option<u128>) {
+    let argument = match nested {
+        NestedMeta::Meta(Meta::List(list)) => match list.nested.iter().collect::<Vec<_>>()[..] {
+            [NestedMeta::Lit(Lit::Int(lit))] => lit.base10_parse::<u128>().ok(),
+            _ => None,
+        },
+        NestedMeta::Meta(Meta::Path(path)) if path.is_ident("packed") => Some(1),
+        _ => None,
+    };
+    match (nested, argument) {
+        (NestedMeta::Meta(meta), Some(argument)) => (tokens_to_string(meta.path()), Some(argument)),
+        _ => (tokens_to_string(nested), None),
+    }
+}
+
+/// Finds the integer type given in `#[repr(...)]` among `attrs`.
+fn repr_int(attrs: &[Attribute]) -> Option<Ident> {
+    const INTS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+
+    reprs(attrs).find_map(|nested| match nested {
+        NestedMeta::Meta(Meta::Path(path)) => path
+            .get_ident()
+            .filter(|ident| INTS.iter().any(|int| *ident == int))
+            .cloned(),
+        _ => None,
+    })
+}
+
+/// The attribute `#[assert_size(USIZE)]` performs **compile-time** check, if the
+/// structure it is attached to has the exact size in bytes.
+///
+/// The attribute can be attached to a `struct`, an `enum` or a `union`. Lifetime
+/// parameters of the type are set to `'static` for the check, which does not
+/// change the layout. Arguments in none of the forms described below are
+/// rejected with a list of the accepted ones. Types with type or const
+/// parameters are rejected, as their size depends on the instantiation; check
+/// them with `assert_size_for!`.
+///
+/// The size is always the one of the declared type as written, so a generic
+/// type is only checked with its parameters spelled out. Next to the
+/// definition, they are given by `phantom_fill`, either one type for every
+/// type parameter, `#[assert_size(16, phantom_fill = "u64")]`, or a value for
+/// each parameter, `#[assert_size(12, phantom_fill(T = u32, N = 3))]`, which
+/// const parameters always need; the check is then the one of
+/// `size_of::<Pair<u64>>()`. Lifetimes are set to `'static` as usual, every
+/// other parameter needs a substitution and every substitution a parameter.
+///
+/// The expected size may be given either as an integer literal or as any
+/// expression that can be evaluated in const context, e.g.
+/// `#[assert_size(4 * core::mem::size_of::<u64>())]` or a path to a named
+/// `const` such as `#[assert_size(proto::HEADER_LEN)]`. In the latter case the
+/// failure message quotes the expression as written.
+///
+/// Paths may have any number of segments, e.g. a const written by a build script
+/// into a generated module, `#[assert_size(crate::generated::HEADER_SIZE)]`. They
+/// are spliced into the check verbatim and resolved where the attribute is used.
+/// Items of a module, or of a function body, are visible regardless of their
+/// order, so the const may as well be declared below the structure.
+///
+/// Literal sizes may be given in any radix (`0x40`, `0o100`, `0b100_0000`) and
+/// with a unit suffix: `KB`, `MB` and `GB` for powers of 1000, `KiB`, `MiB` and
+/// `GiB` for powers of 1024, e.g. `#[assert_size(4KiB)]`. The `ptr` suffix
+/// counts pointers of the target, e.g. `#[assert_size(2ptr)]` is equivalent to
+/// `#[assert_size(2 * core::mem::size_of::<usize>())]`. An integer type suffix,
+/// e.g. `#[assert_size(16u32)]`, is ignored, so `16usize` and `16u32` both mean
+/// 16 bytes; a value beyond the range of the type is an error.
+///
+/// When the size legitimately differs between configurations that cannot be
+/// told apart by `cfg`, it may be given as `|`-separated alternatives, e.g.
+/// `#[assert_size(8 | 16)]`, requiring the size to equal any of them. A bitwise
+/// or meant as the size itself has to be parenthesized, e.g. `(A | B)`.
+///
+/// When the size legitimately differs between targets, it may be given as
+/// comma-separated `cfg`-keyed arms, e.g.
+/// `#[assert_size(target_pointer_width = "64" => 24, target_pointer_width = "32" => 12)]`.
+/// Any `cfg` predicate may key an arm, e.g. `unix`, `target_os = "macos"` or
+/// `any(target_os = "linux", target_os = "android")`, for types differing by
+/// operating system like the ones of `libc`. Each arm is checked only when its
+/// predicate holds, as if the check was annotated with `#[cfg(...)]`. Targets
+/// matching no arm are not checked at all, no assertion is emitted for them;
+/// add an arm keyed by `not(any(...))` of the others to cover them.
+///
+/// The default failure message may be replaced with a custom one, explaining
+/// *why* the size matters, with `#[assert_size(USIZE, msg = "...")]`.
+///
+/// To guard against attributes copied along with another type, the expected size
+/// may be preceded by the name of the annotated type, `#[assert_size(Header = 16)]`,
+/// or by `self`, `#[assert_size(self = 16)]`. A name other than the one of the
+/// annotated type is an error.
+///
+/// The check refers to the type by its name, so it observes the final layout
+/// whatever other macros do around it: derives only add items next to the
+/// type, and fields injected by an enclosing `macro_rules!` or by another
+/// attribute are part of the type being measured. Fields cannot be given by a
+/// macro in the body of the structure, like `include!("fields.rs")`: Rust does
+/// not expand macros in place of fields, and the attribute sees the body as
+/// written, so it reports the macro. Generated code has to include the whole
+/// structure, along with its attributes, instead.
+///
+/// An attribute macro replacing the structure, like `#[bitfield]` of
+/// `modular-bitfield` turning the fields into a byte array, is order-sensitive:
+/// attributes above it are expanded first and pass the structure on unchanged,
+/// while those below it are only kept if the macro copies them to its output.
+/// Put `#[assert_size]` above such a macro, so it measures the replaced
+/// structure by its name; it sees the attributes as written though, so add
+/// `allow_default_repr` where the macro supplies the `#[repr(...)]`. Checks
+/// reading the fields would see them before the rewrite. Where the order cannot
+/// be controlled, check the type after its declaration with
+/// `assert_type_size!(Type, USIZE)`, which does not depend on it.
+///
+/// Any number of checks may be stacked on one type, e.g. `#[assert_size]`,
+/// `#[assert_align]` and `#[assert_no_padding]`: every check is an anonymous
+/// const, and the named items, the consts of `export` and `lock` and the tests
+/// of `runtime-assert`, are named after the type.
+///
+/// A failed check is reported at the name of the annotated type, so editors
+/// highlight the offending one among many structures of the same size. Checks
+/// in an `impl` block are reported at the expected size instead, and a failing
+/// `cfg`-keyed arm at the arm itself, on its own line of a multi-line attribute.
+///
+/// An exact size only holds as long as the layout does, and the default layout
+/// of a structure without `#[repr(...)]` is not guaranteed to stay the same
+/// between compiler versions. Such a structure gets a deprecation warning, at
+/// the attribute, suggesting to add `#[repr(C)]`, or `#[repr(Rust)]` to make the
+/// choice explicit. Where the default layout is fine, silence the warning with
+/// `#[assert_size(USIZE, allow_default_repr)]`. Within a single build the layout
+/// never changes, so a check of a structure outside of FFI, kept to catch a
+/// field added by mistake, is valid without `#[repr(C)]`: a new compiler may
+/// only make it fail, never let a wrong size pass.
+///
+/// Attached to an `impl` block, the attribute checks the size of a type in its
+/// scope instead, given as `#[assert_size(Self::Assoc = USIZE)]` for an
+/// associated type defined in the block, `#[assert_size(Self = USIZE)]` for the
+/// implementing type, or a type path `#[assert_size(TYPE = USIZE)]`. The block
+/// must not have type or const parameters.
+///
+/// The expected size may be exported for other code with
+/// `#[assert_size(16, export = HEADER_SIZE)]`, which additionally generates a
+/// documented `const HEADER_SIZE: usize = 16;` with the visibility of the
+/// structure, so the number is written once; with `cfg`-keyed arms, one const
+/// is generated for each of them, under its predicate. Alternative sizes cannot
+/// be exported. Being a `const`, not a `static`, it can be used wherever a const
+/// expression is expected, like the length of an array or a const generic
+/// argument.
+///
+/// With `#[assert_size(16, test_only)]` the checks are generated under
+/// `#[cfg(test)]`, so only test builds evaluate them. This trades the safety of
+/// every build for build time in crates carrying many checks; the attribute is
+/// still expanded, and an `export` const is generated regardless.
+///
+/// To introduce checks in a large code base without breaking its build, a
+/// mismatch of `#[assert_size(16, audit)]` is a warning instead of an error,
+/// `use of deprecated associated function`, noting the expected size; the
+/// actual size is not known to the note. Remove the flag once the size is
+/// right, as the warning does not fail the build unless denied, e.g. with
+/// `-D warnings`.
+///
+/// For documentation of an ABI, `#[assert_size(16, doc)]` appends the expected
+/// size to the documentation of the type, ``Size: 16 bytes, checked by
+/// `#[assert_size]`.``, in a paragraph of its own; for `cfg`-keyed arms, the one
+/// of the documented target. The alignment is only known when the check is
+/// evaluated, after the documentation is written, so it is not stated.
+///
+/// Sizes of a stable ABI may carry its version, `#[assert_size(16, abi = "1.2")]`,
+/// so a changed size is tied to a bump of the version in review. The version is
+/// appended to the failure message, `..., which violates ABI 1.2 layout`, and,
+/// with `doc`, to the documented size.
+///
+/// Safety-critical code relying on the size being the same in debug and release
+/// builds may state it with `#[assert_size(16, stable_across_opt)]`. The macro
+/// cannot tell the optimization level, and layouts do not depend on it, so the
+/// check is the same; the flag marks the intent, appending ``Size is the same in
+/// debug and release builds, checked by `#[assert_size]` in both.`` to the
+/// documentation of the type. Build the checks in both profiles to hold the
+/// intent, e.g. `cargo test` and `cargo test --release` in CI.
+///
+/// In fast-moving code the size may be locked instead of checked with
+/// `#[assert_size(lock)]`, which records the current size in a const named
+/// after the structure, e.g. `HEADER_SIZE` for `Header`, with the visibility of
+/// the structure. As macros are stateless, pin the const against the number in
+/// a file under version control, e.g. a test or a `const` assertion next to the
+/// snapshot; every change of the size then shows up in the review diff.
+///
+/// It uses the newly stabilized usage of `panic!` in const context to perform
+/// check and early bailout. The generated code only refers to `core`, so it
+/// works in `#![no_std]` crates as well. With the `runtime-assert` feature, the
+/// check is additionally repeated in a generated `#[test]`, e.g.
+/// `header_assert_size_16` for `Header`, compiled for debug test builds only.
+/// A mismatch found there goes to the handler registered with
+/// `set_mismatch_handler()`, if any, instead of panicking.
+///
+/// A single check may be repeated at runtime without the feature with
+/// `#[assert_size(16, paranoid)]`, for targets where the const evaluation is
+/// not trusted to agree with the layout of the compiled code. The same
+/// `#[test]` is generated, compiled for debug test builds only, and panics on a
+/// mismatch; the handler is only consulted with the feature. Like every test,
+/// it is not run for items in function bodies, which rustc warns about with
+/// `unnameable_test_items`. Audited checks must not fail, so `paranoid` cannot
+/// be combined with `audit`.
+///
+/// With the `collect` feature, the checked size is also registered along with
+/// the path of the structure, so all of them can be listed at runtime with
+/// `asserted_sizes()`, e.g. to write a manifest of an ABI surface. With the
+/// `json-report` feature, the check is registered with its expected and actual
+/// size for `layout_report()`, a JSON report of all checks for CI.
+///
+/// The attribute may be applied conditionally with `cfg_attr`, e.g.
+/// `#[cfg_attr(feature = "strict", assert_size(16))]`; nothing is generated when
+/// the predicate does not hold. A `#[cfg]` on the structure itself, above or
+/// below the attribute, is resolved by rustc before the attribute is expanded,
+/// so a structure configured out takes its checks along, leaving nothing that
+/// refers to it. All checks are anonymous consts, so any number of
+/// them may be applied to one structure. Anonymous consts are items, valid at
+/// module scope as well as among the statements of a function body, so
+/// structures declared inside a function are checked the same way.
+///
+/// All paths in the generated code are absolute, e.g. `::core::mem::size_of`,
+/// so it depends on no imports, and resolves the same in crates of the 2015,
+/// 2018 and 2021 editions. When another crate's `assert_size` is in scope,
+/// the attribute may be invoked by its full path instead,
+/// `#[pakr_assert_size::assert_size(16)]`.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to the following, where `ActualSize` is the real size of the
+/// structure and `RoundedSize` the next power of two it rounds up to, e.g. to
+/// judge the allocator bucket it lands in, both formatted into the message at
+/// compile time. The name of the structure is written with the path of its
+/// module, `module_path!()`, e.g. `my_crate::net::StructName`, to tell apart
+/// types of the same name in a workspace; so are the names in the messages of
+/// the other attributes. A literal size of at least 1 KiB is followed by its
+/// value in binary units, e.g. `1048576 bytes (1 MiB)`; a size given as an
+/// expression is written as is
+/// ```
+/// # struct StructName{}
+/// # const ExpectedSize: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() == ExpectedSize,
+///    "size of 'ModulePath::StructName' is not ExpectedSize bytes (actual size: \
+///     ActualSize bytes, next power of two: RoundedSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (real size matches expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C, packed)]
+/// #[assert_size(16)]
+/// struct A {
+///     field1: u64,
+///     field2: u64,
+/// }
+/// ```
+///
+/// Success (real size matches expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(24)]
+/// #[repr(C, packed)]
+/// struct B {
+///     field1: u64,
+///     field2: u64,
+///     field3: u64,
+/// }
+/// ```
+///
+/// Success (tuple and unit structures):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(3)]
+/// struct Rgb(u8, u8, u8);
+///
+/// #[assert_size(0)]
+/// struct Marker;
+/// ```
+///
+/// Success (opaque FFI handles, declared with a zero-length array or a
+/// `PhantomData` of a raw pointer, stay zero-sized):
+///
+/// ```
+/// # use pakr_assert_size::*;
+/// use core::marker::{PhantomData, PhantomPinned};
+///
+/// #[assert_size(0)]
+/// #[repr(C)]
+/// pub struct Opaque {
+///     _data: [u8; 0],
+///     _marker: PhantomData<(*mut u8, PhantomPinned)>,
+/// }
+///
+/// #[assert_size(0)]
+/// #[repr(C)]
+/// pub struct Handle(PhantomData<*mut ()>);
+/// ```
+///
+/// Success (expected size exported for a buffer holding the structure):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(8, export = HEADER_SIZE)]
+/// #[repr(C)]
+/// pub struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// pub fn header_buffer() -> [u8; HEADER_SIZE] {
+///     [0; HEADER_SIZE]
+/// }
+///
+/// pub struct Frame {
+///     header: [u8; HEADER_SIZE],
+///     body: [u8; 4 * HEADER_SIZE],
+/// }
+/// # assert_eq!(header_buffer().len(), 8);
+/// # assert_eq!(core::mem::size_of::<Frame>(), 40);
+/// ```
+///
+/// Success (mismatch of an audited check warns instead of failing):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(4, audit)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+/// ```
+///
+/// Success (expected size stated in the documentation of the type):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// /// Header of a frame sent over the wire.
+/// #[assert_size(8, doc)]
+/// #[repr(C)]
+/// pub struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+/// ```
+///
+/// Success (size relied on in debug and release builds alike):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(16, stable_across_opt)]
+/// #[repr(C)]
+/// pub struct Command {
+///     id: u32,
+///     flags: u32,
+///     deadline: u64,
+/// }
+/// ```
+///
+/// Failure (size of version 1.2 of the ABI changed):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(8, abi = "1.2", doc)]
+/// #[repr(C)]
+/// pub struct Header {
+///     tag: u32,
+///     len: u32,
+///     flags: u32,
+/// }
+/// ```
+///
+/// Success (check of `test_only` skipped outside test builds, as in a
+/// doctest):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(4, test_only)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+/// ```
+///
+/// Success (generic structure checked for the substituted type parameter):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(16, phantom_fill = "u64")]
+/// #[repr(C)]
+/// struct Pair<T> {
+///     first: T,
+///     second: T,
+/// }
+///
+/// #[assert_size(12, phantom_fill(T = u32, N = 3))]
+/// #[repr(C)]
+/// struct Array<T, const N: usize> {
+///     items: [T; N],
+/// }
+/// ```
+///
+/// Failure (real size of `Pair<u8>` is 2 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(16, phantom_fill = "u8")]
+/// #[repr(C)]
+/// struct Pair<T> {
+///     first: T,
+///     second: T,
+/// }
+/// ```
+///
+/// Success (helper structure declared inside a function):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// fn checksum(data: &[u8]) -> u32 {
+///     #[assert_size(8)]
+///     #[repr(C)]
+///     struct State {
+///         sum: u32,
+///         count: u32,
+///     }
+///
+///     let mut state = State { sum: 0, count: 0 };
+///     for byte in data {
+///         state.sum = state.sum.wrapping_add(u32::from(*byte));
+///         state.count += 1;
+///     }
+///     state.sum ^ state.count
+/// }
+/// # assert_eq!(checksum(&[1, 2]), 3 ^ 2);
+/// ```
+///
+/// Failure (real size of the structure inside a function is 8 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// fn main() {
+///     #[assert_size(4)]
+///     #[repr(C)]
+///     struct State {
+///         sum: u32,
+///         count: u32,
+///     }
+/// }
+/// ```
+///
+/// Success (structure with lifetime parameters):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(16)]
+/// struct Ref<'a, 'b>
+/// where
+///     'a: 'b,
+/// {
+///     first: &'a u64,
+///     second: &'b u64,
+/// }
+/// ```
+///
+/// Success (default layout accepted, no warning about the missing `#[repr]`):
+///
+/// ```
+/// #![deny(deprecated)]
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(8, allow_default_repr)]
+/// struct Span {
+///     start: u32,
+///     end: u32,
+/// }
+/// ```
+///
+/// Failure (warning about the missing `#[repr]` denied):
+/// ```compile_fail
+/// #![deny(deprecated)]
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(8)]
+/// struct Span {
+///     start: u32,
+///     end: u32,
+/// }
+/// ```
+///
+/// Failure (structure with a type parameter):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(8)]
+/// struct Wrapper<T> {
+///     value: T,
+/// }
+/// ```
+///
+/// Success (attribute attached to an enumeration):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(1)]
+/// #[repr(u8)]
+/// enum Tag {
+///     First,
+///     Second,
+///     Third,
+/// }
+/// ```
+///
+/// Success (attribute attached to a union):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(8)]
+/// #[repr(C)]
+/// union Word {
+///     bytes: [u8; 8],
+///     value: u64,
+/// }
+/// ```
+///
+/// Success (expected size given as an arithmetic expression):
+///
+/// ```
+/// # use pakr_assert_size::*;
+/// use core::mem::size_of;
+///
+/// #[assert_size(3 * size_of::<u64>())]
+/// #[repr(C, packed)]
+/// struct D {
+///     field1: u64,
+///     field2: u64,
+///     field3: u64,
+/// }
+/// ```
+///
+/// Success (expected size referencing size of another type):
+///
+/// ```
+/// # use pakr_assert_size::*;
+/// use core::mem::size_of;
+///
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// #[assert_size(size_of::<Header>() + 8)]
+/// #[repr(C)]
+/// struct Packet {
+///     header: Header,
+///     payload: u64,
+/// }
+/// ```
+///
+/// Success (expected size referencing named constants):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// mod proto {
+///     pub const HEADER_LEN: usize = 20;
+/// }
+///
+/// const TRAILER_LEN: usize = 4;
+///
+/// #[assert_size(proto::HEADER_LEN)]
+/// #[repr(C)]
+/// struct Header {
+///     bytes: [u8; 20],
+/// }
+///
+/// #[assert_size(TRAILER_LEN)]
+/// #[repr(C)]
+/// struct Trailer {
+///     crc: u32,
+/// }
+/// ```
+///
+/// Success (named constant declared below the structure):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(HEADER_LEN)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// const HEADER_LEN: usize = 8;
+/// ```
+///
+/// Failure (real size is 24 bytes), the build aborts with
+/// `size of 'Header' is not proto::HEADER_LEN bytes (actual size: 24 bytes, next
+/// power of two: 32 bytes)`:
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// mod proto {
+///     pub const HEADER_LEN: usize = 20;
+/// }
+///
+/// #[assert_size(proto::HEADER_LEN)]
+/// #[repr(C)]
+/// struct Header {
+///     bytes: [u8; 24],
+/// }
+/// ```
+///
+/// Success (expected size generated by a build script, e.g. included with
+/// `include!(concat!(env!("OUT_DIR"), "/sizes.rs"))`):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// mod generated {
+///     pub mod sizes {
+///         pub const HEADER_SIZE: usize = 16;
+///     }
+/// }
+///
+/// #[assert_size(crate::generated::sizes::HEADER_SIZE)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u64,
+///     len: u64,
+/// }
+/// #
+/// # fn main() {}
+/// ```
+///
+/// Failure (real size is 8 bytes, generated size is 16 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// mod generated {
+///     pub mod sizes {
+///         pub const HEADER_SIZE: usize = 16;
+///     }
+/// }
+///
+/// #[assert_size(crate::generated::sizes::HEADER_SIZE)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+/// #
+/// # fn main() {}
+/// ```
+///
+/// Success (size is any of the alternatives):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(8 | 16)]
+/// #[repr(C)]
+/// struct Handle {
+///     id: u64,
+/// }
+/// ```
+///
+/// Failure (real size is 12 bytes, neither 8 nor 16 bytes):
+///
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(8 | 16)]
+/// #[repr(C)]
+/// struct Handle {
+///     id: [u32; 3],
+/// }
+/// ```
+///
+/// Success (associated type checked at the trait impl):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// trait Codec {
+///     type Frame;
+/// }
+///
+/// struct Modbus;
+///
+/// #[assert_size(Self::Frame = 8)]
+/// impl Codec for Modbus {
+///     type Frame = [u16; 4];
+/// }
+/// ```
+///
+/// Success (invoked by full path, with no imports and `core` shadowed by a module):
+///
+/// ```
+/// mod core {}
+///
+/// #[pakr_assert_size::assert_size(8)]
+/// #[pakr_assert_size::assert_size_fits(16)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+/// ```
+///
+/// Success (applied conditionally, the check of 1 byte is not applied at all):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[cfg_attr(all(), assert_size(8))]
+/// #[cfg_attr(any(), assert_size(1))]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+/// ```
+///
+/// Success (size locked into a generated const):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(lock)]
+/// #[repr(C)]
+/// pub struct HttpHeader {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// // Committed snapshot, failing the build whenever the size changes
+/// const _: () = assert!(HTTP_HEADER_SIZE == 8);
+/// ```
+///
+/// Success (structures generated by `macro_rules!`, expected size given by a
+/// metavariable):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// macro_rules! register {
+///     ($name:ident, $ty:ty, $size:expr) => {
+///         #[assert_size($size)]
+///         #[repr(C)]
+///         struct $name {
+///             value: $ty,
+///         }
+///     };
+/// }
+///
+/// register!(Control, u32, 4);
+/// register!(Counter, u64, 2 * 4);
+/// register!(Window, [u8; 1024], 1KiB);
+/// ```
+///
+/// Success (expected size given as hexadecimal, octal or binary literal):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(0x40)]
+/// #[assert_size(0o100)]
+/// #[assert_size(0b100_0000)]
+/// #[assert_size(64)]
+/// #[repr(C)]
+/// struct Block {
+///     bytes: [u8; 64],
+/// }
+/// ```
+///
+/// Success (expected size given with a unit suffix):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(4KiB)]
+/// #[repr(C, align(4096))]
+/// struct Page {
+///     bytes: [u8; 4096],
+/// }
+/// ```
+///
+/// Success (integer type suffix of the expected size is ignored):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(16usize)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u64,
+///     len: u64,
+/// }
+///
+/// #[assert_size(8u32)]
+/// #[repr(C)]
+/// struct Pair {
+///     first: u32,
+///     second: u32,
+/// }
+/// ```
+///
+/// Failure (real size is 4096 bytes, expected is 4000 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(4KB)]
+/// #[repr(C, align(4096))]
+/// struct Page {
+///     bytes: [u8; 4096],
+/// }
+/// ```
+///
+/// Failure (unknown unit suffix):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(4Kib)]
+/// #[repr(C, align(4096))]
+/// struct Page {
+///     bytes: [u8; 4096],
+/// }
+/// ```
+///
+/// Success (expected size depending on the pointer width):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(target_pointer_width = "64" => 24, target_pointer_width = "32" => 12)]
+/// #[repr(C)]
+/// struct Slice {
+///     ptr: *const u8,
+///     len: usize,
+///     cap: usize,
+/// }
+/// ```
+///
+/// Success (expected size depending on the operating system, checked on the
+/// hosts of the listed ones only):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(
+///     any(target_os = "linux", target_os = "android") => 16,
+///     target_os = "macos" => 16,
+///     windows => 8,
+/// )]
+/// #[repr(C)]
+/// struct Timeval {
+///     #[cfg(windows)]
+///     tv_sec: i32,
+///     #[cfg(not(windows))]
+///     tv_sec: i64,
+///     #[cfg(windows)]
+///     tv_usec: i32,
+///     #[cfg(not(windows))]
+///     tv_usec: i64,
+/// }
+/// ```
+///
+/// Failure (arm matching the host target is wrong):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(target_pointer_width = "64" => 16, target_pointer_width = "32" => 8)]
+/// #[repr(C)]
+/// struct Slice {
+///     ptr: *const u8,
+///     len: usize,
+///     cap: usize,
+/// }
+/// ```
+///
+/// Success (custom failure message):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(8, msg = "must match on-wire protocol header")]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+/// ```
+///
+/// Failure (real size is 8 bytes, expected is 12 bytes), the build aborts with
+/// `must match on-wire protocol header`:
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(12, msg = "must match on-wire protocol header")]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+/// ```
+///
+/// Success (size given along with the name of the annotated type):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(Header = 8)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// #[assert_size(self = 4)]
+/// #[repr(C)]
+/// struct Trailer {
+///     crc: u32,
+/// }
+/// ```
+///
+/// Failure (attribute copied from `Header` names another type):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(Header = 8)]
+/// #[repr(C)]
+/// struct Footer {
+///     crc: u32,
+///     len: u32,
+/// }
+/// ```
+///
+/// Failure (unknown key in the attribute):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(8, message = "must match on-wire protocol header")]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+/// ```
+///
+/// Success (doc comments, derives and other attributes on the structure are
+/// preserved, regardless of their order relative to the attribute):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// /// Point in 2D space.
+/// #[derive(Clone, Copy, Debug, Default, PartialEq)]
+/// #[assert_size(8)]
+/// #[repr(C)]
+/// #[allow(dead_code)]
+/// struct Point {
+///     /// Horizontal coordinate.
+///     x: f32,
+///     /// Vertical coordinate.
+///     y: f32,
+/// }
+///
+/// /// Point in 3D space.
+/// #[assert_size(12)]
+/// #[repr(C)]
+/// #[derive(Clone, Copy, Debug, Default, PartialEq)]
+/// struct Point3 {
+///     x: f32,
+///     y: f32,
+///     z: f32,
+/// }
+///
+/// let p = Point { x: 1.0, y: 2.0 };
+/// assert_eq!(p, p.clone());
+/// assert_eq!(format!("{:?}", p), "Point { x: 1.0, y: 2.0 }");
+/// assert_eq!(Point3::default(), Point3 { x: 0.0, y: 0.0, z: 0.0 });
+/// ```
+///
+/// Success (used in a `#![no_std]` crate):
+///
+/// ```
+/// #![no_std]
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(16)]
+/// #[assert_align(8)]
+/// #[repr(C)]
+/// struct Regs {
+///     control: u64,
+///     status: u64,
+/// }
+/// #
+/// # // Link `std` for the test harness without making it nameable at the crate root.
+/// # mod runtime {
+/// #     extern crate std;
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// Failure (real size is 24 bytes, expected is 32 bytes), the build aborts with
+/// `size of 'C' is not 32 bytes (actual size: 24 bytes, next power of two: 32
+/// bytes)`:
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(32)]
+/// #[repr(C, packed)]
+/// struct C {
+///     field1: u64,
+///     field2: u64,
+///     field3: u64,
+/// }
+/// ```
+///
+/// Failure (attributes cannot check type aliases), the build aborts pointing to
+/// `assert_type_size!(Handle, 8)` instead:
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(8)]
+/// type Handle = u64;
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| match syn::parse::<Item>(item)? {
+        Item::Impl(item) => impl_size(attr, item),
+        item => expand_size(attr.into(), item, Comparison::Eq),
+    })
+}
+
+/// The attribute `#[assert_size_fits(USIZE)]` performs **compile-time** check, if the
+/// structure it is attached is not bigger than given amount of bytes.
+///
+/// The attribute can be attached to a `struct`, an `enum` or a `union`.
+///
+/// A soft budget below the hard limit may be given with
+/// `#[assert_size_fits(USIZE, warn_at = USIZE)]`. When the real size exceeds
+/// `warn_at` but still fits in the hard limit, the build succeeds with a
+/// warning; exceeding the hard limit still aborts the build. As procedural
+/// macros cannot emit lints on stable, the warning is produced by calling a
+/// `#[deprecated]` const function selected by the outcome of the comparison,
+/// so it is reported by the `deprecated` lint and obeys `#[allow(deprecated)]`
+/// and `#[deny(deprecated)]`. The same way, `#[assert_size_fits(0)]` warns at
+/// the literal, as only zero-sized types fit in no bytes, which
+/// `#[assert_zst]` checks more plainly.
+///
+/// The failure message reports by how many bytes the real size exceeds the
+/// maximum, `Overflow = ActualSize - ExpectedSize` below, to tell how much has to
+/// be cut, and the next power of two the real size rounds up to, `RoundedSize`.
+///
+/// For storage in an inline buffer, e.g. a `union` with a `[u8; N]` field, the
+/// type has to fit in its alignment as well. A maximum alignment may be given
+/// with `#[assert_size_fits(USIZE, align = USIZE)]`, checked along with the size
+/// by a second anonymous const.
+///
+/// With the `runtime-assert` feature, the check is also repeated in a generated
+/// `#[test]`, see `#[assert_size]`.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const ExpectedSize: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() <= ExpectedSize,
+///    "'StructName' does not fit in ExpectedSize bytes (actual size: ActualSize bytes, Overflow bytes over, \
+///     next power of two: RoundedSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (real size matches expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C, packed)]
+/// #[assert_size_fits(16)]
+/// struct A {
+///     field1: u64,
+///     field2: u64,
+/// }
+/// ```
+///
+/// Success (real size is less than expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fits(32)]
+/// #[repr(C, packed)]
+/// struct B {
+///     field1: u64,
+///     field2: u64,
+///     field3: u64,
+/// }
+/// ```
+///
+/// Success (attribute attached to an enumeration):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fits(2)]
+/// #[repr(u8)]
+/// enum Tag {
+///     First,
+///     Second,
+///     Third,
+/// }
+/// ```
+///
+/// Success (attribute attached to a union):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fits(16)]
+/// #[repr(C)]
+/// union Word {
+///     bytes: [u8; 8],
+///     value: u64,
+/// }
+/// ```
+///
+/// Success (fat pointer fits in two pointers on every target):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fits(2ptr)]
+/// #[repr(C)]
+/// struct RawSlice {
+///     ptr: *const u8,
+///     len: usize,
+/// }
+///
+/// #[assert_size_fits(1ptr)]
+/// struct Owned {
+///     inner: Box<[u64; 64]>,
+/// }
+/// ```
+///
+/// Failure (three pointers do not fit in two):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fits(2ptr)]
+/// #[repr(C)]
+/// struct RawVec {
+///     ptr: *const u8,
+///     len: usize,
+///     cap: usize,
+/// }
+/// ```
+///
+/// Success (fits in a 64-byte buffer aligned to 16 bytes):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fits(64, align = 16)]
+/// #[repr(C)]
+/// struct Task {
+///     id: u64,
+///     state: [u32; 4],
+/// }
+/// ```
+///
+/// Failure (real alignment is 32 bytes, exceeding maximum of 16 bytes), the
+/// build aborts with `alignment of 'Task' exceeds 16 bytes (actual alignment: 32
+/// bytes)`:
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fits(64, align = 16)]
+/// #[repr(C, align(32))]
+/// struct Task {
+///     id: u64,
+/// }
+/// ```
+///
+/// Success (real size of 8 bytes is below the soft budget of 16 bytes, no
+/// warning):
+///
+/// ```
+/// #![deny(deprecated)]
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fits(32, warn_at = 16)]
+/// #[repr(C)]
+/// struct Hot {
+///     bytes: [u8; 8],
+/// }
+/// ```
+///
+/// Warning (real size of 24 bytes exceeds the soft budget of 16 bytes), the
+/// build succeeds reporting `'Hot' exceeds the soft size budget of 16 bytes
+/// (hard limit: 32 bytes)`:
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fits(32, warn_at = 16)]
+/// #[repr(C)]
+/// struct Hot {
+///     bytes: [u8; 24],
+/// }
+/// ```
+///
+/// The same warning turned into an error:
+/// ```compile_fail
+/// #![deny(deprecated)]
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fits(32, warn_at = 16)]
+/// #[repr(C)]
+/// struct Hot {
+///     bytes: [u8; 24],
+/// }
+/// ```
+///
+/// Failure (real size of 40 bytes exceeds the hard limit of 32 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fits(32, warn_at = 16)]
+/// #[repr(C)]
+/// struct Hot {
+///     bytes: [u8; 40],
+/// }
+/// ```
+///
+/// Failure (real size is 24 bytes, exceeding maximum of 16 bytes), the build
+/// aborts with `'C' does not fit in 16 bytes (actual size: 24 bytes, 8 bytes over,
+/// next power of two: 32 bytes)`:
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fits(16)]
+/// #[repr(C, packed)]
+/// struct C {
+///     field1: u64,
+///     field2: u64,
+///     field3: u64,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_fits(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        expand_size(attr.into(), syn::parse(item)?, Comparison::Le)
+    })
+}
+
+/// The attribute `#[assert_size_at_least(USIZE)]` performs **compile-time** check, if the
+/// structure it is attached is not smaller than given amount of bytes.
+///
+/// With the `runtime-assert` feature, the check is also repeated in a generated
+/// `#[test]`, see `#[assert_size]`.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const ExpectedSize: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() >= ExpectedSize,
+///    "'StructName' is smaller than ExpectedSize bytes (actual size: ActualSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (real size matches expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C, packed)]
+/// #[assert_size_at_least(16)]
+/// struct A {
+///     field1: u64,
+///     field2: u64,
+/// }
+/// ```
+///
+/// Success (real size is greater than expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_at_least(16)]
+/// #[repr(C, packed)]
+/// struct B {
+///     field1: u64,
+///     field2: u64,
+///     field3: u64,
+/// }
+/// ```
+///
+/// Failure (real size is 16 bytes, below minimum of 24 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_at_least(24)]
+/// #[repr(C, packed)]
+/// struct C {
+///     field1: u64,
+///     field2: u64,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_at_least(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        expand_size(attr.into(), syn::parse(item)?, Comparison::Ge)
+    })
+}
+
+/// The attribute `#[assert_size_not(USIZE)]` performs **compile-time** check, if
+/// the structure it is attached to does **not** have given size in bytes.
+///
+/// It is a regression guard during migrations, making sure a structure does not
+/// return to an old, wrong size.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{ field: u8 }
+/// # const ForbiddenSize: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() != ForbiddenSize,
+///    "'StructName' unexpectedly equals ForbiddenSize bytes"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (real size is 16 bytes, the old size was 12 bytes):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_not(12)]
+/// #[repr(C)]
+/// struct Record {
+///     id: u64,
+///     flags: u32,
+/// }
+/// ```
+///
+/// Failure (real size is still 12 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_not(12)]
+/// #[repr(C, packed)]
+/// struct Record {
+///     id: u64,
+///     flags: u32,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_not(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let size = syn::parse::<ExpSize>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let check = emit_check(
+            size.span(),
+            quote!(#actual != #size),
+            &size.message(vec![MessagePart::Text(format!(
+                "'{}' unexpectedly equals {} bytes",
+                qualified(id),
+                size
+            ))]),
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_size_grows_by(base = USIZE, delta = ISIZE)]` performs
+/// **compile-time** check, if the structure it is attached to is `delta` bytes
+/// bigger than the `base` size, or smaller for a negative `delta`.
+///
+/// It documents an intended change of the size next to the definition: the
+/// base is usually a const holding the old size, e.g. kept from the previous
+/// release, so the diff growing the structure states by how much. The delta
+/// is a literal, which may carry a unit suffix like an expected size.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const BaseSize: usize=0;
+/// # const Delta: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() == BaseSize + Delta,
+///    "size of 'StructName' did not grow by Delta bytes from BaseSize (base: \
+///     BaseSize bytes, actual size: ActualSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (a `u64` field added to a header of 16 bytes):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// /// Size of `Header` in version 1 of the protocol.
+/// const HEADER_V1_SIZE: usize = 16;
+///
+/// #[assert_size_grows_by(base = HEADER_V1_SIZE, delta = 8)]
+/// #[repr(C)]
+/// struct Header {
+///     magic: u64,
+///     len: u64,
+///     checksum: u64,
+/// }
+/// ```
+///
+/// Success (a `u32` field removed, shrinking the structure):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_grows_by(base = 12, delta = -4)]
+/// #[repr(C)]
+/// struct Record {
+///     id: u32,
+///     flags: u32,
+/// }
+/// ```
+///
+/// Failure (real size grew by 4 bytes, not 8 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// const HEADER_V1_SIZE: usize = 16;
+///
+/// #[assert_size_grows_by(base = HEADER_V1_SIZE, delta = 8)]
+/// #[repr(C, packed)]
+/// struct Header {
+///     magic: u64,
+///     len: u64,
+///     checksum: u32,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_grows_by(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let SizeDelta {
+            base,
+            delta,
+            bytes,
+            shrink,
+        } = syn::parse::<SizeDelta>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let bytes = Literal::usize_unsuffixed(bytes);
+        // A shrink adds the delta to the actual size, so a base smaller than the
+        // delta fails the check instead of overflowing.
+        let (condition, change) = if shrink {
+            (quote!(#actual + #bytes == #base), "shrink")
+        } else {
+            (quote!(#actual == #base + #bytes), "grow")
+        };
+        let mut message = vec![MessagePart::Text(format!(
+            "size of '{}' did not {} by {} bytes from {}",
+            qualified(id),
+            change,
+            bytes,
+            base
+        ))];
+        // The value of a named base is not visible in the attribute.
+        match base.value {
+            Some(_) => message.push(MessagePart::Text(" bytes (actual size: ".into())),
+            None => message.extend([
+                MessagePart::Text(" (base: ".into()),
+                MessagePart::Value(base.to_token_stream()),
+                MessagePart::Text(" bytes, actual size: ".into()),
+            ]),
+        }
+        message.extend([
+            MessagePart::Value(actual),
+            MessagePart::Text(" bytes)".into()),
+        ]);
+        let check = emit_check(delta.span(), condition, &message);
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_size_between(MIN, MAX)]` performs **compile-time** check, if the
+/// size of the structure it is attached to lies within the inclusive range `MIN..=MAX` bytes.
+///
+/// `MIN` must not be greater than `MAX`, otherwise the attribute itself is rejected.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const MinSize: usize=0;
+/// # const MaxSize: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() >= MinSize,
+///    "size of 'StructName' is not between MinSize and MaxSize bytes (actual size: ActualSize bytes)"
+/// );
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() <= MaxSize,
+///    "size of 'StructName' is not between MinSize and MaxSize bytes (actual size: ActualSize bytes)"
+/// );
+/// ```
+///
+/// A failure is reported at the bound the size violates.
+///
+/// # Examples
+///
+/// Success (real size is within range):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C, packed)]
+/// #[assert_size_between(8, 32)]
+/// struct A {
+///     field1: u64,
+///     field2: u64,
+/// }
+/// ```
+///
+/// Success (real size equals either boundary):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_between(16, 32)]
+/// #[repr(C, packed)]
+/// struct B {
+///     field1: u64,
+///     field2: u64,
+/// }
+///
+/// #[assert_size_between(8, 16)]
+/// #[repr(C, packed)]
+/// struct C {
+///     field1: u64,
+///     field2: u64,
+/// }
+/// ```
+///
+/// Failure (real size is 16 bytes, below minimum of 24 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_between(24, 32)]
+/// #[repr(C, packed)]
+/// struct D {
+///     field1: u64,
+///     field2: u64,
+/// }
+/// ```
+///
+/// Failure (real size is 16 bytes, exceeding maximum of 8 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_between(4, 8)]
+/// #[repr(C, packed)]
+/// struct E {
+///     field1: u64,
+///     field2: u64,
+/// }
+/// ```
+///
+/// Failure (minimum is greater than maximum):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_between(32, 8)]
+/// #[repr(C, packed)]
+/// struct F {
+///     field1: u64,
+///     field2: u64,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_between(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let range = syn::parse::<RangeSize>(attr)?;
+        let (min, max) = (range.min, range.max);
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let message = [
+            MessagePart::Text(format!(
+                "size of '{}' is not between {} and {} bytes (actual size: ",
+                qualified(id),
+                min,
+                max
+            )),
+            MessagePart::Value(actual.clone()),
+            MessagePart::Text(" bytes)".into()),
+        ];
+        // At most one of the bounds fails, as `min <= max`.
+        let min_check = emit_check(range.min_span, quote!(#actual >= #min), &message);
+        let max_check = emit_check(range.max_span, quote!(#actual <= #max), &message);
+
+        let checker = quote! {
+            #min_check
+            #max_check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_size_multiple_of(USIZE)]` performs **compile-time**
+/// check, if the size of the structure it is attached to is a multiple of given
+/// amount of bytes.
+///
+/// It is useful for structures packed into arrays, e.g. uploaded to a GPU, where
+/// every element must start at a given granularity. Zero multiple is rejected.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const Multiple: usize=1;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() % Multiple == 0,
+///    "size of 'StructName' (ActualSize) is not a multiple of Multiple bytes"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (real size of 32 bytes is a multiple of 16 bytes):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_multiple_of(16)]
+/// #[repr(C)]
+/// struct Vertex {
+///     position: [f32; 4],
+///     color: [f32; 4],
+/// }
+/// ```
+///
+/// Failure (real size of 24 bytes is not a multiple of 16 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_multiple_of(16)]
+/// #[repr(C)]
+/// struct Vertex {
+///     position: [f32; 4],
+///     uv: [f32; 2],
+/// }
+/// ```
+///
+/// Failure (zero multiple):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_multiple_of(0)]
+/// #[repr(C)]
+/// struct Vertex {
+///     position: [f32; 4],
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_multiple_of(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let size = syn::parse::<ExpSize>(attr)?;
+        if size.size.value == Some(0) {
+            return Err(syn::Error::new_spanned(
+                &size.size.expr,
+                "size cannot be a multiple of zero",
+            ));
+        }
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let check = emit_check(
+            size.span(),
+            quote!(#actual % #size == 0),
+            &size.message(vec![
+                MessagePart::Text(format!("size of '{}' (", qualified(id))),
+                MessagePart::Value(actual),
+                MessagePart::Text(format!(") is not a multiple of {} bytes", size)),
+            ]),
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_size_pow2_bucket(EXP)]` performs **compile-time**
+/// check, if the size of the structure it is attached to falls into the
+/// power-of-two bucket `EXP`, i.e. it is bigger than `2^(EXP-1)` bytes and not
+/// bigger than `2^EXP` bytes.
+///
+/// It is meant for types allocated from pools bucketed by powers of two, where
+/// growing past the bucket doubles the memory taken and shrinking below it
+/// leaves half of it unused. The exponent is an integer literal of at most 63;
+/// bucket 0 holds sizes up to 1 byte, zero-sized types included.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName(u16);
+/// # const EXP: usize=1;
+/// #
+/// const _: () = assert!(
+///    (1 << (EXP - 1)) < core::mem::size_of::<StructName>()
+///        && core::mem::size_of::<StructName>() <= (1 << EXP),
+///    "size of 'StructName' is not in power-of-two bucket 2^EXP (Min..=Max bytes, \
+///     actual size: ActualSize bytes in bucket 2^ActualExp)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (real size of 24 bytes falls into the bucket of 17 to 32 bytes):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_pow2_bucket(5)]
+/// #[repr(C)]
+/// struct Node {
+///     next: u64,
+///     prev: u64,
+///     value: u64,
+/// }
+/// ```
+///
+/// Failure (real size of 40 bytes falls into the bucket of 33 to 64 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_pow2_bucket(5)]
+/// #[repr(C)]
+/// struct Node {
+///     next: u64,
+///     prev: u64,
+///     value: [u64; 3],
+/// }
+/// ```
+///
+/// Failure (real size of 16 bytes falls into the bucket below):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_pow2_bucket(5)]
+/// #[repr(C)]
+/// struct Node {
+///     next: u64,
+///     prev: u64,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_pow2_bucket(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let exp_lit = syn::parse::<LitInt>(attr)?;
+        let exp = exp_lit.base10_parse::<u32>()?;
+        if exp > 63 {
+            return Err(syn::Error::new(
+                exp_lit.span(),
+                format!("bucket exponent must be at most 63, found {}", exp),
+            ));
+        }
+        let max = 1u64 << exp;
+        // Sizes above the bucket below, which bucket 0 does not have.
+        let min = if exp == 0 { 0 } else { max / 2 + 1 };
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let (min_lit, max_lit) = (Literal::u64_unsuffixed(min), Literal::u64_unsuffixed(max));
+        let bucket = quote!(#actual.next_power_of_two().trailing_zeros() as usize);
+        let check =
+            emit_check(
+                exp_lit.span(),
+                match min {
+                    0 => quote!(#actual <= #max_lit),
+                    _ => quote!(#min_lit <= #actual && #actual <= #max_lit),
+                },
+                &[
+                    MessagePart::Text(format!(
+                    "size of '{}' is not in power-of-two bucket 2^{} ({}..={} bytes, actual size: ",
+                    qualified(id), exp, min, max
+                )),
+                    MessagePart::Value(actual),
+                    MessagePart::Text(" bytes in bucket 2^".into()),
+                    MessagePart::Value(bucket),
+                    MessagePart::Text(")".into()),
+                ],
+            );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_size_in_words(USIZE)]` performs **compile-time**
+/// check, if the structure it is attached to has the exact size in words of 8
+/// bytes, e.g. the value representation of an interpreter.
+///
+/// The word size may be overridden with `#[assert_size_in_words(USIZE, word = USIZE)]`,
+/// the default failure message may be replaced with `msg = "..."`.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const ExpectedWords: usize=0;
+/// # const WordSize: usize=8;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() == ExpectedWords * WordSize,
+///    "'StructName' is not ExpectedWords words (ExpectedSize bytes) (actual size: ActualSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (tag and payload take 2 words):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_in_words(2)]
+/// #[repr(C)]
+/// struct Value {
+///     tag: u64,
+///     payload: u64,
+/// }
+/// ```
+///
+/// Success (words of 4 bytes):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_in_words(3, word = 4)]
+/// #[repr(C)]
+/// struct Triple(u32, u32, u32);
+/// ```
+///
+/// Failure (real size is 24 bytes, 2 words take 16 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_in_words(2)]
+/// #[repr(C)]
+/// struct Value {
+///     tag: u64,
+///     payload: [u64; 2],
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_in_words(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| expand_words(attr, item, false))
+}
+
+/// The attribute `#[assert_stride(USIZE)]` performs **compile-time** check, if
+/// the array stride of the structure it is attached to, i.e. the distance between
+/// consecutive elements of `[StructName; N]`, matches expected amount of bytes.
+///
+/// It is useful for structures laid out in arrays, where the tail padding forced
+/// by the alignment counts towards every element.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const ExpectedStride: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<[StructName; 2]>() - core::mem::size_of::<[StructName; 1]>()
+///        == ExpectedStride,
+///    "stride of 'StructName' is not ExpectedStride bytes (actual stride: ActualStride bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (fields take 5 bytes, alignment pads the stride to 8 bytes):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_stride(8)]
+/// #[repr(C)]
+/// struct Sample {
+///     value: u32,
+///     flags: u8,
+/// }
+/// ```
+///
+/// Failure (real stride is 8 bytes, expected is 5 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_stride(5)]
+/// #[repr(C)]
+/// struct Sample {
+///     value: u32,
+///     flags: u8,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_stride(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let size = syn::parse::<ExpSize>(attr)?;
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual =
+            quote!(::core::mem::size_of::<[#ty; 2]>() - ::core::mem::size_of::<[#ty; 1]>());
+        let check = emit_check(
+            size.span(),
+            quote!(#actual == #size),
+            &size.message(vec![
+                MessagePart::Text(format!(
+                    "stride of '{}' is not {} bytes (actual stride: ",
+                    qualified(id),
+                    size
+                )),
+                MessagePart::Value(actual),
+                MessagePart::Text(" bytes)".into()),
+            ]),
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_zst]` performs **compile-time** check, if the
+/// structure it is attached to is zero-sized.
+///
+/// It is useful for marker types, which must stay zero-sized when refactored,
+/// and for opaque handles of foreign types, declared in FFI code as a
+/// `#[repr(C)]` structure holding `[u8; 0]` and a `PhantomData` of a raw pointer,
+/// which are only ever used behind pointers.
+///
+/// A generic marker, e.g. `struct Tag<T>(PhantomData<T>);`, is zero-sized for
+/// every `T`, but the attribute cannot tell a field depending on `T` from one
+/// that does not, so it rejects type and const parameters. Check instantiations
+/// of such a marker instead, with `assert_type_size!(Tag<u8>, 0)` or
+/// `assert_size_for!(Tag<u8> = 0, Tag<String> = 0)`; a `PhantomData` takes no
+/// space whatever its parameter, so any instantiation stands for all of them.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() == 0,
+///    "'StructName' is not zero-sized (actual size: ActualSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (empty structure):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_zst]
+/// struct Marker {}
+/// ```
+///
+/// Success (structure holding only `PhantomData`):
+///
+/// ```
+/// # use pakr_assert_size::*;
+/// use core::marker::PhantomData;
+///
+/// #[assert_zst]
+/// struct Tagged {
+///     _tag: PhantomData<u64>,
+/// }
+/// ```
+///
+/// Success (generic marker, checked for some of its instantiations):
+///
+/// ```
+/// # use pakr_assert_size::*;
+/// use core::marker::PhantomData;
+///
+/// pub struct Tag<T>(PhantomData<T>);
+///
+/// assert_type_size!(Tag<u8>, 0);
+/// assert_size_for!(Tag<String> = 0, Tag<[u64; 64]> = 0);
+/// ```
+///
+/// Failure (generic marker, whose size could depend on its parameter):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+/// use core::marker::PhantomData;
+///
+/// #[assert_zst]
+/// pub struct Tag<T>(PhantomData<T>);
+/// ```
+///
+/// Success (opaque FFI handle; its zero-length array is an ordinary field to
+/// the other checks, e.g. the one of padding or offsets):
+///
+/// ```
+/// # use pakr_assert_size::*;
+/// use core::marker::{PhantomData, PhantomPinned};
+///
+/// #[assert_zst]
+/// #[assert_no_padding]
+/// #[assert_field_offset(_data = 0, _marker = 0)]
+/// #[repr(C)]
+/// pub struct Opaque {
+///     _data: [u8; 0],
+///     _marker: PhantomData<(*mut u8, PhantomPinned)>,
+/// }
+/// ```
+///
+/// Failure (a zero-length array of a type aligned to 8 bytes still takes no
+/// space, but a field next to it does):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_zst]
+/// #[repr(C)]
+/// pub struct Opaque {
+///     _align: [u64; 0],
+///     _tag: u8,
+/// }
+/// ```
+///
+/// Failure (real size is 1 byte):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_zst]
+/// struct Marker {
+///     flag: u8,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_zst(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        syn::parse::<Nothing>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let check = emit_check(
+            Span::call_site(),
+            quote!(#actual == 0),
+            &[
+                MessagePart::Text(format!(
+                    "'{}' is not zero-sized (actual size: ",
+                    qualified(id)
+                )),
+                MessagePart::Value(actual),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_size_eq_discriminant_type]` performs **compile-time**
+/// check, if the enumeration it is attached to has exactly the size of its
+/// discriminant type, declared with `#[repr(u8)]`, `#[repr(u16)]` etc.
+///
+/// It guards FFI enumerations against variant payloads silently changing their
+/// layout. Enumerations without an integer `#[repr(...)]` are rejected.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # #[repr(u16)] enum EnumName { A }
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<EnumName>() == core::mem::size_of::<u16>(),
+///    "size of 'EnumName' (ActualSize bytes) is not equal to size of its discriminant type 'u16' (2 bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (fieldless enumeration):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_eq_discriminant_type]
+/// #[repr(u16)]
+/// enum Opcode {
+///     Nop = 0,
+///     Load = 0x100,
+///     Store = 0x200,
+/// }
+/// ```
+///
+/// Failure (variant carries a payload bigger than the discriminant):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_eq_discriminant_type]
+/// #[repr(u16)]
+/// enum Opcode {
+///     Nop,
+///     Load(u64),
+/// }
+/// ```
+///
+/// Failure (no integer representation):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_eq_discriminant_type]
+/// enum Opcode {
+///     Nop,
+///     Load,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_eq_discriminant_type(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        syn::parse::<Nothing>(attr)?;
+
+        let enum_item = syn::parse::<ItemEnum>(item)?;
+        let id = &enum_item.ident;
+        let ty = self_type(id, &enum_item.generics)?;
+
+        let repr = match repr_int(&enum_item.attrs) {
+            Some(repr) => repr,
+            None => {
+                return Err(syn::Error::new(
+                    id.span(),
+                    format!(
+                        "'{}' has no integer representation, add e.g. `#[repr(u16)]`",
+                        id
+                    ),
+                ))
+            }
+        };
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let expected = quote!(::core::mem::size_of::<#repr>());
+        let check = emit_check(
+            Span::call_site(),
+            quote!(#actual == #expected),
+            &[
+                MessagePart::Text(format!("size of '{}' (", qualified(id))),
+                MessagePart::Value(actual),
+                MessagePart::Text(format!(
+                    " bytes) is not equal to size of its discriminant type '{}' (",
+                    repr
+                )),
+                MessagePart::Value(expected),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #enum_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_variant_size(VARIANT = USIZE, ...)]` performs
+/// **compile-time** check, if the payloads of the variants of the enumeration it
+/// is attached to have the exact sizes in bytes.
+///
+/// The payload of a variant is measured as a structure holding the fields of
+/// the variant, without the discriminant. For enumerations with `#[repr(C)]` or
+/// an integer representation, which lay variants out as `#[repr(C)]`
+/// structures, that structure is `#[repr(C)]` as well. Naming a variant the
+/// enumeration does not have is an error.
+///
+/// Check does not pollute namespace, for every variant it is expanded to an
+/// anonymous const equivalent to
+/// ```
+/// # struct FieldType{}
+/// # const ExpectedSize: usize=0;
+/// #
+/// const _: () = {
+///     #[repr(C)]
+///     struct Payload(FieldType);
+///
+///     assert!(
+///         core::mem::size_of::<Payload>() == ExpectedSize,
+///         "size of payload of 'EnumName::Variant' is not ExpectedSize bytes (actual size: ActualSize bytes)"
+///     );
+/// };
+/// ```
+///
+/// # Examples
+///
+/// Success (real payload sizes match expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_variant_size(Ping = 0, Data = 16, Error = 8)]
+/// #[repr(C, u8)]
+/// enum Message {
+///     Ping,
+///     Data { id: u32, len: u32, offset: u64 },
+///     Error(u32, u16),
+/// }
+/// ```
+///
+/// Failure (real payload size of `Data` is 16 bytes, expected is 12 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_variant_size(Data = 12)]
+/// #[repr(C, u8)]
+/// enum Message {
+///     Ping,
+///     Data { id: u32, len: u32, offset: u64 },
+/// }
+/// ```
+///
+/// Failure (no such variant):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_variant_size(Pong = 0)]
+/// #[repr(C, u8)]
+/// enum Message {
+///     Ping,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_variant_size(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let sizes = syn::parse::<FieldSizes>(attr)?;
+
+        let enum_item = syn::parse::<ItemEnum>(item)?;
+        let id = &enum_item.ident;
+        self_type(id, &enum_item.generics)?;
+
+        let repr_c = repr_int(&enum_item.attrs).is_some()
+            || reprs(&enum_item.attrs).any(|nested| tokens_to_string(&nested) == "C");
+        let repr = if repr_c { quote!(#[repr(C)]) } else { quote!() };
+
+        let mut checks = TokenStream2::new();
+        for FieldSize { member, size } in &sizes.entries {
+            let variant = enum_item
+                .variants
+                .iter()
+                .find(|variant| matches!(member, Member::Named(name) if variant.ident == *name))
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        member,
+                        format!("no variant `{}` in enum `{}`", tokens_to_string(member), id),
+                    )
+                })?;
+            let field_types = variant
+                .fields
+                .iter()
+                .map(|field| static_lifetimes(&field.ty, &enum_item.generics));
+
+            let actual = quote!(::core::mem::size_of::<Payload>());
+            let check = emit_check(
+                size.span(),
+                quote!(#actual == #size),
+                &[
+                    MessagePart::Text(format!(
+                        "size of payload of '{}::{}' is not {} bytes (actual size: ",
+                        qualified(id),
+                        variant.ident,
+                        size
+                    )),
+                    MessagePart::Value(actual),
+                    MessagePart::Text(" bytes)".into()),
+                ],
+            );
+            checks.extend(quote! {
+                const _: () = {
+                    #[allow(dead_code)]
+                    #repr
+                    struct Payload(#(#field_types),*);
+
+                    #check
+                };
+            });
+        }
+
+        let checker = quote! {
+            #checks
+            #enum_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_discriminant_overhead(USIZE)]` performs
+/// **compile-time** check, if the enumeration it is attached to is at most the
+/// given number of bytes bigger than the payload of its largest variant.
+///
+/// It bounds the space spent on the discriminant (and the padding it brings),
+/// which adds up in large arrays of enumerations. The payloads are measured as
+/// in `#[assert_variant_size]`, i.e. as structures holding the fields of the
+/// variants. The size may be followed by `, msg = "..."` replacing the default
+/// failure message.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # enum EnumName { Variant(u32) }
+/// # const MaxOverhead: usize=4;
+/// #
+/// const _: () = {
+///     const PAYLOAD_SIZES: [usize; 1] = [{
+///         struct Payload(u32);
+///         core::mem::size_of::<Payload>()
+///     }];
+///     const PAYLOAD_SIZE: usize = {
+///         // largest of PAYLOAD_SIZES
+/// #       PAYLOAD_SIZES[0]
+///     };
+///
+///     assert!(
+///         core::mem::size_of::<EnumName>() - PAYLOAD_SIZE <= MaxOverhead,
+///         "discriminant overhead of 'EnumName' exceeds MaxOverhead bytes (actual overhead: ActualOverhead bytes, size: ActualSize bytes, largest payload: PayloadSize bytes)"
+///     );
+/// };
+/// ```
+///
+/// # Examples
+///
+/// Success (discriminant takes 4 bytes next to two `f32` fields):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_discriminant_overhead(4)]
+/// enum Shape {
+///     Circle(f32),
+///     Rect(f32, f32),
+/// }
+/// ```
+///
+/// Success (null pointer niche makes the discriminant free):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_discriminant_overhead(0)]
+/// enum Slot {
+///     Empty,
+///     Full(Box<u64>),
+/// }
+/// ```
+///
+/// Failure (`u64` discriminant takes 15 bytes next to a `u8` field):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_discriminant_overhead(1)]
+/// #[repr(u64)]
+/// enum Tagged {
+///     Byte(u8),
+///     Empty,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_discriminant_overhead(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let overhead = syn::parse::<ExpSize>(attr)?;
+
+        let enum_item = syn::parse::<ItemEnum>(item)?;
+        let id = &enum_item.ident;
+        let ty = self_type(id, &enum_item.generics)?;
+
+        let repr_c = repr_int(&enum_item.attrs).is_some()
+            || reprs(&enum_item.attrs).any(|nested| tokens_to_string(&nested) == "C");
+        let repr = if repr_c { quote!(#[repr(C)]) } else { quote!() };
+
+        let count = enum_item.variants.len();
+        let payloads = enum_item.variants.iter().map(|variant| {
+            let field_types = variant
+                .fields
+                .iter()
+                .map(|field| static_lifetimes(&field.ty, &enum_item.generics));
+            quote! {{
+                #[allow(dead_code)]
+                #repr
+                struct Payload(#(#field_types),*);
+
+                ::core::mem::size_of::<Payload>()
+            }}
+        });
+
+        let size = quote!(::core::mem::size_of::<#ty>());
+        let actual = quote!(#size.saturating_sub(PAYLOAD_SIZE));
+        let check = emit_check(
+            overhead.span(),
+            quote!(#actual <= #overhead),
+            &overhead.message(vec![
+                MessagePart::Text(format!(
+                    "discriminant overhead of '{}' exceeds {} bytes (actual overhead: ",
+                    qualified(id),
+                    overhead
+                )),
+                MessagePart::Value(actual.clone()),
+                MessagePart::Text(" bytes, size: ".into()),
+                MessagePart::Value(size),
+                MessagePart::Text(" bytes, largest payload: ".into()),
+                MessagePart::Value(quote!(PAYLOAD_SIZE)),
+                MessagePart::Text(" bytes)".into()),
+            ]),
+        );
+
+        let checker = quote! {
+            const _: () = {
+                const PAYLOAD_SIZES: [usize; #count] = [#(#payloads),*];
+                const PAYLOAD_SIZE: usize = {
+                    let mut max = 0;
+                    let mut i = 0;
+                    while i < PAYLOAD_SIZES.len() {
+                        if PAYLOAD_SIZES[i] > max {
+                            max = PAYLOAD_SIZES[i];
+                        }
+                        i += 1;
+                    }
+                    max
+                };
+
+                #check
+            };
+            #enum_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_size_eq_option]` performs **compile-time** check, if
+/// `Option` of the structure it is attached to has the same size as the
+/// structure itself.
+///
+/// It guards the niche optimization, i.e. `None` being represented by an
+/// otherwise invalid bit pattern of the structure (like a null `NonNull`), so
+/// that wrapping it in an `Option` costs no extra space.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName(core::ptr::NonNull<u8>);
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<Option<StructName>>() == core::mem::size_of::<StructName>(),
+///    "size of 'Option<StructName>' (OptionSize bytes) is not equal to size of 'StructName' (ActualSize bytes), niche optimization was lost"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (`NonNull` provides a niche):
+///
+/// ```
+/// # use pakr_assert_size::*;
+/// use core::ptr::NonNull;
+///
+/// #[assert_size_eq_option]
+/// struct Node {
+///     next: NonNull<Node>,
+/// }
+/// ```
+///
+/// Failure (every bit pattern of `u32` is valid, so `Option` needs extra space):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_eq_option]
+/// struct Index {
+///     value: u32,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_eq_option(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        syn::parse::<Nothing>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let option = quote!(::core::mem::size_of::<::core::option::Option<#ty>>());
+        let check = emit_check(
+            Span::call_site(),
+            quote!(#option == #actual),
+            &[
+                MessagePart::Text(format!("size of 'Option<{}>' (", id)),
+                MessagePart::Value(option),
+                MessagePart::Text(format!(
+                    " bytes) is not equal to size of '{}' (",
+                    qualified(id)
+                )),
+                MessagePart::Value(actual),
+                MessagePart::Text(" bytes), niche optimization was lost".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_result_niche(E)]` performs **compile-time** check, if
+/// `Result` of the structure it is attached to and of the error type `E` has
+/// the same size as the structure itself.
+///
+/// It extends `#[assert_size_eq_option]` to results whose error carries no data,
+/// like `Result<T, Infallible>`: the check is meaningful when `E` is zero-sized.
+/// An uninhabited `E`, like `Infallible`, rules the error out, so the result is
+/// laid out as `T` alone; any other zero-sized `E`, like `()`, needs a single
+/// bit pattern besides the values of `T`, which a niche of `T`, like a null
+/// `NonNull`, provides. An error carrying data needs space of its own, so the
+/// check fails for it.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName(core::ptr::NonNull<u8>);
+/// # type E = core::convert::Infallible;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<Result<StructName, E>>() == core::mem::size_of::<StructName>(),
+///    "size of 'Result<StructName, E>' (ResultSize bytes) is not equal to size of 'StructName' (ActualSize bytes), niche optimization was lost (size of 'E': ErrorSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (`NonNull` provides a niche, `Infallible` is zero-sized):
+///
+/// ```
+/// # use pakr_assert_size::*;
+/// use core::convert::Infallible;
+/// use core::ptr::NonNull;
+///
+/// #[assert_result_niche(Infallible)]
+/// struct Buffer {
+///     data: NonNull<u8>,
+/// }
+///
+/// #[assert_result_niche(())]
+/// struct Handle(NonNull<u8>);
+///
+/// // No niche is needed, as the error cannot exist.
+/// #[assert_result_niche(Infallible)]
+/// struct Index(u32);
+/// ```
+///
+/// Failure (the error `u32` is not zero-sized, so the result needs extra space):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+/// use core::ptr::NonNull;
+///
+/// #[assert_result_niche(u32)]
+/// struct Buffer {
+///     data: NonNull<u8>,
+/// }
+/// ```
+///
+/// Failure (every bit pattern of `u32` is valid, so there is no niche for `()`):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_result_niche(())]
+/// struct Index {
+///     value: u32,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_result_niche(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let ExpType { ty: error } = syn::parse::<ExpType>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let result = quote!(::core::mem::size_of::<::core::result::Result<#ty, #error>>());
+        let error_size = quote!(::core::mem::size_of::<#error>());
+        let error_name = tokens_to_string(&error);
+        let check = emit_check(
+            error.span(),
+            quote!(#result == #actual),
+            &[
+                MessagePart::Text(format!("size of 'Result<{}, {}>' (", id, error_name)),
+                MessagePart::Value(result),
+                MessagePart::Text(format!(
+                    " bytes) is not equal to size of '{}' (",
+                    qualified(id)
+                )),
+                MessagePart::Value(actual),
+                MessagePart::Text(format!(
+                    " bytes), niche optimization was lost (size of '{}': ",
+                    error_name
+                )),
+                MessagePart::Value(error_size),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_size_eq_type(TYPE)]` performs **compile-time** check,
+/// if the structure it is attached to has exactly the same size as the given type.
+///
+/// It is useful for newtype wrappers, which must have the same size as the type
+/// they wrap, and for generated bindings mirroring other types.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # struct OtherType{}
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() == core::mem::size_of::<OtherType>(),
+///    "size of 'StructName' (ActualSize bytes) is not equal to size of 'OtherType' (OtherSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (transparent wrapper has the size of the wrapped type):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(transparent)]
+/// #[assert_size_eq_type(u32)]
+/// struct Handle(u32);
+/// ```
+///
+/// Success (comparing with a generic type):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_eq_type([u16; 4])]
+/// #[repr(C)]
+/// struct Quad {
+///     a: u16,
+///     b: u16,
+///     c: u16,
+///     d: u16,
+/// }
+/// ```
+///
+/// Failure (real size is 8 bytes, size of `u32` is 4 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_eq_type(u32)]
+/// #[repr(C)]
+/// struct Handle(u64);
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_eq_type(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let ExpType { ty: other } = syn::parse::<ExpType>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let expected = quote!(::core::mem::size_of::<#other>());
+        let check = emit_check(
+            other.span(),
+            quote!(#actual == #expected),
+            &[
+                MessagePart::Text(format!("size of '{}' (", qualified(id))),
+                MessagePart::Value(actual),
+                MessagePart::Text(format!(
+                    " bytes) is not equal to size of '{}' (",
+                    tokens_to_string(&other)
+                )),
+                MessagePart::Value(expected),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_size_ratio(TYPE, times = USIZE, plus = USIZE)]`
+/// performs **compile-time** check, if the size of the structure it is attached
+/// to is the size of the given type multiplied by `times` and increased by
+/// `plus` bytes.
+///
+/// It captures relations between sizes of sibling types, which literal sizes
+/// cannot, e.g. a doubled SIMD lane type, `times = 2`, or a header prepended to
+/// a payload, `plus = 8`. Either of the keys may be omitted, but not both, and
+/// they may be given in any order; both accept the same expressions as the
+/// expected size of `#[assert_size]`.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # struct OtherType{}
+/// # const Times: usize=0;
+/// # const Plus: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() == core::mem::size_of::<OtherType>() * Times + Plus,
+///    "size of 'StructName' (ActualSize bytes) is not Times times the size of 'OtherType' \
+///     (OtherSize bytes) plus Plus bytes"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (a type of two lanes is twice the size of a lane):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Lane {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[assert_size_ratio(Lane, times = 2)]
+/// #[repr(C)]
+/// struct Lane2 {
+///     lo: Lane,
+///     hi: Lane,
+/// }
+/// ```
+///
+/// Success (a header prepended to a payload of four records):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Record {
+///     id: u32,
+///     value: u32,
+/// }
+///
+/// #[assert_size_ratio(Record, plus = 8, times = 4)]
+/// #[repr(C)]
+/// struct Batch {
+///     header: u64,
+///     records: [Record; 4],
+/// }
+/// ```
+///
+/// Failure (real size is 12 bytes, twice the size of `Lane` is 16 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Lane {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[assert_size_ratio(Lane, times = 2)]
+/// #[repr(C)]
+/// struct Lane2 {
+///     lo: Lane,
+///     hi: f32,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_ratio(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let SizeRatio { other, times, plus } = syn::parse::<SizeRatio>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let other_size = quote!(::core::mem::size_of::<#other>());
+        let mut expected = other_size.clone();
+        let mut relation = String::from(" bytes) is not ");
+        if let Some(times) = &times {
+            expected = quote!(#expected * #times);
+            relation.push_str(&format!("{} times ", times));
+        }
+        relation.push_str(&format!("the size of '{}' (", tokens_to_string(&other)));
+        let mut message = vec![
+            MessagePart::Text(format!("size of '{}' (", qualified(id))),
+            MessagePart::Value(actual.clone()),
+            MessagePart::Text(relation),
+            MessagePart::Value(other_size),
+            MessagePart::Text(" bytes)".into()),
+        ];
+        if let Some(plus) = &plus {
+            expected = quote!(#expected + #plus);
+            message.push(MessagePart::Text(format!(" plus {} bytes", plus)));
+        }
+        let check = emit_check(other.span(), quote!(#actual == #expected), &message);
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_array_size(count = USIZE, total = USIZE)]` performs
+/// **compile-time** check, if an array of `count` elements of the structure it
+/// is attached to takes exactly `total` bytes.
+///
+/// Elements of an array follow each other with the stride of the size of the
+/// type, which includes the trailing padding up to its alignment, so an array
+/// of a type with fields adding up to less than its size is larger than the
+/// sum of its fields suggests. Both keys are required and may be given in any
+/// order; they accept the same expressions as the expected size of
+/// `#[assert_size]`.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const Count: usize=0;
+/// # const Total: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<[StructName; Count]>() == Total,
+///    "size of '[StructName; Count]' is not Total bytes (actual size: ActualSize bytes, \
+///     size of 'StructName': ElementSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (four 16-byte records take 64 bytes):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_array_size(count = 4, total = 64)]
+/// #[repr(C)]
+/// struct Record {
+///     id: u64,
+///     value: u32,
+///     flags: u32,
+/// }
+/// ```
+///
+/// Failure (`Record` is padded to 16 bytes, four of them take 64 bytes, not 52):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_array_size(count = 4, total = 52)]
+/// #[repr(C)]
+/// struct Record {
+///     id: u64,
+///     tag: u8,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_array_size(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let ArraySize { count, total } = syn::parse::<ArraySize>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::size_of::<[#ty; #count]>());
+        let element = quote!(::core::mem::size_of::<#ty>());
+        let check = emit_check(
+            total.span(),
+            quote!(#actual == #total),
+            &[
+                MessagePart::Text(format!(
+                    "size of '[{}; {}]' is not {} bytes (actual size: ",
+                    qualified(id),
+                    count,
+                    total
+                )),
+                MessagePart::Value(actual),
+                MessagePart::Text(format!(" bytes, size of '{}': ", qualified(id))),
+                MessagePart::Value(element),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_size_eq_backing]` performs **compile-time** check, if
+/// the single-field structure it is attached to has exactly the size of its
+/// field.
+///
+/// It is meant for flag sets in the `bitflags` style, newtypes over their
+/// backing integer, which must stay exactly that integer, e.g. not be extended
+/// by `#[repr(align(...))]`. A structure with no fields or with more than one
+/// field is rejected.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName(u32);
+/// # type FieldType = u32;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() == core::mem::size_of::<FieldType>(),
+///    "size of 'StructName' (ActualSize bytes) is not equal to size of its backing 'FieldType' (FieldSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (flag set stored as its backing integer):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_eq_backing]
+/// #[repr(transparent)]
+/// #[derive(Clone, Copy, PartialEq, Eq)]
+/// struct Permissions(u8);
+///
+/// impl Permissions {
+///     const READ: Self = Self(1 << 0);
+///     const WRITE: Self = Self(1 << 1);
+///
+///     const fn union(self, other: Self) -> Self {
+///         Self(self.0 | other.0)
+///     }
+/// }
+/// # assert!(Permissions::READ.union(Permissions::WRITE) == Permissions(3));
+/// ```
+///
+/// Failure (structure with two fields):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_eq_backing]
+/// #[repr(C)]
+/// struct Permissions(u8, u8);
+/// ```
+///
+/// Failure (real size is 8 bytes, size of `u8` is 1 byte):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_eq_backing]
+/// #[repr(C, align(8))]
+/// struct Permissions(u8);
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_eq_backing(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        syn::parse::<Nothing>(attr)?;
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+        let ty = self_type(id, &struct_item.generics)?;
+
+        let field = match struct_item.fields.iter().collect::<Vec<_>>()[..] {
+            [field] => field,
+            [] => {
+                return Err(syn::Error::new_spanned(
+                    id,
+                    "expected a structure with exactly one field, found 0",
+                ))
+            }
+            ref fields => {
+                return Err(syn::Error::new_spanned(
+                    &struct_item.fields,
+                    format!(
+                        "expected a structure with exactly one field, found {}",
+                        fields.len()
+                    ),
+                ))
+            }
+        };
+        let field_ty = static_lifetimes(&field.ty, &struct_item.generics);
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let backing = quote!(::core::mem::size_of::<#field_ty>());
+        let check = emit_check(
+            Span::call_site(),
+            quote!(#actual == #backing),
+            &[
+                MessagePart::Text(format!("size of '{}' (", qualified(id))),
+                MessagePart::Value(actual),
+                MessagePart::Text(format!(
+                    " bytes) is not equal to size of its backing '{}' (",
+                    tokens_to_string(&field.ty)
+                )),
+                MessagePart::Value(backing),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// Returns `true` if `ty` is zero-sized as written: `PhantomData`,
+/// `PhantomPinned`, the unit type, or a tuple or an array of length 0 or of
+/// such types. Other types, e.g. user-defined markers, may not be.
+fn known_zst(ty: &Type) -> bool {
+    match ty {
+        Type::Paren(paren) => known_zst(&paren.elem),
+        Type::Group(group) => known_zst(&group.elem),
+        Type::Tuple(tuple) => tuple.elems.iter().all(known_zst),
+        Type::Array(array) => {
+            known_zst(&array.elem)
+                || matches!(&array.len, Expr::Lit(ExprLit { lit: Lit::Int(len), .. })
+                    if len.base10_parse::<usize>().is_ok_and(|len| len == 0))
+        }
+        Type::Path(path) => {
+            path.qself.is_none()
+                && path.path.segments.last().is_some_and(|last| {
+                    last.ident == "PhantomData" || last.ident == "PhantomPinned"
+                })
+        }
+        _ => false,
+    }
+}
+
+/// The attribute `#[assert_transparent]` performs **compile-time** checks, if the
+/// structure it is attached to has the size and the alignment of its only field
+/// that is not zero-sized.
+///
+/// It asserts the equivalence `#[repr(transparent)]` promises, e.g. for wrappers
+/// passed across FFI in place of the wrapped type, and keeps holding it when
+/// the wrapper is refactored: an added field with a size of its own is a
+/// compile error. The representation itself is not required, so structures
+/// meant to be equivalent under another representation are checked the same way.
+///
+/// The fields are told apart as written. `PhantomData`, `PhantomPinned`, the
+/// unit type `()` and arrays of length 0 are known to be zero-sized; every other
+/// field may not be, a user-defined marker type included, which is then to be
+/// wrapped in `PhantomData`. A structure with no such field, or with more than
+/// one, is rejected. A zero-sized field may still raise the alignment, e.g.
+/// `[u64; 0]` next to a `u32`, which the alignment check catches.
+///
+/// Checks do not pollute namespace, they are expanded to anonymous consts
+/// equivalent to
+/// ```
+/// # struct StructName(u32);
+/// # type FieldType = u32;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() == core::mem::size_of::<FieldType>(),
+///    "size of 'StructName' (ActualSize bytes) is not equal to size of its field 'FieldType' (FieldSize bytes)"
+/// );
+/// const _: () = assert!(
+///    core::mem::align_of::<StructName>() == core::mem::align_of::<FieldType>(),
+///    "alignment of 'StructName' (ActualAlign bytes) is not equal to alignment of its field 'FieldType' (FieldAlign bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (transparent wrapper with a zero-sized marker):
+///
+/// ```
+/// # use pakr_assert_size::*;
+/// use core::marker::PhantomData;
+///
+/// pub struct Metric;
+///
+/// #[assert_transparent]
+/// #[repr(transparent)]
+/// pub struct Meters {
+///     value: f64,
+///     _unit: PhantomData<Metric>,
+/// }
+/// ```
+///
+/// Success (several zero-sized fields along with the wrapped one):
+///
+/// ```
+/// # use pakr_assert_size::*;
+/// use core::marker::{PhantomData, PhantomPinned};
+///
+/// #[assert_transparent]
+/// #[repr(transparent)]
+/// pub struct Handle(*mut u8, PhantomData<*mut u8>, PhantomPinned, [u8; 0]);
+/// ```
+///
+/// Failure (two fields that may not be zero-sized):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_transparent]
+/// #[repr(C)]
+/// pub struct Tagged {
+///     value: u32,
+///     tag: u8,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_transparent(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        syn::parse::<Nothing>(attr)?;
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+        let ty = self_type(id, &struct_item.generics)?;
+
+        let sized = struct_item
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| !known_zst(&field.ty))
+            .collect::<Vec<_>>();
+        let field = match sized[..] {
+            [(_, field)] => field,
+            [] => {
+                return Err(syn::Error::new_spanned(
+                    id,
+                    format!(
+                        "'{}' has no field that is not zero-sized, expected exactly one",
+                        id
+                    ),
+                ))
+            }
+            ref fields => {
+                let names = fields.iter().map(|(index, field)| match &field.ident {
+                    Some(ident) => format!("`{}`", ident.unraw()),
+                    None => format!("`{}`", index),
+                });
+                // Reported at the first field in excess of the one.
+                return Err(syn::Error::new_spanned(
+                    fields[1].1,
+                    format!(
+                        "'{}' has {} fields that may not be zero-sized, {}, expected exactly \
+                         one; wrap zero-sized markers in `PhantomData`",
+                        id,
+                        fields.len(),
+                        names.collect::<Vec<_>>().join(", ")
+                    ),
+                ));
+            }
+        };
+        let field_ty = static_lifetimes(&field.ty, &struct_item.generics);
+
+        let mut checks = TokenStream2::new();
+        for (quantity, function) in [("size", quote!(size_of)), ("alignment", quote!(align_of))] {
+            let actual = quote!(::core::mem::#function::<#ty>());
+            let expected = quote!(::core::mem::#function::<#field_ty>());
+            checks.extend(emit_check(
+                Span::call_site(),
+                quote!(#actual == #expected),
+                &[
+                    MessagePart::Text(format!("{} of '{}' (", quantity, qualified(id))),
+                    MessagePart::Value(actual),
+                    MessagePart::Text(format!(
+                        " bytes) is not equal to {} of its field '{}' (",
+                        quantity,
+                        tokens_to_string(&field.ty)
+                    )),
+                    MessagePart::Value(expected),
+                    MessagePart::Text(" bytes)".into()),
+                ],
+            ));
+        }
+
+        let checker = quote! {
+            #checks
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_align_eq_type(TYPE)]` performs **compile-time** check,
+/// if the structure it is attached to has exactly the same alignment as the
+/// given type.
+///
+/// It is the alignment counterpart of `#[assert_size_eq_type]`, e.g. for
+/// reinterpreting slices of one type as slices of another. Together they give
+/// the guarantee of `#[assert_same_layout]` piecewise.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # struct OtherType{}
+/// #
+/// const _: () = assert!(
+///    core::mem::align_of::<StructName>() == core::mem::align_of::<OtherType>(),
+///    "alignment of 'StructName' (ActualAlign bytes) is not equal to alignment of 'OtherType' (OtherAlign bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (pair of `u32` is aligned like `u32`):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_align_eq_type(u32)]
+/// #[repr(C)]
+/// struct Pair {
+///     first: u32,
+///     second: u32,
+/// }
+/// ```
+///
+/// Failure (byte array is aligned to 1 byte, `u32` to 4 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_align_eq_type(u32)]
+/// #[repr(C)]
+/// struct Bytes([u8; 4]);
+/// ```
+#[proc_macro_attribute]
+pub fn assert_align_eq_type(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let ExpType { ty: other } = syn::parse::<ExpType>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::align_of::<#ty>());
+        let expected = quote!(::core::mem::align_of::<#other>());
+        let check = emit_check(
+            other.span(),
+            quote!(#actual == #expected),
+            &[
+                MessagePart::Text(format!("alignment of '{}' (", qualified(id))),
+                MessagePart::Value(actual),
+                MessagePart::Text(format!(
+                    " bytes) is not equal to alignment of '{}' (",
+                    tokens_to_string(&other)
+                )),
+                MessagePart::Value(expected),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_same_layout(TYPE)]` performs **compile-time** check,
+/// if the structure it is attached to has exactly the same size **and**
+/// alignment as the given type.
+///
+/// It is the precondition of transmuting between the two types and of
+/// `#[repr(transparent)]` newtypes, where the size equality alone is not enough.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # struct OtherType{}
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() == core::mem::size_of::<OtherType>()
+///        && core::mem::align_of::<StructName>() == core::mem::align_of::<OtherType>(),
+///    "layout of 'StructName' (size: ActualSize bytes, alignment: ActualAlign bytes) differs from 'OtherType' (size: OtherSize bytes, alignment: OtherAlign bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (transparent wrapper has the layout of the wrapped type):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_same_layout(u64)]
+/// #[repr(transparent)]
+/// struct Handle(u64);
+/// ```
+///
+/// Failure (sizes match, but alignment is 1 byte instead of 8 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_same_layout(u64)]
+/// #[repr(C)]
+/// struct Handle([u8; 8]);
+/// ```
+#[proc_macro_attribute]
+pub fn assert_same_layout(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let ExpType { ty: other } = syn::parse::<ExpType>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let size = quote!(::core::mem::size_of::<#ty>());
+        let align = quote!(::core::mem::align_of::<#ty>());
+        let other_size = quote!(::core::mem::size_of::<#other>());
+        let other_align = quote!(::core::mem::align_of::<#other>());
+        let check = emit_check(
+            other.span(),
+            quote!(#size == #other_size && #align == #other_align),
+            &[
+                MessagePart::Text(format!("layout of '{}' (size: ", qualified(id))),
+                MessagePart::Value(size),
+                MessagePart::Text(" bytes, alignment: ".into()),
+                MessagePart::Value(align),
+                MessagePart::Text(format!(
+                    " bytes) differs from '{}' (size: ",
+                    tokens_to_string(&other)
+                )),
+                MessagePart::Value(other_size),
+                MessagePart::Text(" bytes, alignment: ".into()),
+                MessagePart::Value(other_align),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_layout_compatible(TYPE)]` performs **compile-time**
+/// check, if the structure it is attached to has the same size and alignment
+/// as the given structure, and every one of its fields has the same offset and
+/// size as the field of the same name in the other one.
+///
+/// It is meant for verified `transmute` between two structurally identical
+/// `#[repr(C)]` types, e.g. a generated binding and its hand-written mirror,
+/// where equal sizes alone may hide swapped or resized fields. Fields named
+/// differently in the other structure are paired up after the type, e.g.
+/// `#[assert_layout_compatible(ffi::header_t, kind = r#type)]`; the others are
+/// looked up by their own name, and a name missing in the other structure is
+/// an error. Fields of the other structure are only covered as far as the
+/// annotated one names them, so the equal sizes guard against extra ones. Tuple
+/// structures are compared by field index.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// comparing size and alignment like `#[assert_same_layout(TYPE)]`, and for
+/// every field to an anonymous const equivalent to
+/// ```
+/// # struct StructName{ field: u32 }
+/// # struct OtherType{ field: u32 }
+/// #
+/// const _: () = assert!(
+///    core::mem::offset_of!(StructName, field) == core::mem::offset_of!(OtherType, field)
+///        && core::mem::size_of::<u32>() == core::mem::size_of::<u32>(),
+///    "field 'StructName::field' (offset: Offset bytes, size: Size bytes) does not match 'OtherType::field' (offset: OtherOffset bytes, size: OtherSize bytes)"
+/// );
+/// ```
+///
+/// where the size of the field of the other structure is inferred from a
+/// pointer to it, as its type is not known to the attribute.
+///
+/// # Examples
+///
+/// Success (hand-written mirror of a generated binding):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// mod ffi {
+///     #[repr(C)]
+///     pub struct header_t {
+///         pub r#type: u16,
+///         pub flags: u16,
+///         pub len: u32,
+///     }
+/// }
+///
+/// #[assert_layout_compatible(ffi::header_t, kind = r#type)]
+/// #[repr(C)]
+/// struct Header {
+///     kind: u16,
+///     flags: u16,
+///     len: u32,
+/// }
+/// ```
+///
+/// Success (tuple structures, compared by index):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Raw(u32, [u8; 4]);
+///
+/// #[assert_layout_compatible(Raw)]
+/// #[repr(C)]
+/// struct Pair(u32, u32);
+/// ```
+///
+/// Failure (same size and alignment, but `flags` and `kind` are swapped):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Binding {
+///     flags: u16,
+///     kind: u16,
+///     len: u32,
+/// }
+///
+/// #[assert_layout_compatible(Binding)]
+/// #[repr(C)]
+/// struct Header {
+///     kind: u16,
+///     flags: u16,
+///     len: u32,
+/// }
+/// ```
+///
+/// Failure (the mirror is missing a field, so the sizes differ):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Binding {
+///     kind: u16,
+///     flags: u16,
+///     len: u32,
+///     crc: u32,
+/// }
+///
+/// #[assert_layout_compatible(Binding)]
+/// #[repr(C)]
+/// struct Header {
+///     kind: u16,
+///     flags: u16,
+///     len: u32,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_layout_compatible(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let CompatibleType { ty: other, renames } = syn::parse::<CompatibleType>(attr)?;
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+        let ty = self_type(id, &struct_item.generics)?;
+        for (field, _) in &renames {
+            field_type(&struct_item, field)?;
+        }
+
+        let size = quote!(::core::mem::size_of::<#ty>());
+        let align = quote!(::core::mem::align_of::<#ty>());
+        let other_size = quote!(::core::mem::size_of::<#other>());
+        let other_align = quote!(::core::mem::align_of::<#other>());
+        let other_name = tokens_to_string(&other);
+        let mut checks = emit_check(
+            other.span(),
+            quote!(#size == #other_size && #align == #other_align),
+            &[
+                MessagePart::Text(format!("layout of '{}' (size: ", qualified(id))),
+                MessagePart::Value(size),
+                MessagePart::Text(" bytes, alignment: ".into()),
+                MessagePart::Value(align),
+                MessagePart::Text(format!(" bytes) differs from '{}' (size: ", other_name)),
+                MessagePart::Value(other_size),
+                MessagePart::Text(" bytes, alignment: ".into()),
+                MessagePart::Value(other_align),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        );
+
+        for (index, field) in struct_item.fields.iter().enumerate() {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(index.into()),
+            };
+            let counterpart = renames
+                .iter()
+                .find(|(field, _)| *field == member)
+                .map_or(&member, |(_, other)| other);
+            let field_ty = static_lifetimes(&field.ty, &struct_item.generics);
+            let offset = emit_offset_of(&ty, &member);
+            let field_size = quote!(::core::mem::size_of::<#field_ty>());
+            let other_offset = emit_offset_of(&other, counterpart);
+            let other_field_size = emit_field_size(&other, counterpart);
+            checks.extend(emit_check(
+                member.span(),
+                quote!(#offset == #other_offset && #field_size == #other_field_size),
+                &[
+                    MessagePart::Text(format!(
+                        "field '{}::{}' (offset: ",
+                        qualified(id),
+                        tokens_to_string(&member)
+                    )),
+                    MessagePart::Value(offset),
+                    MessagePart::Text(" bytes, size: ".into()),
+                    MessagePart::Value(field_size),
+                    MessagePart::Text(format!(
+                        " bytes) does not match '{}::{}' (offset: ",
+                        other_name,
+                        tokens_to_string(counterpart)
+                    )),
+                    MessagePart::Value(other_offset),
+                    MessagePart::Text(" bytes, size: ".into()),
+                    MessagePart::Value(other_field_size),
+                    MessagePart::Text(" bytes)".into()),
+                ],
+            ));
+        }
+
+        let checker = quote! {
+            #checks
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_fits_in(TYPE)]` performs **compile-time** check, if
+/// the structure it is attached to fits in the storage of the given type, i.e.
+/// it is neither bigger nor more strictly aligned.
+///
+/// It is meant for union-style storage, where a value of the structure is
+/// written into the bytes of a bigger type. Both size and alignment are
+/// checked, as either one alone does not make such a store sound.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # struct OtherType{}
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() <= core::mem::size_of::<OtherType>()
+///        && core::mem::align_of::<StructName>() <= core::mem::align_of::<OtherType>(),
+///    "'StructName' (size: ActualSize bytes, alignment: ActualAlign bytes) does not fit in 'OtherType' (size: OtherSize bytes, alignment: OtherAlign bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (small type fits in the storage of a large one):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_fits_in([u64; 4])]
+/// #[repr(C)]
+/// struct Small {
+///     tag: u32,
+///     len: u32,
+/// }
+/// ```
+///
+/// Failure (size fits, but alignment of 8 bytes is stricter than 1 byte):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_fits_in([u8; 16])]
+/// #[repr(C)]
+/// struct Small {
+///     value: u64,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_fits_in(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let ExpType { ty: other } = syn::parse::<ExpType>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let size = quote!(::core::mem::size_of::<#ty>());
+        let align = quote!(::core::mem::align_of::<#ty>());
+        let other_size = quote!(::core::mem::size_of::<#other>());
+        let other_align = quote!(::core::mem::align_of::<#other>());
+        let check = emit_check(
+            other.span(),
+            quote!(#size <= #other_size && #align <= #other_align),
+            &[
+                MessagePart::Text(format!("'{}' (size: ", qualified(id))),
+                MessagePart::Value(size),
+                MessagePart::Text(" bytes, alignment: ".into()),
+                MessagePart::Value(align),
+                MessagePart::Text(format!(
+                    " bytes) does not fit in '{}' (size: ",
+                    tokens_to_string(&other)
+                )),
+                MessagePart::Value(other_size),
+                MessagePart::Text(" bytes, alignment: ".into()),
+                MessagePart::Value(other_align),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_size_eq_max(TYPE, ...)]` performs **compile-time**
+/// check, if the structure it is attached to has exactly the size of the largest
+/// of the given types.
+///
+/// It is meant for `#[repr(C)]` unions, which must be exactly as big as their
+/// largest member, without hardcoding the size.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # struct TypeA{}
+/// # struct TypeB{}
+/// # const fn max(a: usize, b: usize) -> usize { if a > b { a } else { b } }
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>()
+///        == max(core::mem::size_of::<TypeA>(), core::mem::size_of::<TypeB>()),
+///    "size of 'StructName' (ActualSize bytes) is not equal to the largest size of 'TypeA', 'TypeB' (MaxSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (union is as big as its largest member):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_eq_max(u8, [u8; 4], u16)]
+/// #[repr(C)]
+/// union Value {
+///     byte: u8,
+///     bytes: [u8; 4],
+///     word: u16,
+/// }
+/// ```
+///
+/// Failure (real size is 8 bytes, the largest listed type takes 4 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_eq_max(u8, [u8; 4], u16)]
+/// #[repr(C)]
+/// union Value {
+///     byte: u8,
+///     bytes: [u8; 4],
+///     word: u16,
+///     long: u64,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_eq_max(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let ExpTypes { types } = syn::parse::<ExpTypes>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let largest = types.iter().fold(
+            quote!(0),
+            |largest, ty| quote!(max(#largest, ::core::mem::size_of::<#ty>())),
+        );
+        let expected = quote! {{
+            const fn max(a: usize, b: usize) -> usize {
+                if a > b {
+                    a
+                } else {
+                    b
+                }
+            }
+            #largest
+        }};
+        let names = types
+            .iter()
+            .map(|ty| format!("'{}'", tokens_to_string(ty)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let check = emit_check(
+            types.span(),
+            quote!(#actual == #expected),
+            &[
+                MessagePart::Text(format!("size of '{}' (", qualified(id))),
+                MessagePart::Value(actual),
+                MessagePart::Text(format!(
+                    " bytes) is not equal to the largest size of {} (",
+                    names
+                )),
+                MessagePart::Value(expected),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// Returns the sum of sizes of all fields of `struct_item`, whose type is `ty`,
+/// along with the message parts listing every field with its size and offset,
+/// `; fields: name (SIZE bytes at offset OFFSET), ...`.
+fn field_layout(struct_item: &ItemStruct, ty: &TokenStream2) -> (TokenStream2, Vec<MessagePart>) {
+    let field_types = struct_item
+        .fields
+        .iter()
+        .map(|field| static_lifetimes(&field.ty, &struct_item.generics));
+    let field_sum = quote!(0 #(+ ::core::mem::size_of::<#field_types>())*);
+
+    let mut message = Vec::new();
+    for (index, field) in struct_item.fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        let field_ty = static_lifetimes(&field.ty, &struct_item.generics);
+        let separator = if index == 0 { "; fields: " } else { ", " };
+        message.extend([
+            MessagePart::Text(format!("{}{} (", separator, tokens_to_string(&member))),
+            MessagePart::Value(quote!(::core::mem::size_of::<#field_ty>())),
+            MessagePart::Text(" bytes at offset ".into()),
+            MessagePart::Value(emit_offset_of(ty, &member)),
+            MessagePart::Text(")".into()),
+        ]);
+    }
+    (field_sum, message)
+}
+
+/// The attribute `#[assert_no_padding]` performs **compile-time** check, if the
+/// structure it is attached to contains no padding bytes, i.e. its size is
+/// equal to the sum of sizes of all its fields.
+///
+/// It is useful for `#[repr(C)]` structures serialized as raw bytes, where any
+/// compiler-inserted padding is a silent bug. The attribute can be attached to
+/// named, tuple and unit structures.
+///
+/// Unlike the size, the fields are taken from the definition as the attribute
+/// receives it. Attributes are expanded top to bottom, so place it below any
+/// attribute macro rewriting the fields; a field added later makes the check
+/// fail rather than pass.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{ field1: u8, field2: u8 }
+/// # type Field1Type = u8;
+/// # type Field2Type = u8;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>()
+///        == 0 + core::mem::size_of::<Field1Type>() + core::mem::size_of::<Field2Type>(),
+///    "'StructName' has padding bytes (size: ActualSize bytes, sum of field sizes: FieldSum bytes; \
+///     fields: field1 (Field1Size bytes at offset Field1Offset), field2 (Field2Size bytes at offset Field2Offset))"
+/// );
+/// ```
+///
+/// The failure message lists every field with its size and offset in
+/// declaration order, so the padding shows up as the gap between the end of one
+/// field and the offset of the next (or the end of the structure), e.g.
+/// `'C' has padding bytes (size: 16 bytes, sum of field sizes: 9 bytes; fields:
+/// field1 (8 bytes at offset 0), field2 (1 bytes at offset 8))`, where the 7
+/// bytes after `field2` are padding.
+///
+/// # Examples
+///
+/// Success (packed structure has no padding):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_no_padding]
+/// #[repr(C, packed)]
+/// struct A {
+///     field1: u64,
+///     field2: u8,
+/// }
+/// ```
+///
+/// Success (tuple structure with naturally aligned fields):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_no_padding]
+/// #[repr(C)]
+/// struct B(u32, u16, u16);
+/// ```
+///
+/// Success (unit structure has no fields and no padding):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_no_padding]
+/// struct Marker;
+/// ```
+///
+/// Failure (tuple structure, real size is 8 bytes, fields take 5 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_no_padding]
+/// #[repr(C)]
+/// struct Tagged(u8, u32);
+/// ```
+///
+/// Failure (real size is 16 bytes, fields take 9 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_no_padding]
+/// #[repr(C)]
+/// struct C {
+///     field1: u64,
+///     field2: u8,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_no_padding(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        syn::parse::<Nothing>(attr)?;
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+        let ty = self_type(id, &struct_item.generics)?;
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let (field_sum, fields) = field_layout(&struct_item, &ty);
+
+        let mut message = vec![
+            MessagePart::Text(format!("'{}' has padding bytes (size: ", qualified(id))),
+            MessagePart::Value(actual.clone()),
+            MessagePart::Text(" bytes, sum of field sizes: ".into()),
+            MessagePart::Value(field_sum.clone()),
+            MessagePart::Text(" bytes".into()),
+        ];
+        message.extend(fields);
+        message.push(MessagePart::Text(")".into()));
+
+        let check = emit_check(Span::call_site(), quote!(#actual == #field_sum), &message);
+
+        let checker = quote! {
+            #check
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_pod_layout(USIZE)]` performs **compile-time** check,
+/// if the structure it is attached to has the exact size in bytes and contains
+/// no padding bytes.
+///
+/// It combines `#[assert_size]` and `#[assert_no_padding]` into the single
+/// layout guarantee required from plain-old-data types, e.g. ones implementing
+/// `bytemuck::Pod` or read from raw bytes: every byte of the value belongs to a
+/// field, and there are exactly as many of them as expected. Both conditions
+/// are reported by one message. The expected size may be followed by
+/// `msg = "..."` replacing it, like in `#[assert_size]`.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{ field1: u8, field2: u8 }
+/// # type Field1Type = u8;
+/// # type Field2Type = u8;
+/// # const ExpectedSize: usize = 2;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() == ExpectedSize
+///        && core::mem::size_of::<StructName>()
+///            == 0 + core::mem::size_of::<Field1Type>() + core::mem::size_of::<Field2Type>(),
+///    "'StructName' is not plain old data of ExpectedSize bytes without padding (size: ActualSize bytes, \
+///     sum of field sizes: FieldSum bytes; fields: field1 (Field1Size bytes at offset Field1Offset), \
+///     field2 (Field2Size bytes at offset Field2Offset))"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (packed structure of the right size has no padding):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_pod_layout(13)]
+/// #[repr(C, packed)]
+/// struct Record {
+///     tag: u8,
+///     len: u32,
+///     id: u64,
+/// }
+/// ```
+///
+/// Failure (real size is 16 bytes, fields take 13 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_pod_layout(16)]
+/// #[repr(C)]
+/// struct Record {
+///     tag: u8,
+///     len: u32,
+///     id: u64,
+/// }
+/// ```
+///
+/// Failure (no padding, but real size is 13 bytes, expected is 16 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_pod_layout(16)]
+/// #[repr(C, packed)]
+/// struct Record {
+///     tag: u8,
+///     len: u32,
+///     id: u64,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_pod_layout(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let size = syn::parse::<ExpSize>(attr)?;
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+        let ty = self_type(id, &struct_item.generics)?;
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let (field_sum, fields) = field_layout(&struct_item, &ty);
+
+        let mut message = vec![
+            MessagePart::Text(format!(
+                "'{}' is not plain old data of {} bytes without padding (size: ",
+                qualified(id),
+                size
+            )),
+            MessagePart::Value(actual.clone()),
+            MessagePart::Text(" bytes, sum of field sizes: ".into()),
+            MessagePart::Value(field_sum.clone()),
+            MessagePart::Text(" bytes".into()),
+        ];
+        message.extend(fields);
+        message.push(MessagePart::Text(")".into()));
+
+        let check = emit_check(
+            size.span(),
+            quote!(#actual == #size && #actual == #field_sum),
+            &size.message(message),
+        );
+
+        let checker = quote! {
+            #check
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_zerocopy(USIZE)]` performs **compile-time** checks,
+/// if the structure it is attached to has the exact size in bytes and contains
+/// no padding bytes, the layout expectations of `zerocopy::FromBytes`.
+///
+/// Together with a defined representation, `#[repr(C)]`, `#[repr(transparent)]`
+/// or `#[repr(packed)]`, required at expansion time, they are necessary
+/// conditions for reading the structure from raw bytes without a copy: the
+/// layout doesn't change between compiler versions, every byte of the value
+/// belongs to a field and there are exactly as many of them as the wire format
+/// has. They are not sufficient, the fields have to accept any bit pattern as
+/// well, which only `zerocopy`'s derives check; the crate doesn't depend on
+/// `zerocopy`, so the attribute may be used alongside them or in their place.
+///
+/// Unlike `#[assert_pod_layout]`, the exact size and the padding are checked
+/// separately, each reported by its own message like `#[assert_size]` and
+/// `#[assert_no_padding]` do. The expected size may be followed by
+/// `msg = "..."` replacing the message of the size check.
+///
+/// Checks do not pollute namespace, they are expanded to anonymous consts
+/// equivalent to
+/// ```
+/// # struct StructName{ field1: u8, field2: u8 }
+/// # type Field1Type = u8;
+/// # type Field2Type = u8;
+/// # const ExpectedSize: usize = 2;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() == ExpectedSize,
+///    "size of 'ModulePath::StructName' is not ExpectedSize bytes (actual size: \
+///     ActualSize bytes, next power of two: RoundedSize bytes)"
+/// );
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>()
+///        == 0 + core::mem::size_of::<Field1Type>() + core::mem::size_of::<Field2Type>(),
+///    "'ModulePath::StructName' has padding bytes (size: ActualSize bytes, sum of field sizes: \
+///     FieldSum bytes; fields: field1 (Field1Size bytes at offset Field1Offset), \
+///     field2 (Field2Size bytes at offset Field2Offset))"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (fields of a `#[repr(C)]` header are aligned without padding):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_zerocopy(16)]
+/// #[repr(C)]
+/// struct Header {
+///     magic: u32,
+///     len: u32,
+///     seq: u64,
+/// }
+/// ```
+///
+/// Failure (real size is 16 bytes, but fields take 13 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_zerocopy(16)]
+/// #[repr(C)]
+/// struct Header {
+///     kind: u8,
+///     len: u32,
+///     seq: u64,
+/// }
+/// ```
+///
+/// Failure (no defined representation):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_zerocopy(16)]
+/// struct Header {
+///     magic: u32,
+///     len: u32,
+///     seq: u64,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_zerocopy(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let size = syn::parse::<ExpSize>(attr)?;
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+        let ty = self_type(id, &struct_item.generics)?;
+
+        let defined = reprs(&struct_item.attrs).any(|nested| match nested {
+            NestedMeta::Meta(meta) => ["C", "transparent", "packed"]
+                .iter()
+                .any(|repr| meta.path().is_ident(repr)),
+            _ => false,
+        });
+        if !defined {
+            return Err(syn::Error::new(
+                id.span(),
+                format!(
+                    "'{}' has no defined layout for zero-copy access, \
+                     add `#[repr(C)]`, `#[repr(transparent)]` or `#[repr(packed)]`",
+                    id
+                ),
+            ));
+        }
+
+        let checks = emit_size_and_padding(&struct_item, &ty, &size);
+
+        let checker = quote! {
+            #checks
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// Emits the checks of `#[assert_zerocopy]` and `#[assert_zeroable_layout]`: the
+/// exact size of the structure of type `ty`, reported at its name, and the lack
+/// of padding, each with its own message.
+fn emit_size_and_padding(
+    struct_item: &ItemStruct,
+    ty: &TokenStream2,
+    size: &ExpSize,
+) -> TokenStream2 {
+    let id = &struct_item.ident;
+    let actual = quote!(::core::mem::size_of::<#ty>());
+    let size_check = emit_check(
+        id.span(),
+        quote!(#actual == #size),
+        &size.message(Comparison::Eq.message(&qualified(id), size, "", actual.clone())),
+    );
+
+    let (field_sum, fields) = field_layout(struct_item, ty);
+    let mut message = vec![
+        MessagePart::Text(format!("'{}' has padding bytes (size: ", qualified(id))),
+        MessagePart::Value(actual.clone()),
+        MessagePart::Text(" bytes, sum of field sizes: ".into()),
+        MessagePart::Value(field_sum.clone()),
+        MessagePart::Text(" bytes".into()),
+    ];
+    message.extend(fields);
+    message.push(MessagePart::Text(")".into()));
+    let padding_check = emit_check(Span::call_site(), quote!(#actual == #field_sum), &message);
+
+    quote! {
+        #size_check
+        #padding_check
+    }
+}
+
+/// The attribute `#[assert_zeroable_layout(USIZE)]` performs **compile-time**
+/// checks, if the structure it is attached to has the exact size in bytes and
+/// contains no padding bytes, the layout preconditions this crate checks for
+/// types implementing `bytemuck::Zeroable`.
+///
+/// `Zeroable` promises that the all-zero bit pattern is a valid value of the
+/// type, which no layout check can prove: a reference, a `NonZeroU32` or an
+/// enumeration without a zero discriminant is never zero. The attribute
+/// therefore appends a note to the documentation of the structure, ``Layout
+/// checked by `#[assert_zeroable_layout]`; verify by hand that every field
+/// accepts the all-zero bit pattern.``, in a paragraph of its own, as a reminder
+/// for whoever implements the trait. The crate doesn't depend on `bytemuck`.
+///
+/// Unlike `#[assert_zerocopy]`, a defined representation is not required, as
+/// `Zeroable` does not require one. The checks are the ones of
+/// `#[assert_zerocopy]`, each reported by its own message, and the expected size
+/// may be followed by `msg = "..."` replacing the message of the size check.
+///
+/// Checks do not pollute namespace, they are expanded to anonymous consts
+/// equivalent to
+/// ```
+/// # struct StructName{ field1: u8, field2: u8 }
+/// # type Field1Type = u8;
+/// # type Field2Type = u8;
+/// # const ExpectedSize: usize = 2;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() == ExpectedSize,
+///    "size of 'ModulePath::StructName' is not ExpectedSize bytes (actual size: \
+///     ActualSize bytes, next power of two: RoundedSize bytes)"
+/// );
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>()
+///        == 0 + core::mem::size_of::<Field1Type>() + core::mem::size_of::<Field2Type>(),
+///    "'ModulePath::StructName' has padding bytes (size: ActualSize bytes, sum of field sizes: \
+///     FieldSum bytes; fields: field1 (Field1Size bytes at offset Field1Offset), \
+///     field2 (Field2Size bytes at offset Field2Offset))"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (fields of a `#[repr(C)]` vertex are aligned without padding):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// /// Vertex uploaded to a GPU buffer.
+/// #[assert_zeroable_layout(16)]
+/// #[repr(C)]
+/// pub struct Vertex {
+///     position: [f32; 3],
+///     color: u32,
+/// }
+/// ```
+///
+/// Failure (real size is 8 bytes, but fields take 5 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_zeroable_layout(8)]
+/// #[repr(C)]
+/// pub struct Sample {
+///     flag: u8,
+///     value: u32,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_zeroable_layout(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let size = syn::parse::<ExpSize>(attr)?;
+
+        let mut struct_item = syn::parse::<ItemStruct>(item)?;
+        let ty = self_type(&struct_item.ident, &struct_item.generics)?;
+
+        let checks = emit_size_and_padding(&struct_item, &ty, &size);
+
+        // Bit validity is left to the reader, the note says so in rustdoc.
+        struct_item.attrs.push(syn::parse_quote!(#[doc = ""]));
+        struct_item.attrs.push(syn::parse_quote!(
+            #[doc = "Layout checked by `#[assert_zeroable_layout]`; verify by hand that every field accepts the all-zero bit pattern."]
+        ));
+
+        let checker = quote! {
+            #checks
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_packed(USIZE)]` performs **compile-time** checks,
+/// if the structure it is attached to has the exact size in bytes and the
+/// alignment of 1 byte.
+///
+/// It proves that `#[repr(packed)]` took effect: the padding between fields is
+/// gone when the size matches, and the alignment of 1 byte keeps arrays and
+/// enclosing structures from adding it back. The size and the alignment are
+/// checked separately, so the message tells which of them failed. The expected
+/// size may be followed by `msg = "..."` replacing the message of the size
+/// check.
+///
+/// Checks do not pollute namespace, they are expanded to anonymous consts
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const ExpectedSize: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() == ExpectedSize,
+///    "size of 'ModulePath::StructName' is not ExpectedSize bytes (actual size: \
+///     ActualSize bytes, next power of two: RoundedSize bytes)"
+/// );
+/// const _: () = assert!(
+///    core::mem::align_of::<StructName>() == 1,
+///    "'ModulePath::StructName' is not packed (actual alignment: ActualAlign bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (packed structure):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_packed(7)]
+/// #[repr(C, packed)]
+/// struct Record {
+///     tag: u8,
+///     len: u16,
+///     id: u32,
+/// }
+/// ```
+///
+/// Failure (fields take 8 bytes without packing, but the alignment is 4 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_packed(8)]
+/// #[repr(C)]
+/// struct Record {
+///     len: u16,
+///     flags: u16,
+///     id: u32,
+/// }
+/// ```
+///
+/// Failure (`packed(2)` keeps the alignment of 2 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_packed(6)]
+/// #[repr(C, packed(2))]
+/// struct Record {
+///     len: u16,
+///     id: u32,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_packed(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let size = syn::parse::<ExpSize>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let size_check = emit_check(
+            id.span(),
+            quote!(#actual == #size),
+            &size.message(Comparison::Eq.message(&qualified(id), &size, "", actual.clone())),
+        );
+
+        let align = quote!(::core::mem::align_of::<#ty>());
+        let align_check = emit_check(
+            Span::call_site(),
+            quote!(#align == 1),
+            &[
+                MessagePart::Text(format!(
+                    "'{}' is not packed (actual alignment: ",
+                    qualified(id)
+                )),
+                MessagePart::Value(align),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #size_check
+            #align_check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_size_fields(each = USIZE)]` performs **compile-time**
+/// check, if the structure it is attached to has the size of its field count
+/// times the given number of bytes.
+///
+/// It is meant for homogeneous structures, often generated, where every field
+/// takes the same number of bytes and any padding is a mistake. The field count
+/// is taken at expansion time, so adding or removing a field needs no update of
+/// the attribute. The attribute can be attached to named, tuple and unit
+/// structures.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const FieldCount: usize=0;
+/// # const Each: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<StructName>() == FieldCount * Each,
+///    "size of 'StructName' is not FieldCount fields of Each bytes (actual size: ActualSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (three `u64` fields take 24 bytes):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fields(each = 8)]
+/// #[repr(C, packed)]
+/// struct Coords {
+///     x: u64,
+///     y: u64,
+///     z: u64,
+/// }
+/// ```
+///
+/// Success (tuple structure):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fields(each = 4)]
+/// #[repr(C)]
+/// struct Rgba(f32, f32, f32, f32);
+/// ```
+///
+/// Failure (real size is 12 bytes, 2 fields of 4 bytes take 8 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size_fields(each = 4)]
+/// #[repr(C)]
+/// struct Pair {
+///     first: u32,
+///     second: u64,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_size_fields(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let parser = Punctuated::<LayoutArg, Token![,]>::parse_terminated;
+        let mut args = Vec::new();
+        for arg in parser.parse(attr)? {
+            push_layout_arg(&mut args, arg, &["each"])?;
+        }
+        let each = match args.pop() {
+            Some(LayoutArg { value, .. }) => value,
+            None => {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "expected `each = USIZE`",
+                ))
+            }
+        };
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+        let ty = self_type(id, &struct_item.generics)?;
+
+        let count = struct_item.fields.len();
+        let actual = quote!(::core::mem::size_of::<#ty>());
+        let check = emit_check(
+            each.span(),
+            quote!(#actual == #count * #each),
+            &[
+                MessagePart::Text(format!(
+                    "size of '{}' is not {} fields of {} bytes (actual size: ",
+                    qualified(id),
+                    count,
+                    each
+                )),
+                MessagePart::Value(actual.clone()),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_field_offset(FIELD = USIZE, ...)]` performs
+/// **compile-time** check, if the fields of the structure it is attached to
+/// live at the exact byte offsets.
+///
+/// Any number of comma-separated `field = offset` pairs may be given. Fields of
+/// tuple structures are referred to by their index. Naming a field the
+/// structure does not have is an error.
+///
+/// A field of a field may be given as well, e.g. `inner.payload = 8`, for a
+/// single level of nesting. It is resolved through the declared type of
+/// `inner`, which has to be in scope, with `payload` visible.
+///
+/// Offsets are taken with `core::mem::offset_of!`, stable since Rust 1.77. With
+/// the `legacy-offset` feature they are computed by a generated `const fn`
+/// instead, with identical results, so the check also works on Rust 1.70. The
+/// same applies to every other attribute checking field offsets.
+///
+/// Check does not pollute namespace, for every field it is expanded to an
+/// anonymous const equivalent to
+/// ```
+/// # struct StructName{ field: u8 }
+/// # const ExpectedOffset: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::offset_of!(StructName, field) == ExpectedOffset,
+///    "offset of 'StructName::field' is not ExpectedOffset bytes (actual offset: ActualOffset bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (real offsets match expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_field_offset(tag = 0, len = 4, payload = 8)]
+/// #[repr(C)]
+/// struct A {
+///     tag: u16,
+///     len: u32,
+///     payload: u64,
+/// }
+/// ```
+///
+/// Success (fields of tuple structure):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_field_offset(0 = 0, 1 = 8)]
+/// #[repr(C)]
+/// struct B(u8, u64);
+/// ```
+///
+/// Success (field of a nested structure):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// #[assert_field_offset(header.len = 4, body = 8)]
+/// #[repr(C)]
+/// struct Packet {
+///     header: Header,
+///     body: [u8; 32],
+/// }
+/// ```
+///
+/// Failure (real offset of `len` is 4 bytes, expected is 2 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_field_offset(tag = 0, len = 2)]
+/// #[repr(C)]
+/// struct C {
+///     tag: u16,
+///     len: u32,
+/// }
+/// ```
+///
+/// Failure (no such field):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_field_offset(length = 4)]
+/// #[repr(C)]
+/// struct D {
+///     tag: u16,
+///     len: u32,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_field_offset(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let offsets = syn::parse::<FieldPathSizes>(attr)?;
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+        let ty = self_type(id, &struct_item.generics)?;
+
+        let mut checks = TokenStream2::new();
+        for FieldPathSize { path, size } in &offsets.entries {
+            let (actual, _) = field_path_layout(&struct_item, &ty, path)?;
+            checks.extend(emit_check(
+                size.span(),
+                quote!(#actual == #size),
+                &[
+                    MessagePart::Text(format!(
+                        "offset of '{}::{}' is not {} bytes (actual offset: ",
+                        qualified(id),
+                        path,
+                        size
+                    )),
+                    MessagePart::Value(actual),
+                    MessagePart::Text(" bytes)".into()),
+                ],
+            ));
+        }
+
+        let checker = quote! {
+            #checks
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_offsets_ascending]` performs **compile-time** check,
+/// if the fields of the structure it is attached to are laid out in declaration
+/// order, i.e. the offset of every field is greater than the one of the field
+/// declared before it.
+///
+/// Under `#[repr(C)]` the compiler never reorders fields, so for such
+/// structures the check mainly guards against mistakes when fields are added or
+/// removed, e.g. a `#[repr(C)]` dropped by accident, letting the compiler
+/// reorder the rest. A field following a zero-sized one may share its offset.
+/// The attribute can be attached to named and tuple structures.
+///
+/// Check does not pollute namespace, for every pair of consecutive fields it is
+/// expanded to an anonymous const equivalent to
+/// ```
+/// # struct StructName{ prev: u8, next: u8 }
+/// # type PrevType = u8;
+/// #
+/// const _: () = assert!(
+///    core::mem::offset_of!(StructName, next) > core::mem::offset_of!(StructName, prev)
+///        || core::mem::size_of::<PrevType>() == 0,
+///    "'StructName::next' (offset: NextOffset bytes) is not laid out after 'StructName::prev' (offset: PrevOffset bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (`#[repr(C)]` keeps the declaration order):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_offsets_ascending]
+/// #[repr(C)]
+/// struct A {
+///     tag: u8,
+///     len: u32,
+///     flags: u8,
+/// }
+/// ```
+///
+/// Success (zero-sized marker shares the offset of the next field):
+///
+/// ```
+/// # use pakr_assert_size::*;
+/// use core::marker::PhantomData;
+///
+/// #[assert_offsets_ascending]
+/// #[repr(C)]
+/// struct Tagged(PhantomData<u8>, u32);
+/// ```
+///
+/// Failure (default representation moves `len` in front of `tag`):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_offsets_ascending]
+/// struct B {
+///     tag: u8,
+///     len: u32,
+///     flags: u8,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_offsets_ascending(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        syn::parse::<Nothing>(attr)?;
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+        let ty = self_type(id, &struct_item.generics)?;
+
+        let members: Vec<(Member, &Type)> = struct_item
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| match &field.ident {
+                Some(ident) => (Member::Named(ident.clone()), &field.ty),
+                None => (Member::Unnamed(index.into()), &field.ty),
+            })
+            .collect();
+
+        let mut checks = TokenStream2::new();
+        for pair in members.windows(2) {
+            let (prev, prev_ty) = &pair[0];
+            let (next, _) = &pair[1];
+            let prev_ty = static_lifetimes(prev_ty, &struct_item.generics);
+            let prev_offset = emit_offset_of(&ty, prev);
+            let next_offset = emit_offset_of(&ty, next);
+            checks.extend(emit_check(
+                next.span(),
+                quote!(#next_offset > #prev_offset || ::core::mem::size_of::<#prev_ty>() == 0),
+                &[
+                    MessagePart::Text(format!(
+                        "'{}::{}' (offset: ",
+                        qualified(id),
+                        tokens_to_string(next)
+                    )),
+                    MessagePart::Value(next_offset),
+                    MessagePart::Text(format!(
+                        " bytes) is not laid out after '{}::{}' (offset: ",
+                        qualified(id),
+                        tokens_to_string(prev)
+                    )),
+                    MessagePart::Value(prev_offset),
+                    MessagePart::Text(" bytes)".into()),
+                ],
+            ));
+        }
+
+        let checker = quote! {
+            #checks
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_no_overlap(a, b)]` performs **compile-time** check,
+/// if the two given fields of the structure or union it is attached to occupy
+/// disjoint bytes, i.e. the field laid out first ends at or before the offset
+/// of the other one.
+///
+/// Fields of a structure never overlap, so for structures the check guards a
+/// layout emulating a union by hand, e.g. one whose fields are read through
+/// offsets computed elsewhere. For unions, where all fields start at offset 0,
+/// it fails unless one of the fields is zero-sized, which occupies no bytes.
+/// Both fields are named like in `offset_of!`, by name or by index of a tuple
+/// structure.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{ a: u8, b: u8 }
+/// # type TypeA = u8;
+/// # type TypeB = u8;
+/// # const OffsetA: usize = core::mem::offset_of!(StructName, a);
+/// # const OffsetB: usize = core::mem::offset_of!(StructName, b);
+/// # const SizeA: usize = core::mem::size_of::<TypeA>();
+/// # const SizeB: usize = core::mem::size_of::<TypeB>();
+/// #
+/// const _: () = assert!(
+///    OffsetA + SizeA <= OffsetB || OffsetB + SizeB <= OffsetA
+///        || SizeA == 0 || SizeB == 0,
+///    "fields 'StructName::a' (offset: OffsetA bytes, size: SizeA bytes) and 'StructName::b' (offset: OffsetB bytes, size: SizeB bytes) overlap"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (`len` starts right after `tag` ends):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_no_overlap(tag, len)]
+/// #[repr(C)]
+/// struct A {
+///     tag: u16,
+///     pad: [u8; 2],
+///     len: u32,
+/// }
+/// ```
+///
+/// Success (fields of tuple structure, in any order):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_no_overlap(1, 0)]
+/// #[repr(C)]
+/// struct B(u8, u64);
+/// ```
+///
+/// Failure (fields of a union share their first bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_no_overlap(word, bytes)]
+/// #[repr(C)]
+/// union C {
+///     word: u32,
+///     bytes: [u8; 4],
+/// }
+/// ```
+///
+/// Failure (a field overlaps itself):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_no_overlap(tag, tag)]
+/// #[repr(C)]
+/// struct D {
+///     tag: u16,
+///     len: u32,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_no_overlap(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let MemberPair { first, second } = syn::parse::<MemberPair>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let union_fields;
+        let (kind, id, generics, fields) = match &item {
+            Item::Struct(item) => ("struct", &item.ident, &item.generics, &item.fields),
+            Item::Union(item) => {
+                union_fields = Fields::Named(item.fields.clone());
+                ("union", &item.ident, &item.generics, &union_fields)
+            }
+            _ => return Err(syn::Error::new_spanned(&item, "expected struct or union")),
+        };
+        let ty = self_type(id, generics)?;
+
+        let mut ranges = Vec::new();
+        for member in [&first, &second] {
+            let field_ty = static_lifetimes(member_type(kind, id, fields, member)?, generics);
+            let offset = emit_offset_of(&ty, member);
+            let size = quote!(::core::mem::size_of::<#field_ty>());
+            ranges.push((offset, size));
+        }
+        let (first_offset, first_size) = ranges[0].clone();
+        let (second_offset, second_size) = ranges[1].clone();
+
+        let check = emit_check(
+            second.span(),
+            quote! {
+                #first_offset + #first_size <= #second_offset
+                    || #second_offset + #second_size <= #first_offset
+                    || #first_size == 0
+                    || #second_size == 0
+            },
+            &[
+                MessagePart::Text(format!(
+                    "fields '{}::{}' (offset: ",
+                    qualified(id),
+                    tokens_to_string(&first)
+                )),
+                MessagePart::Value(first_offset),
+                MessagePart::Text(" bytes, size: ".into()),
+                MessagePart::Value(first_size),
+                MessagePart::Text(format!(
+                    " bytes) and '{}::{}' (offset: ",
+                    qualified(id),
+                    tokens_to_string(&second)
+                )),
+                MessagePart::Value(second_offset),
+                MessagePart::Text(" bytes, size: ".into()),
+                MessagePart::Value(second_size),
+                MessagePart::Text(" bytes) overlap".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_header_size(USIZE)]` performs **compile-time** check,
+/// if the last field of the structure it is attached to lives at the exact byte
+/// offset, i.e. if the fields before it take the given number of bytes along
+/// with their padding.
+///
+/// It is meant for `#[repr(C)]` headers ending with a flexible array member
+/// emulated by a zero-sized placeholder, e.g. `data: [u8; 0]`, which the size of
+/// the structure does not account for reliably, as it includes trailing padding.
+/// The expected size may be followed by `msg = "..."`, like in `#[assert_size]`.
+/// A structure with no fields is rejected. Structures ending with a slice
+/// itself are checked by `#[assert_sized_prefix]`.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{ last: [u8; 0] }
+/// # const ExpectedSize: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::offset_of!(StructName, last) == ExpectedSize,
+///    "header of 'StructName' is not ExpectedSize bytes (offset of 'last': ActualOffset bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (header of 8 bytes followed by the payload):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_header_size(8)]
+/// #[repr(C)]
+/// struct Packet {
+///     kind: u16,
+///     len: u32,
+///     data: [u8; 0],
+/// }
+/// ```
+///
+/// Failure (real header is 8 bytes, as `len` is aligned to 4 bytes, expected
+/// is 6 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_header_size(6)]
+/// #[repr(C)]
+/// struct Packet {
+///     kind: u16,
+///     len: u32,
+///     data: [u8; 0],
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_header_size(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let size = syn::parse::<ExpSize>(attr)?;
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+        let ty = self_type(id, &struct_item.generics)?;
+
+        let member = match struct_item.fields.iter().enumerate().last() {
+            Some((index, field)) => match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(index.into()),
+            },
+            None => {
+                return Err(syn::Error::new_spanned(
+                    id,
+                    "expected a structure with at least one field",
+                ))
+            }
+        };
+
+        let actual = emit_offset_of(&ty, &member);
+        let check = emit_check(
+            size.span(),
+            quote!(#actual == #size),
+            &size.message(vec![
+                MessagePart::Text(format!(
+                    "header of '{}' is not {} bytes (offset of '{}': ",
+                    qualified(id),
+                    size,
+                    tokens_to_string(&member)
+                )),
+                MessagePart::Value(actual.clone()),
+                MessagePart::Text(" bytes)".into()),
+            ]),
+        );
+
+        let checker = quote! {
+            #check
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_sized_prefix(USIZE)]` performs **compile-time**
+/// check, if the unsized last field of the structure it is attached to lives at
+/// the exact byte offset, i.e. if the sized fields before it take the given
+/// number of bytes along with their padding.
+///
+/// It is `#[assert_header_size]` for dynamically sized structures ending with a
+/// slice `[T]` or a `str`, e.g. `#[repr(C)]` messages with a payload of variable
+/// length, for which neither `size_of` nor `offset_of!` are available. The
+/// offset is measured on a sized copy of the structure, with the same `repr`,
+/// holding the types of the fields and an empty `[T; 0]` in place of the last
+/// one, which has the alignment of the slice. The expected size may be followed
+/// by `msg = "..."`, like in `#[assert_size]`. A structure whose last field is
+/// not a slice or a `str` is rejected.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # const ExpectedSize: usize=4;
+/// #
+/// const _: () = {
+///     #[repr(C)]
+///     struct Prefix(u32, [u8; 0]);
+///
+///     assert!(
+///        core::mem::offset_of!(Prefix, 1) == ExpectedSize,
+///        "sized prefix of 'StructName' is not ExpectedSize bytes (offset of 'last': ActualOffset bytes)"
+///     );
+/// };
+/// ```
+///
+/// # Examples
+///
+/// Success (length of 4 bytes followed by the payload):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_sized_prefix(4)]
+/// #[repr(C)]
+/// struct Msg {
+///     len: u32,
+///     data: [u8],
+/// }
+/// ```
+///
+/// Failure (real prefix is 8 bytes, as the payload is aligned to 8 bytes,
+/// expected is 4 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_sized_prefix(4)]
+/// #[repr(C)]
+/// struct Msg {
+///     len: u32,
+///     data: [u64],
+/// }
+/// ```
+///
+/// Failure (last field is sized, checked by `#[assert_header_size]` instead):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_sized_prefix(4)]
+/// #[repr(C)]
+/// struct Msg {
+///     len: u32,
+///     data: [u8; 0],
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_sized_prefix(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let size = syn::parse::<ExpSize>(attr)?;
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+        self_type(id, &struct_item.generics)?;
+
+        let mut fields = struct_item.fields.iter().collect::<Vec<_>>();
+        let last = fields.pop().ok_or_else(|| {
+            syn::Error::new_spanned(id, "expected a structure with at least one field")
+        })?;
+        let member = match &last.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(fields.len().into()),
+        };
+        let element = match &last.ty {
+            Type::Slice(slice) => static_lifetimes(&slice.elem, &struct_item.generics),
+            Type::Path(path) if path.qself.is_none() && path.path.is_ident("str") => quote!(u8),
+            ty => {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    format!(
+                        "last field '{}' of '{}' is not a slice or `str`, check the header of \
+                         a sized structure with `#[assert_header_size]` instead",
+                        tokens_to_string(&member),
+                        id
+                    ),
+                ))
+            }
+        };
+        let field_types = fields
+            .iter()
+            .map(|field| static_lifetimes(&field.ty, &struct_item.generics));
+        let repr = struct_item
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("repr"));
+
+        let tail = Member::Unnamed(fields.len().into());
+        let actual = emit_offset_of(&quote!(Prefix), &tail);
+        let check = emit_check(
+            size.span(),
+            quote!(#actual == #size),
+            &size.message(vec![
+                MessagePart::Text(format!(
+                    "sized prefix of '{}' is not {} bytes (offset of '{}': ",
+                    qualified(id),
+                    size,
+                    tokens_to_string(&member)
+                )),
+                MessagePart::Value(actual.clone()),
+                MessagePart::Text(" bytes)".into()),
+            ]),
+        );
+
+        let checker = quote! {
+            const _: () = {
+                #[allow(dead_code)]
+                #(#repr)*
+                struct Prefix(#(#field_types,)* [#element; 0]);
+
+                #check
+            };
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_field_size(FIELD = USIZE, ...)]` performs
+/// **compile-time** check, if the fields of the structure it is attached to
+/// have the exact sizes in bytes.
+///
+/// Any number of comma-separated `field = size` pairs may be given, fields of
+/// the same type are checked independently. Fields of tuple structures are
+/// referred to by their index. Naming a field the structure does not have is an
+/// error.
+///
+/// The size of a field is the size of its type, so the check also proves that
+/// a niche optimization of `repr(Rust)` enums holds: `Option<NonZeroU32>`,
+/// `Option<&T>` or `Option<Box<T>>` keep the size of their payload only as long
+/// as it has an invalid value to encode `None` in, and grow to make room for a
+/// discriminant otherwise.
+///
+/// Check does not pollute namespace, for every field it is expanded to an
+/// anonymous const equivalent to
+/// ```
+/// # struct FieldType{}
+/// # const ExpectedSize: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<FieldType>() == ExpectedSize,
+///    "size of 'StructName::field' is not ExpectedSize bytes (actual size: ActualSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (real field sizes match expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_field_size(header = 8, data = 32, crc = 8)]
+/// #[repr(C)]
+/// struct Block {
+///     header: u64,
+///     data: [u8; 32],
+///     crc: u64,
+/// }
+/// ```
+///
+/// Failure (real size of `data` is 16 bytes, expected is 32 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_field_size(header = 8, data = 32)]
+/// #[repr(C)]
+/// struct Block {
+///     header: u64,
+///     data: [u8; 16],
+/// }
+/// ```
+///
+/// Success (`None` is stored in the niche of `NonZeroU32`):
+///
+/// ```
+/// # use pakr_assert_size::*;
+/// use core::num::NonZeroU32;
+///
+/// #[assert_field_size(flags = 4)]
+/// #[repr(C)]
+/// struct Entry {
+///     id: u64,
+///     flags: Option<NonZeroU32>,
+/// }
+/// ```
+///
+/// Failure (`u32` has no niche, real size of `flags` is 8 bytes, expected is 4
+/// bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_field_size(flags = 4)]
+/// #[repr(C)]
+/// struct Entry {
+///     id: u64,
+///     flags: Option<u32>,
+/// }
+/// ```
+///
+/// Failure (no such field):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_field_size(payload = 32)]
+/// #[repr(C)]
+/// struct Block {
+///     header: u64,
+///     data: [u8; 32],
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_field_size(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let sizes = syn::parse::<FieldSizes>(attr)?;
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+
+        let mut checks = TokenStream2::new();
+        for FieldSize { member, size } in &sizes.entries {
+            let field_ty =
+                static_lifetimes(field_type(&struct_item, member)?, &struct_item.generics);
+
+            let actual = quote!(::core::mem::size_of::<#field_ty>());
+            checks.extend(emit_check(
+                size.span(),
+                quote!(#actual == #size),
+                &[
+                    MessagePart::Text(format!(
+                        "size of '{}::{}' is not {} bytes (actual size: ",
+                        qualified(id),
+                        tokens_to_string(member),
+                        size
+                    )),
+                    MessagePart::Value(actual),
+                    MessagePart::Text(" bytes)".into()),
+                ],
+            ));
+        }
+
+        let checker = quote! {
+            #checks
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_nested_size(FIELD.NESTED = USIZE, ...)]` performs
+/// **compile-time** check, if the fields of the fields of the structure it is
+/// attached to have the exact sizes in bytes.
+///
+/// Any number of comma-separated `field.nested = size` pairs may be given, for
+/// a single level of nesting; a plain `field = size` is checked as by
+/// `#[assert_field_size]`. The nested field is resolved through the declared
+/// type of its parent, which has to be in scope, with the nested field
+/// visible. Its offset within the outer structure, taken by
+/// `#[assert_field_offset]` with the same path, is reported along with the
+/// size.
+///
+/// Check does not pollute namespace, for every field it is expanded to an
+/// anonymous const equivalent to
+/// ```
+/// # struct Inner { nested: u64 }
+/// # struct StructName { field: Inner }
+/// # const ExpectedSize: usize=8;
+/// #
+/// const _: () = assert!(
+///    // size of the type of `field.nested`
+///    core::mem::size_of::<u64>() == ExpectedSize,
+///    "size of 'StructName::field.nested' is not ExpectedSize bytes (actual size: ActualSize bytes at offset ActualOffset)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (size and offset of a field of a nested structure):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Body {
+///     len: u32,
+///     payload: [u8; 32],
+/// }
+///
+/// #[assert_nested_size(body.payload = 32, body.len = 4)]
+/// #[assert_field_offset(body.payload = 12)]
+/// #[repr(C)]
+/// struct Frame {
+///     tag: u64,
+///     body: Body,
+/// }
+/// ```
+///
+/// Failure (real size of `body.payload` is 32 bytes, expected is 16 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Body {
+///     len: u32,
+///     payload: [u8; 32],
+/// }
+///
+/// #[assert_nested_size(body.payload = 16)]
+/// #[repr(C)]
+/// struct Frame {
+///     tag: u64,
+///     body: Body,
+/// }
+/// ```
+///
+/// Failure (no such field):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Body {
+///     len: u32,
+/// }
+///
+/// #[assert_nested_size(header.len = 4)]
+/// #[repr(C)]
+/// struct Frame {
+///     body: Body,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_nested_size(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let sizes = syn::parse::<FieldPathSizes>(attr)?;
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+        let ty = self_type(id, &struct_item.generics)?;
+
+        let mut checks = TokenStream2::new();
+        for FieldPathSize { path, size } in &sizes.entries {
+            let (offset, actual) = field_path_layout(&struct_item, &ty, path)?;
+            checks.extend(emit_check(
+                size.span(),
+                quote!(#actual == #size),
+                &[
+                    MessagePart::Text(format!(
+                        "size of '{}::{}' is not {} bytes (actual size: ",
+                        qualified(id),
+                        path,
+                        size
+                    )),
+                    MessagePart::Value(actual),
+                    MessagePart::Text(" bytes at offset ".into()),
+                    MessagePart::Value(offset),
+                    MessagePart::Text(")".into()),
+                ],
+            ));
+        }
+
+        let checker = quote! {
+            #checks
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_array_fills(FIELD = USIZE, ...)]` performs
+/// **compile-time** check, if the array fields of the structure it is attached
+/// to hold exactly as many elements as fill the given number of bytes.
+///
+/// Every field has to be an array `[T; N]`; the check compares
+/// `N * size_of::<T>()` with the expected size, keeping `N` as written, so it
+/// also covers lengths given by named constants. Naming a field the structure
+/// does not have, or a field which is not an array, is an error.
+///
+/// Check does not pollute namespace, for every field it is expanded to an
+/// anonymous const equivalent to
+/// ```
+/// # struct StructName{ field: [ElementType; Length] }
+/// # type ElementType = u32;
+/// # const Length: usize = 4;
+/// # const ExpectedSize: usize = 16;
+/// #
+/// const _: () = assert!(
+///    Length * core::mem::size_of::<ElementType>() == ExpectedSize,
+///    "'StructName::field' does not fill ExpectedSize bytes (actual: Length elements of ElementSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (4 elements of 4 bytes fill 16 bytes):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// const WORDS: usize = 4;
+///
+/// #[assert_array_fills(words = 16, tail = 8)]
+/// #[repr(C)]
+/// struct Block {
+///     words: [u32; 4],
+///     tail: [u16; WORDS],
+/// }
+/// ```
+///
+/// Failure (3 elements of 4 bytes take 12 bytes, expected is 16 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_array_fills(words = 16)]
+/// #[repr(C)]
+/// struct Block {
+///     words: [u32; 3],
+/// }
+/// ```
+///
+/// Failure (field is not an array):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_array_fills(words = 16)]
+/// #[repr(C)]
+/// struct Block {
+///     words: u128,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_array_fills(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let sizes = syn::parse::<FieldSizes>(attr)?;
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+
+        let mut checks = TokenStream2::new();
+        for FieldSize { member, size } in &sizes.entries {
+            let mut field_ty = field_type(&struct_item, member)?;
+            // Metavariables of `macro_rules!` arrive wrapped in invisible groups.
+            while let Type::Group(group) = field_ty {
+                field_ty = &group.elem;
+            }
+            let array = match field_ty {
+                Type::Array(array) => array,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        member,
+                        format!(
+                            "field `{}` is not an array, found `{}`",
+                            tokens_to_string(member),
+                            tokens_to_string(field_ty)
+                        ),
+                    ))
+                }
+            };
+            let len = &array.len;
+            let elem = static_lifetimes(&array.elem, &struct_item.generics);
+
+            let elem_size = quote!(::core::mem::size_of::<#elem>());
+            checks.extend(emit_check(
+                size.span(),
+                quote!((#len) * #elem_size == #size),
+                &[
+                    MessagePart::Text(format!(
+                        "'{}::{}' does not fill {} bytes (actual: ",
+                        qualified(id),
+                        tokens_to_string(member),
+                        size
+                    )),
+                    MessagePart::Value(quote!(#len)),
+                    MessagePart::Text(" elements of ".into()),
+                    MessagePart::Value(elem_size),
+                    MessagePart::Text(" bytes)".into()),
+                ],
+            ));
+        }
+
+        let checker = quote! {
+            #checks
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_repr("REPR", ...)]` performs **compile-time** check,
+/// if the structure it is attached to carries all the given representation
+/// hints, e.g. `#[assert_repr("C", "packed")]`.
+///
+/// Size and offset checks are meaningless if `#[repr(C)]` is removed, as Rust
+/// may reorder fields then. Unlike other checks, this one is performed entirely
+/// during expansion, by inspecting `#[repr(...)]` attributes of the item. As an
+/// attribute only sees attributes placed below it, it must be placed above
+/// `#[repr(...)]`. Hints may be spread over several `#[repr(...)]` attributes
+/// and are compared by value, so `"align(16)"` is satisfied by `align(0x10)` and
+/// `"packed"` by `packed(1)`, but not by `packed(2)`.
+///
+/// # Examples
+///
+/// Success (all required representation hints given):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_repr("C", "packed")]
+/// #[repr(C, packed)]
+/// struct Header {
+///     tag: u8,
+///     len: u32,
+/// }
+/// ```
+///
+/// Success (hints given in separate attributes):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_repr("C", "align(8)")]
+/// #[repr(C)]
+/// #[repr(align(8))]
+/// struct Header {
+///     tag: u8,
+///     len: u32,
+/// }
+/// ```
+///
+/// Success (combined hints, with arguments):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_repr("C", "align(16)")]
+/// #[repr(C, align(16))]
+/// struct Block([u8; 64]);
+///
+/// #[assert_repr("packed(2)")]
+/// #[repr(packed(2))]
+/// struct Pair {
+///     tag: u8,
+///     len: u32,
+/// }
+/// ```
+///
+/// Failure (`packed(2)` is not `packed`, which packs to 1 byte):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_repr("packed")]
+/// #[repr(C, packed(2))]
+/// struct Pair {
+///     tag: u8,
+///     len: u32,
+/// }
+/// ```
+///
+/// Failure (`packed` is missing), the build aborts with
+/// ``'Header' has no `#[repr(packed)]` ``:
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_repr("C", "packed")]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u8,
+///     len: u32,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_repr(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let ReprNames { names } = syn::parse::<ReprNames>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, attrs) = match &item {
+            Item::Struct(item) => (&item.ident, &item.attrs),
+            Item::Enum(item) => (&item.ident, &item.attrs),
+            Item::Union(item) => (&item.ident, &item.attrs),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &item,
+                    "expected struct, enum or union",
+                ))
+            }
+        };
+
+        let given = reprs(attrs)
+            .map(|nested| repr_hint(&nested))
+            .collect::<Vec<_>>();
+        let mut errors = TokenStream2::new();
+        for name in &names {
+            let required = name.parse::<NestedMeta>().map_err(|_| {
+                syn::Error::new(
+                    name.span(),
+                    format!(
+                        "`{}` is not a representation hint, e.g. `\"C\"` or `\"align(8)\"`",
+                        name.value()
+                    ),
+                )
+            })?;
+            if !given.contains(&repr_hint(&required)) {
+                errors.extend(
+                    syn::Error::new(
+                        name.span(),
+                        format!("'{}' has no `#[repr({})]`", id, name.value()),
+                    )
+                    .to_compile_error(),
+                );
+            }
+        }
+
+        let checker = quote! {
+            #errors
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_align(USIZE)]` performs **compile-time** check, if the
+/// structure it is attached to has the exact alignment in bytes.
+///
+/// Alignments are powers of two, so a literal alignment that is not, e.g. `0`
+/// or `12`, is rejected at expansion time instead of failing the check.
+///
+/// The alignment checks are named after the size checks they mirror:
+/// `#[assert_align]`, `#[assert_align_at_least]`, `#[assert_align_fits]`,
+/// `#[assert_align_multiple_of]` and `#[assert_align_in_words]` check the
+/// alignment like `#[assert_size]`, `#[assert_size_at_least]`,
+/// `#[assert_size_fits]`, `#[assert_size_multiple_of]` and
+/// `#[assert_size_in_words]` check the size. The earlier names
+/// `#[assert_min_align]`, `#[assert_max_align]` and `#[assert_align_words]`
+/// still work but are deprecated.
+///
+/// When the alignment differs between targets, e.g. of a structure holding
+/// pointers, it may be given as `cfg`-keyed arms, parsed like the ones of
+/// `#[assert_size]`, e.g.
+/// `#[assert_align(target_pointer_width = "64" => 8, target_pointer_width = "32" => 4)]`.
+/// Each arm is checked only when its predicate holds.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const ExpectedAlign: usize=1;
+/// #
+/// const _: () = assert!(
+///    core::mem::align_of::<StructName>() == ExpectedAlign,
+///    "alignment of 'StructName' is not ExpectedAlign bytes (actual alignment: ActualAlign bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (real alignment matches expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// #[assert_align(8)]
+/// struct A {
+///     field1: u64,
+///     field2: u8,
+/// }
+/// ```
+///
+/// Success (real alignment matches expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_align(16)]
+/// #[repr(C, align(16))]
+/// struct B {
+///     field1: u64,
+/// }
+/// ```
+///
+/// Success (size and alignment checked by the same family of names):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_size(16)]
+/// #[assert_align(8)]
+/// #[assert_size_at_least(8)]
+/// #[assert_align_at_least(4)]
+/// #[assert_size_fits(32)]
+/// #[assert_align_fits(8)]
+/// #[assert_size_multiple_of(8)]
+/// #[assert_align_multiple_of(4)]
+/// #[assert_size_in_words(2)]
+/// #[assert_align_in_words(1)]
+/// #[repr(C)]
+/// struct Value {
+///     tag: u64,
+///     payload: u64,
+/// }
+/// ```
+///
+/// Success (alignment of a pointer depending on the target):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_align(
+///     target_pointer_width = "64" => 8,
+///     target_pointer_width = "32" => 4,
+///     target_pointer_width = "16" => 2,
+/// )]
+/// #[repr(C)]
+/// struct Node {
+///     next: *const Node,
+///     tag: u8,
+/// }
+/// ```
+///
+/// Failure (real alignment is 1 byte, expected is 8 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_align(8)]
+/// #[repr(C, packed)]
+/// struct C {
+///     field1: u64,
+///     field2: u64,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_align(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let arms = match syn::parse::<ExpAlign>(attr)? {
+            ExpAlign::Single(align) => vec![(None, *align)],
+            ExpAlign::Arms(arms) => arms.into_sizes(),
+        };
+        for (_, align) in &arms {
+            if let Some(value) = align.size.value.filter(|value| !value.is_power_of_two()) {
+                return Err(syn::Error::new_spanned(
+                    &align.size.expr,
+                    format!("alignment is always a power of two, found {}", value),
+                ));
+            }
+        }
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::align_of::<#ty>());
+        let type_name = {
+            let name = id.unraw().to_string();
+            quote!(::core::concat!(::core::module_path!(), "::", #name))
+        };
+        let mut checks = TokenStream2::new();
+        for (cfg, align) in &arms {
+            checks.extend(emit_report(
+                cfg.as_ref(),
+                &type_name,
+                "assert_align",
+                &[&align.size],
+                quote!(::core::mem::align_of::<#ty>),
+            ));
+            let span = match cfg {
+                Some(cfg) => arm_span(cfg, align.span()),
+                None => align.span(),
+            };
+            let cfg = cfg.as_ref().map(|cfg| quote!(#[cfg(#cfg)]));
+            let check = emit_check(
+                span,
+                quote!(#actual == #align),
+                &align.message(vec![
+                    MessagePart::Text(format!(
+                        "alignment of '{}' is not {} bytes (actual alignment: ",
+                        qualified(id),
+                        align
+                    )),
+                    MessagePart::Value(actual.clone()),
+                    MessagePart::Text(" bytes)".into()),
+                ]),
+            );
+            checks.extend(quote! {
+                #cfg
+                #check
+            });
+        }
+
+        let checker = quote! {
+            #checks
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_align_fits(USIZE)]` performs **compile-time** check, if the
+/// structure it is attached to has alignment not bigger than given amount of bytes.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const ExpectedAlign: usize=1;
+/// #
+/// const _: () = assert!(
+///    core::mem::align_of::<StructName>() <= ExpectedAlign,
+///    "alignment of 'StructName' exceeds ExpectedAlign bytes (actual alignment: ActualAlign bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (real alignment matches expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// #[assert_align_fits(8)]
+/// struct A {
+///     field1: u64,
+///     field2: u8,
+/// }
+/// ```
+///
+/// Success (real alignment is less than expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_align_fits(8)]
+/// #[repr(C)]
+/// struct B {
+///     field1: u16,
+///     field2: u16,
+/// }
+/// ```
+///
+/// Failure (real alignment is 16 bytes, exceeding maximum of 8 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_align_fits(8)]
+/// #[repr(C, align(16))]
+/// struct C {
+///     field1: u64,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_align_fits(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let align = syn::parse::<ExpSize>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::align_of::<#ty>());
+        let check = emit_check(
+            align.span(),
+            quote!(#actual <= #align),
+            &align.message(vec![
+                MessagePart::Text(format!(
+                    "alignment of '{}' exceeds {} bytes (actual alignment: ",
+                    qualified(id),
+                    align
+                )),
+                MessagePart::Value(actual),
+                MessagePart::Text(" bytes)".into()),
+            ]),
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// Deprecated name of `#[assert_align_fits(USIZE)]`, which is named after the
+/// size check `#[assert_size_fits(USIZE)]`.
+#[deprecated(note = "renamed to `assert_align_fits`, after `assert_size_fits`")]
+#[proc_macro_attribute]
+pub fn assert_max_align(attr: TokenStream, item: TokenStream) -> TokenStream {
+    assert_align_fits(attr, item)
+}
+
+/// The attribute `#[assert_align_at_least(USIZE)]` performs **compile-time** check, if the
+/// structure it is attached to has alignment not smaller than given amount of bytes.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const ExpectedAlign: usize=1;
+/// #
+/// const _: () = assert!(
+///    core::mem::align_of::<StructName>() >= ExpectedAlign,
+///    "alignment of 'StructName' is less than ExpectedAlign bytes (actual alignment: ActualAlign bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (real alignment matches expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C, align(64))]
+/// #[assert_align_at_least(64)]
+/// struct CacheLine {
+///     counter: u64,
+/// }
+/// ```
+///
+/// Success (real alignment is greater than expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_align_at_least(4)]
+/// #[repr(C)]
+/// struct B {
+///     field1: u64,
+/// }
+/// ```
+///
+/// Failure (real alignment is 8 bytes, below minimum of 64 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_align_at_least(64)]
+/// #[repr(C)]
+/// struct C {
+///     counter: u64,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_align_at_least(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let align = syn::parse::<ExpSize>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::align_of::<#ty>());
+        let check = emit_check(
+            align.span(),
+            quote!(#actual >= #align),
+            &align.message(vec![
+                MessagePart::Text(format!(
+                    "alignment of '{}' is less than {} bytes (actual alignment: ",
+                    qualified(id),
+                    align
+                )),
+                MessagePart::Value(actual),
+                MessagePart::Text(" bytes)".into()),
+            ]),
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// Deprecated name of `#[assert_align_at_least(USIZE)]`, which is named after
+/// the size check `#[assert_size_at_least(USIZE)]`.
+#[deprecated(note = "renamed to `assert_align_at_least`, after `assert_size_at_least`")]
+#[proc_macro_attribute]
+pub fn assert_min_align(attr: TokenStream, item: TokenStream) -> TokenStream {
+    assert_align_at_least(attr, item)
+}
+
+/// The attribute `#[assert_align_multiple_of(USIZE)]` performs **compile-time**
+/// check, if the alignment of the structure it is attached to is a multiple of
+/// given amount of bytes.
+///
+/// It is useful for buffers handed over to hardware, e.g. a DMA engine requiring
+/// 64-byte aligned memory. Zero multiple is rejected.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const Multiple: usize=1;
+/// #
+/// const _: () = assert!(
+///    core::mem::align_of::<StructName>() % Multiple == 0,
+///    "alignment of 'StructName' (ActualAlign) is not a multiple of Multiple bytes"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (real alignment of 64 bytes is a multiple of 64 bytes):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_align_multiple_of(64)]
+/// #[repr(C, align(64))]
+/// struct DmaBuffer {
+///     bytes: [u8; 256],
+/// }
+/// ```
+///
+/// Failure (real alignment of 8 bytes is not a multiple of 64 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_align_multiple_of(64)]
+/// #[repr(C)]
+/// struct DmaBuffer {
+///     words: [u64; 32],
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_align_multiple_of(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        let align = syn::parse::<ExpSize>(attr)?;
+        if align.size.value == Some(0) {
+            return Err(syn::Error::new_spanned(
+                &align.size.expr,
+                "alignment cannot be a multiple of zero",
+            ));
+        }
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::align_of::<#ty>());
+        let check = emit_check(
+            align.span(),
+            quote!(#actual % #align == 0),
+            &align.message(vec![
+                MessagePart::Text(format!("alignment of '{}' (", qualified(id))),
+                MessagePart::Value(actual),
+                MessagePart::Text(format!(") is not a multiple of {} bytes", align)),
+            ]),
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_align_pow2]` performs **compile-time** check, if the
+/// alignment of the structure it is attached to is a power of two.
+///
+/// Rust guarantees it for every type, so the check documents the assumption of
+/// code relying on it, e.g. masking addresses with `align - 1`.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// #
+/// const _: () = assert!(
+///    core::mem::align_of::<StructName>().is_power_of_two(),
+///    "alignment of 'StructName' (ActualAlign bytes) is not a power of two"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success:
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_align_pow2]
+/// #[repr(C, align(64))]
+/// struct DmaBuffer {
+///     bytes: [u8; 256],
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_align_pow2(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        syn::parse::<Nothing>(attr)?;
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let actual = quote!(::core::mem::align_of::<#ty>());
+        let check = emit_check(
+            Span::call_site(),
+            quote!(#actual.is_power_of_two()),
+            &[
+                MessagePart::Text(format!("alignment of '{}' (", qualified(id))),
+                MessagePart::Value(actual),
+                MessagePart::Text(" bytes) is not a power of two".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_align_in_words(USIZE)]` performs **compile-time** check,
+/// if the structure it is attached to has the exact alignment in words of 8
+/// bytes, the alignment counterpart of `#[assert_size_in_words]`.
+///
+/// The word size may be overridden with `#[assert_align_in_words(USIZE, word = USIZE)]`,
+/// the default failure message may be replaced with `msg = "..."`.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{}
+/// # const ExpectedWords: usize=1;
+/// # const WordSize: usize=1;
+/// #
+/// const _: () = assert!(
+///    core::mem::align_of::<StructName>() == ExpectedWords * WordSize,
+///    "alignment of 'StructName' is not ExpectedWords words (ExpectedAlign bytes) (actual alignment: ActualAlign bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (aligned to 1 word):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_align_in_words(1)]
+/// #[repr(C, align(8))]
+/// struct Value([u8; 16]);
+/// ```
+///
+/// Failure (alignment is 4 bytes, 1 word takes 8 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_align_in_words(1)]
+/// #[repr(C)]
+/// struct Value([u32; 4]);
+/// ```
+#[proc_macro_attribute]
+pub fn assert_align_in_words(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| expand_words(attr, item, true))
+}
+
+/// Deprecated name of `#[assert_align_in_words(USIZE)]`, which is named after
+/// the size check `#[assert_size_in_words(USIZE)]`.
+#[deprecated(note = "renamed to `assert_align_in_words`, after `assert_size_in_words`")]
+#[proc_macro_attribute]
+pub fn assert_align_words(attr: TokenStream, item: TokenStream) -> TokenStream {
+    assert_align_in_words(attr, item)
+}
+
+/// The attribute `#[assert_natural_align]` performs **compile-time** check, if
+/// the structure it is attached to has the alignment of its most aligned field,
+/// its natural alignment.
+///
+/// It catches a stray `#[repr(align(N))]` over-aligning a `#[repr(C)]`
+/// structure, e.g. one shared with C code, where the extra alignment changes
+/// the layout of every structure embedding it. A packed structure is
+/// under-aligned, so it fails the check as well; check it with
+/// `#[assert_packed]` instead. A structure without fields is naturally aligned
+/// to 1 byte.
+///
+/// Like in `#[assert_no_padding]`, the fields are taken from the definition as
+/// the attribute receives it.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct StructName{ field1: u8, field2: u8 }
+/// # type Field1Type = u8;
+/// # type Field2Type = u8;
+/// # const fn max_align(values: &[usize]) -> usize { 1 }
+/// #
+/// const _: () = assert!(
+///    core::mem::align_of::<StructName>()
+///        == max_align(&[core::mem::align_of::<Field1Type>(), core::mem::align_of::<Field2Type>()]),
+///    "alignment of 'StructName' is not the natural alignment of NaturalAlign bytes (actual \
+///     alignment: ActualAlign bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (aligned to its `u64` field):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_natural_align]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u8,
+///     id: u64,
+///     len: u32,
+/// }
+/// ```
+///
+/// Failure (over-aligned to 64 bytes, the natural alignment is 8 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_natural_align]
+/// #[repr(C, align(64))]
+/// struct Header {
+///     tag: u8,
+///     id: u64,
+///     len: u32,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_natural_align(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        syn::parse::<Nothing>(attr)?;
+
+        let struct_item = syn::parse::<ItemStruct>(item)?;
+        let id = &struct_item.ident;
+        let ty = self_type(id, &struct_item.generics)?;
+
+        let actual = quote!(::core::mem::align_of::<#ty>());
+        let field_types = struct_item
+            .fields
+            .iter()
+            .map(|field| static_lifetimes(&field.ty, &struct_item.generics));
+        let natural = quote! {
+            ::pakr_assert_size::__private::max_align(&[
+                #(::core::mem::align_of::<#field_types>()),*
+            ])
+        };
+
+        let check = emit_check(
+            Span::call_site(),
+            quote!(#actual == #natural),
+            &[
+                MessagePart::Text(format!(
+                    "alignment of '{}' is not the natural alignment of ",
+                    qualified(id)
+                )),
+                MessagePart::Value(natural.clone()),
+                MessagePart::Text(" bytes (actual alignment: ".into()),
+                MessagePart::Value(actual),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        );
+
+        let checker = quote! {
+            #check
+            #struct_item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The attribute `#[assert_layout(KEY = USIZE, ...)]` performs **compile-time**
+/// checks of the size and alignment of the structure it is attached to, as a
+/// single attribute instead of a stack of them.
+///
+/// Any subset of the following keys may be given:
+///
+/// * `size` checks the exact size, as `assert_size`,
+/// * `align` checks the exact alignment, as `assert_align`,
+/// * `size_fits` checks the maximum size, as `assert_size_fits`,
+/// * `align_fits` checks the maximum alignment, as `assert_align_fits`.
+///
+/// All checks are expanded to a single anonymous const, holding the same
+/// checks as generated by the respective attributes. Arguments that are not
+/// `KEY = USIZE` pairs are rejected with the accepted forms listed.
+///
+/// # Examples
+///
+/// Success (real size and alignment match expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_layout(size = 16, align = 8)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     id: u64,
+/// }
+/// ```
+///
+/// Success (limits only):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[assert_layout(size_fits = 32, align_fits = 8)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     id: u64,
+/// }
+/// ```
+///
+/// Failure (real size matches, real alignment is 8 bytes, expected is 16 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[assert_layout(size = 16, align = 16)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     id: u64,
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn assert_layout(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_attribute(item, |item| {
+        // Keys are checked separately, a parse error does not match the form.
+        let parser = Punctuated::<LayoutArg, Token![,]>::parse_terminated;
+        let entries = parser
+            .parse(attr)
+            .map_err(|err| with_usage(err, ASSERT_LAYOUT_USAGE))?;
+        if entries.is_empty() {
+            return Err(with_usage(
+                syn::Error::new(
+                    Span::call_site(),
+                    "expected at least one `key = value` pair",
+                ),
+                ASSERT_LAYOUT_USAGE,
+            ));
+        }
+        let mut args = Vec::new();
+        for arg in entries {
+            push_layout_arg(
+                &mut args,
+                arg,
+                &["size", "align", "size_fits", "align_fits"],
+            )?;
+        }
+
+        let item = syn::parse::<Item>(item)?;
+        let (id, ty) = item_type(&item)?;
+
+        let size = quote!(::core::mem::size_of::<#ty>());
+        let align = quote!(::core::mem::align_of::<#ty>());
+        let mut checks = TokenStream2::new();
+        for LayoutArg { key, value } in &args {
+            let (condition, text, actual) = if key == "size" {
+                (
+                    quote!(#size == #value),
+                    format!(
+                        "size of '{}' is not {} bytes (actual size: ",
+                        qualified(id),
+                        value
+                    ),
+                    &size,
+                )
+            } else if key == "align" {
+                (
+                    quote!(#align == #value),
+                    format!(
+                        "alignment of '{}' is not {} bytes (actual alignment: ",
+                        qualified(id),
+                        value
+                    ),
+                    &align,
+                )
+            } else if key == "size_fits" {
+                (
+                    quote!(#size <= #value),
+                    format!(
+                        "'{}' does not fit in {} bytes (actual size: ",
+                        qualified(id),
+                        value
+                    ),
+                    &size,
+                )
+            } else {
+                (
+                    quote!(#align <= #value),
+                    format!(
+                        "alignment of '{}' exceeds {} bytes (actual alignment: ",
+                        qualified(id),
+                        value
+                    ),
+                    &align,
+                )
+            };
+            checks.extend(emit_check(
+                value.span(),
+                condition,
+                &[
+                    MessagePart::Text(text),
+                    MessagePart::Value(actual.clone()),
+                    MessagePart::Text(" bytes)".into()),
+                ],
+            ));
+        }
+
+        let checker = quote! {
+            const _: () = {
+                #checks
+            };
+            #item
+        };
+
+        Ok(checker)
+    })
+}
+
+/// The macro `assert_type_size!(TYPE, USIZE)` performs **compile-time** check, if
+/// the given type has the exact size in bytes.
+///
+/// Alternatively the expected size may be given with a comparison operator:
+/// `assert_type_size!(TYPE == USIZE)` checks the exact size,
+/// `assert_type_size!(TYPE <= USIZE)` checks the type fits in given amount of
+/// bytes and `assert_type_size!(TYPE >= USIZE)` checks it is not smaller.
+///
+/// As the check is an item, the macro may be used at module scope as well as
+/// inside any block, e.g. a function body or a `const` initializer.
+///
+/// The expected size may be any const expression, including a call of a
+/// `const fn` computing it, e.g. `assert_type_size!(Header, expected(2))`. The
+/// call is evaluated in const context along with the check, so a function that
+/// is not `const` is rejected by the compiler, and the failure message quotes
+/// the call as written.
+///
+/// Unlike the `#[assert_size(USIZE)]` attribute it does not need to be attached
+/// to the type definition, so it can pin down sizes of types defined in other
+/// crates, including generic instantiations. It is named differently, as an
+/// attribute and a function-like macro cannot share a name.
+///
+/// Type aliases, which attributes cannot be attached to, are checked the same
+/// way, e.g. `assert_type_size!(Handle, 8)` for `type Handle = u64;`. The
+/// failure message quotes the alias as written.
+///
+/// A trait object, `dyn Trait`, has no size, but pointers to it do: `&dyn Trait`,
+/// `*const dyn Trait` and `Box<dyn Trait>` are two words, the address of the
+/// value and of its vtable, e.g. `assert_type_size!(&dyn Shape, 2ptr)`. Several
+/// bounds are parenthesized behind a reference, `&(dyn Shape + Send)`, as in any
+/// type. A bare `dyn Trait` is rejected, suggesting a pointer to it instead.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # type TypeName = ();
+/// # const ExpectedSize: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<TypeName>() == ExpectedSize,
+///    "size of 'TypeName' is not ExpectedSize bytes (actual size: ActualSize bytes, \
+///     next power of two: RoundedSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (size of a standard library type):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// assert_type_size!(std::num::NonZeroU64, 8);
+/// assert_type_size!(Option<std::num::NonZeroU64>, 8);
+/// ```
+///
+/// Success (size of a generic instantiation):
+///
+/// ```
+/// # use pakr_assert_size::*;
+/// use core::mem::size_of;
+///
+/// assert_type_size!(Vec<u8>, 3 * size_of::<usize>());
+/// assert_type_size!([u16; 4], 8);
+/// ```
+///
+/// Success (concrete instantiations of a generic structure):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Pair<T> {
+///     first: T,
+///     second: T,
+/// }
+///
+/// assert_type_size!(Pair<u32>, 8);
+/// assert_type_size!(Pair<u64>, 16);
+/// ```
+///
+/// Success (pointers to trait objects are two words):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// trait Shape {
+///     fn area(&self) -> f64;
+/// }
+///
+/// assert_type_size!(&dyn Shape, 2ptr);
+/// assert_type_size!(*const dyn Shape, 2ptr);
+/// assert_type_size!(Box<dyn Shape + Send>, 2ptr);
+/// assert_type_size!(&(dyn Shape + Sync), 2ptr);
+/// // Thin pointers are one word.
+/// assert_type_size!(&u64, 1ptr);
+/// ```
+///
+/// Failure (a pointer to a trait object is 2 words, not 1):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// trait Shape {
+///     fn area(&self) -> f64;
+/// }
+///
+/// assert_type_size!(&dyn Shape, 1ptr);
+/// ```
+///
+/// Failure (a trait object has no size):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// trait Shape {
+///     fn area(&self) -> f64;
+/// }
+///
+/// assert_type_size!(dyn Shape, 16);
+/// ```
+///
+/// Success (type aliases of a primitive type and of an array):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// pub type Handle = u64;
+/// pub type Bytes = [u8; 16];
+///
+/// assert_type_size!(Handle, 8);
+/// assert_type_size!(Bytes, 16);
+/// ```
+///
+/// Failure (real size of the alias is 4 bytes, expected is 8 bytes), the build
+/// aborts with `size of 'Handle' is not 8 bytes (actual size: 4 bytes, next power
+/// of two: 4 bytes)`:
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// pub type Handle = u32;
+///
+/// assert_type_size!(Handle, 8);
+/// ```
+///
+/// Success (type replaced by another macro, checked independently of the
+/// order of attributes, here a stand-in for `#[bitfield]` of `modular-bitfield`,
+/// which turns the fields of the structure into a byte array of their bits):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// macro_rules! bitfield {
+///     ($(#[$attr:meta])* struct $name:ident { $($field:ident: $bits:literal),* $(,)? }) => {
+///         $(#[$attr])*
+///         #[repr(transparent)]
+///         struct $name {
+///             bytes: [u8; (0usize $(+ $bits)*).div_ceil(8)],
+///         }
+///     };
+/// }
+///
+/// bitfield! {
+///     struct Flags {
+///         ready: 1,
+///         mode: 3,
+///         level: 4,
+///     }
+/// }
+///
+/// bitfield! {
+///     struct Status {
+///         code: 12,
+///         retry: 4,
+///         id: 16,
+///     }
+/// }
+///
+/// assert_type_size!(Flags, 1);
+/// assert_type_size!(Status, 4);
+/// ```
+///
+/// Success (expected size computed by a `const fn`):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// const fn expected(words: usize) -> usize {
+///     words * core::mem::size_of::<u32>()
+/// }
+///
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// assert_type_size!(Header, expected(2));
+/// assert_type_size!(Header <= expected(4));
+/// ```
+///
+/// Failure (real size is 8 bytes, `expected(3)` is 12 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// const fn expected(words: usize) -> usize {
+///     words * core::mem::size_of::<u32>()
+/// }
+///
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// assert_type_size!(Header, expected(3));
+/// ```
+///
+/// Success (comparison operators):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// assert_type_size!(u64 == 8);
+/// assert_type_size!(u64 <= 16);
+/// assert_type_size!(u64 >= 4);
+/// ```
+///
+/// Success (used inside a function body):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// fn checksum(data: &[u32]) -> u32 {
+///     assert_type_size!(u32 == 4);
+///     data.iter().fold(0, |sum, word| sum.wrapping_add(*word))
+/// }
+/// # assert_eq!(checksum(&[1, 2, 3]), 6);
+/// ```
+///
+/// Success (used inside a `const` initializer):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// const HEADER_LEN: usize = {
+///     assert_type_size!(Header <= 8);
+///     core::mem::size_of::<Header>()
+/// };
+/// # assert_eq!(HEADER_LEN, 8);
+/// ```
+///
+/// Failure (real size is 2 bytes, expected is 4 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// assert_type_size!(u16, 4);
+/// ```
+///
+/// Failure (real size is 8 bytes, exceeding maximum of 4 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// fn check() {
+///     assert_type_size!(u64 <= 4);
+/// }
+/// ```
+#[proc_macro]
+pub fn assert_type_size(input: TokenStream) -> TokenStream {
+    let TypeSize { ty, op, size } = parse_macro_input!(input as TypeSize);
+
+    let check = emit_assert(
+        &tokens_to_string(&ty),
+        &ty,
+        op,
+        &size.size,
+        size.msg.as_ref(),
+        "",
+    );
+
+    TokenStream::from(check)
+}
+
+/// The macro `assert_types_size!((TYPE, ...) op USIZE)` performs
+/// **compile-time** checks, if the size of each of the listed types compares
+/// with the given amount of bytes, like `assert_type_size!` does for one type.
+///
+/// It is meant for families of types sharing one size, e.g. vector types of the
+/// same width, so the size is written once. Every type is checked by a separate
+/// anonymous const, and a failure names the type that does not match. The
+/// comparison and the expected size are the ones of `assert_type_size!`:
+/// `(TYPE, ...), USIZE` or `== USIZE` for the exact size, `<= USIZE` for a
+/// maximum and `>= USIZE` for a minimum, optionally followed by `msg = "..."`.
+///
+/// The parentheses list the types, they do not form a tuple type; the size of a
+/// tuple is checked with `assert_type_size!((A, B), USIZE)`.
+///
+/// # Examples
+///
+/// Success (three vector types of 16 bytes):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Vec2 {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// #[repr(C)]
+/// struct Vec4f {
+///     x: f32,
+///     y: f32,
+///     z: f32,
+///     w: f32,
+/// }
+///
+/// #[repr(C)]
+/// struct Vec2i {
+///     x: i64,
+///     y: i64,
+/// }
+///
+/// assert_types_size!((Vec2, Vec4f, Vec2i) == 16);
+/// assert_types_size!((u8, u16, u32) <= 4);
+/// ```
+///
+/// Failure (real size of `Vec3f` is 12 bytes, expected is 16 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Vec2 {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// #[repr(C)]
+/// struct Vec3f {
+///     x: f32,
+///     y: f32,
+///     z: f32,
+/// }
+///
+/// #[repr(C)]
+/// struct Vec2i {
+///     x: i64,
+///     y: i64,
+/// }
+///
+/// assert_types_size!((Vec2, Vec3f, Vec2i) == 16);
+/// ```
+#[proc_macro]
+pub fn assert_types_size(input: TokenStream) -> TokenStream {
+    let TypesSize { types, op, size } = parse_macro_input!(input as TypesSize);
+
+    let checks = types.iter().map(|ty| {
+        emit_assert(
+            &tokens_to_string(ty),
+            ty,
+            op,
+            &size.size,
+            size.msg.as_ref(),
+            "",
+        )
+    });
+
+    TokenStream::from(quote!(#(#checks)*))
+}
+
+/// The macro `assert_size_bound!(TYPE: op USIZE)` performs **compile-time**
+/// check, if the size of a type of generic code compares with the given amount
+/// of bytes, e.g. `assert_size_bound!(Self: <= 32)` in a default method of a
+/// trait, or `assert_size_bound!(T: <= 16)` in a generic function. The
+/// comparison is `==`, `<=` or `>=`, like in `assert_type_size!`, and the size
+/// may be followed by `, msg = "..."` replacing the default failure message.
+///
+/// Anonymous consts cannot refer to generic parameters, so the check is an
+/// associated const of a local generic type instead, evaluated once the
+/// function is instantiated for a concrete type. A function never called is
+/// never checked, and since instances are only built along with the machine
+/// code, a failure shows up in `cargo build`, not in `cargo check`. The failure
+/// message names the type as written, e.g. `Self`, as the concrete type is not
+/// known to the macro; the compiler notes the instance that failed.
+///
+/// The size is only defined for `Sized` types, while `Self` of a trait is not
+/// `Sized` unless required, so a default method asserting its size needs a
+/// `where Self: Sized` bound, or the trait a `Sized` supertrait.
+///
+/// The macro is a statement, usable in any function body, expanding to a block
+/// equivalent to
+/// ```
+/// # trait Trait: Sized { fn method(&self) {
+/// # const ExpectedSize: usize = usize::MAX;
+/// struct SizeBound<Checked>(core::marker::PhantomData<Checked>);
+/// impl<Checked> SizeBound<Checked> {
+///     const CHECK: () = assert!(
+///         core::mem::size_of::<Checked>() <= ExpectedSize,
+///         "'Self' does not fit in ExpectedSize bytes (actual size: ActualSize bytes, \
+///          OverSize bytes over, next power of two: RoundedSize bytes)"
+///     );
+/// }
+/// let () = SizeBound::<Self>::CHECK;
+/// # } }
+/// ```
+///
+/// # Examples
+///
+/// Success (default method of a trait bounding the size of its implementors):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// trait Message: Sized {
+///     fn encode(&self) -> usize {
+///         assert_size_bound!(Self: <= 32);
+///         core::mem::size_of::<Self>()
+///     }
+/// }
+///
+/// struct Ping(u64);
+///
+/// impl Message for Ping {}
+/// # assert_eq!(Ping(0).encode(), 8);
+/// ```
+///
+/// Success (generic function, method of a trait that is not `Sized`):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// fn inline_storage<T>(value: T) -> [T; 1] {
+///     assert_size_bound!(T: <= 16, msg = "values are stored inline");
+///     [value]
+/// }
+///
+/// trait Node {
+///     fn small(&self) -> bool
+///     where
+///         Self: Sized,
+///     {
+///         assert_size_bound!(Self: <= 2ptr);
+///         true
+///     }
+/// }
+///
+/// impl Node for u8 {}
+/// # assert_eq!(inline_storage(1u32)[0], 1);
+/// # assert!(0u8.small());
+/// ```
+///
+/// Failure (real size of `Bulk` is 64 bytes, exceeding maximum of 32 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// trait Message: Sized {
+///     fn encode(&self) -> usize {
+///         assert_size_bound!(Self: <= 32);
+///         core::mem::size_of::<Self>()
+///     }
+/// }
+///
+/// struct Bulk([u8; 64]);
+///
+/// impl Message for Bulk {}
+///
+/// fn main() {
+///     Bulk([0; 64]).encode();
+/// }
+/// ```
+#[proc_macro]
+pub fn assert_size_bound(input: TokenStream) -> TokenStream {
+    let TypeBound { ty, op, size } = parse_macro_input!(input as TypeBound);
+
+    let actual = quote!(::core::mem::size_of::<Checked>());
+    let assertion = emit_assertion(
+        size.span(),
+        quote!(#actual #op #size),
+        &size.message(op.message(&tokens_to_string(&ty), &size, "", actual.clone())),
+    );
+
+    TokenStream::from(quote! {
+        {
+            struct SizeBound<Checked>(::core::marker::PhantomData<Checked>);
+            impl<Checked> SizeBound<Checked> {
+                const CHECK: () = #assertion;
+            }
+            let () = SizeBound::<#ty>::CHECK;
+        }
+    })
+}
+
+/// The macro `assert_size_for!(TYPE = USIZE, ...)` performs **compile-time**
+/// check, if each of the listed types has the exact size in bytes.
+///
+/// It is meant for generic types, whose size can only be checked for concrete
+/// instantiations: the attribute form would have to name the type without
+/// generic arguments. Every `TYPE = USIZE` pair is checked by a separate
+/// anonymous const, equivalent to the one generated by `assert_type_size!`.
+///
+/// Const generic arguments are given as in any type path, either as literals,
+/// e.g. `Buf<16>`, or as blocks, e.g. `Buf<{ 4 * 4 }>`.
+///
+/// # Examples
+///
+/// Success (two instantiations of the same generic structure):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Pair<T> {
+///     first: T,
+///     second: T,
+/// }
+///
+/// assert_size_for!(Pair<u32> = 8, Pair<u64> = 16);
+/// ```
+///
+/// Failure (real size of `Pair<u16>` is 4 bytes, expected is 8 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Pair<T> {
+///     first: T,
+///     second: T,
+/// }
+///
+/// assert_size_for!(Pair<u32> = 8, Pair<u16> = 8);
+/// ```
+///
+/// Success (two instantiations of a structure with a const parameter):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Buf<const N: usize> {
+///     data: [u8; N],
+/// }
+///
+/// assert_size_for!(Buf<16> = 16, Buf<{ 2 * 16 }> = 32);
+/// ```
+///
+/// Failure (real size of `Buf<16>` is 16 bytes, expected is 8 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Buf<const N: usize> {
+///     data: [u8; N],
+/// }
+///
+/// assert_size_for!(Buf<16> = 8);
+/// ```
+#[proc_macro]
+pub fn assert_size_for(input: TokenStream) -> TokenStream {
+    let parser = Punctuated::<TypeEq, Token![,]>::parse_terminated;
+    let entries = parse_macro_input!(input with parser);
+
+    let checks = entries.iter().map(|TypeEq { ty, size }| {
+        emit_assert(&tokens_to_string(ty), ty, Comparison::Eq, size, None, "")
+    });
+
+    TokenStream::from(quote!(#(#checks)*))
+}
+
+/// The macro `size_assert_all! { TYPE = USIZE, ... }` performs **compile-time**
+/// checks, if the size of each of the listed types compares with the given
+/// amount of bytes: `=` for the exact size, `<=` for a maximum and `>=` for a
+/// minimum.
+///
+/// It gathers the size contract of a whole module in one place, e.g. at the
+/// bottom of a module of FFI bindings with hundreds of types, instead of an
+/// attribute on each of them. Every entry is checked by a separate anonymous
+/// const, equivalent to the one generated by `assert_type_size!`, and reported
+/// at its expected size. Unlike `assert_size_for!`, which only checks exact
+/// sizes, it accepts maximums and minimums too; `==` may be written for `=`.
+///
+/// # Examples
+///
+/// Success (exact sizes, a maximum and a minimum):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// mod bindings {
+///     #[repr(C)]
+///     pub struct Header {
+///         pub tag: u32,
+///         pub len: u32,
+///     }
+///
+///     #[repr(C)]
+///     pub struct Frame {
+///         pub header: Header,
+///         pub payload: [u8; 16],
+///     }
+///
+///     #[repr(C)]
+///     pub struct Buf<const N: usize> {
+///         pub data: [u8; N],
+///     }
+///
+///     pakr_assert_size::size_assert_all! {
+///         Header = 8,
+///         Frame = 24,
+///         Frame <= 32,
+///         Buf<64> >= 64,
+///         Buf<{ 2 * 16 }> == 32,
+///         [Header; 4] = 4 * 8,
+///     }
+/// }
+/// ```
+///
+/// Failure (real size of `Frame` is 24 bytes, exceeding maximum of 16 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// #[repr(C)]
+/// struct Frame {
+///     header: Header,
+///     payload: [u8; 16],
+/// }
+///
+/// size_assert_all! {
+///     Header = 8,
+///     Frame <= 16,
+/// }
+/// ```
+#[proc_macro]
+pub fn size_assert_all(input: TokenStream) -> TokenStream {
+    let parser = Punctuated::<TypeEntry, Token![,]>::parse_terminated;
+    let entries = parse_macro_input!(input with parser);
+
+    let checks = entries.iter().map(|TypeEntry { ty, op, size }| {
+        emit_assert(&tokens_to_string(ty), ty, *op, size, None, "")
+    });
+
+    TokenStream::from(quote!(#(#checks)*))
+}
+
+/// The macro `const_assert_size_eq!(TYPE, USIZE)` performs **compile-time**
+/// check, if the type has the exact size in bytes.
+///
+/// It is meant for migrating from the `static_assertions` crate: like its
+/// macros, it is valid at module scope as well as in function bodies, and it
+/// does not pollute namespace. It replaces the typical size checks of that crate
+/// one to one, but fails with the actual size of the type, like
+/// `assert_type_size!(TYPE, USIZE)`, which it is equivalent to:
+///
+/// | `static_assertions`                          | `pakr-assert-size`                                 |
+/// |----------------------------------------------|----------------------------------------------------|
+/// | `const_assert_eq!(size_of::<T>(), 16);`      | `const_assert_size_eq!(T, 16);`                    |
+/// | `assert_eq_size!(T, [u8; 16]);`              | `const_assert_size_eq!(T, size_of::<[u8; 16]>());` |
+/// | `const_assert!(size_of::<T>() <= 16);`       | `assert_type_size!(T <= 16);`                      |
+/// | `const_assert!(size_of::<T>() >= 16);`       | `assert_type_size!(T >= 16);`                      |
+/// | `const_assert_eq!(align_of::<T>(), 8);`      | `layout_assert!(T: align == 8);`                   |
+///
+/// # Examples
+///
+/// Success (`const_assert_eq!(size_of::<Header>(), 8);` migrated):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// const_assert_size_eq!(Header, 8);
+/// ```
+///
+/// Success (`assert_eq_size!(Handle, u64);` migrated, with the size of the other
+/// type as the expected size):
+///
+/// ```
+/// # use pakr_assert_size::*;
+/// use core::mem::size_of;
+///
+/// pub type Handle = usize;
+///
+/// # #[cfg(target_pointer_width = "64")]
+/// const_assert_size_eq!(Handle, size_of::<u64>());
+/// ```
+///
+/// Success (`const_assert_eq!(mem::size_of::<u32>(), 4);` in a function body
+/// migrated):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// fn checksum(data: &[u32]) -> u32 {
+///     const_assert_size_eq!(u32, 4);
+///     data.iter().fold(0, |sum, word| sum.wrapping_add(*word))
+/// }
+/// # assert_eq!(checksum(&[1, 2, 3]), 6);
+/// ```
+///
+/// Failure (real size is 8 bytes, expected is 4 bytes), the build aborts with
+/// `size of 'Header' is not 4 bytes (actual size: 8 bytes, next power of two: 8
+/// bytes)`:
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// const_assert_size_eq!(Header, 4);
+/// ```
+#[proc_macro]
+pub fn const_assert_size_eq(input: TokenStream) -> TokenStream {
+    let TypeAndSize { ty, size } = parse_macro_input!(input as TypeAndSize);
+
+    let check = emit_assert(&tokens_to_string(&ty), &ty, Comparison::Eq, &size, None, "");
+
+    TokenStream::from(check)
+}
+
+/// The macro `assert_fits!(TYPE, CAPACITY)` performs **compile-time** check, if
+/// the size of the first type does not exceed the size of the second one.
+///
+/// It is meant for capacities given as types, e.g. `type Page = [u8; 4096];`,
+/// which the attribute form cannot be attached to. Only the size is compared;
+/// to store a value in the bytes of another type, check the alignment as well
+/// with `#[assert_fits_in(TYPE)]`.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # struct TypeName{}
+/// # struct Capacity{}
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<TypeName>() <= core::mem::size_of::<Capacity>(),
+///    "'TypeName' (size: ActualSize bytes) does not fit in 'Capacity' (size: CapacitySize bytes, Overflow bytes over)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (structure fits in a page):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// type Page = [u8; 4096];
+///
+/// #[repr(C)]
+/// struct Superblock {
+///     magic: u64,
+///     blocks: [u32; 1000],
+/// }
+///
+/// assert_fits!(Superblock, Page);
+/// ```
+///
+/// Failure (real size is 4104 bytes, page is 4096 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// type Page = [u8; 4096];
+///
+/// #[repr(C)]
+/// struct Superblock {
+///     magic: u64,
+///     blocks: [u32; 1024],
+/// }
+///
+/// assert_fits!(Superblock, Page);
+/// ```
+#[proc_macro]
+pub fn assert_fits(input: TokenStream) -> TokenStream {
+    let TypePair { ty, other } = parse_macro_input!(input as TypePair);
+
+    let size = quote!(::core::mem::size_of::<#ty>());
+    let other_size = quote!(::core::mem::size_of::<#other>());
+    let check = emit_check(
+        other.span(),
+        quote!(#size <= #other_size),
+        &[
+            MessagePart::Text(format!("'{}' (size: ", tokens_to_string(&ty))),
+            MessagePart::Value(size.clone()),
+            MessagePart::Text(format!(
+                " bytes) does not fit in '{}' (size: ",
+                tokens_to_string(&other)
+            )),
+            MessagePart::Value(other_size.clone()),
+            MessagePart::Text(" bytes, ".into()),
+            MessagePart::Value(quote!(#size.saturating_sub(#other_size))),
+            MessagePart::Text(" bytes over)".into()),
+        ],
+    );
+
+    TokenStream::from(check)
+}
+
+/// The macro `assert_size_of_val!(EXPR, USIZE)` performs **compile-time**
+/// check, if the constant array or slice `EXPR` takes the exact number of bytes,
+/// i.e. if `size_of_val(&EXPR)` equals the given size.
+///
+/// It extends the checks from types to values, for the cases where the size is
+/// not a property of a nameable type, e.g. of a `const` table whose length is
+/// inferred. As in `size_of_val`, `EXPR` is the value itself: arrays are given
+/// as they are and slices dereferenced, e.g. `*TABLE` for
+/// `const TABLE: &[u16]`. The size may be followed by `, msg = "..."` replacing
+/// the default failure message.
+///
+/// Check does not pollute namespace, it is expanded to an anonymous const
+/// equivalent to
+/// ```
+/// # const EXPR: [u8; 0] = [];
+/// # const ExpectedSize: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of_val(&EXPR) == ExpectedSize,
+///    "size of 'EXPR' is not ExpectedSize bytes (actual size: ActualSize bytes, \
+///     next power of two: RoundedSize bytes)"
+/// );
+/// ```
+///
+/// The size is computed from the length of the slice `&EXPR` coerces to, as
+/// `size_of_val` itself is not usable in constants before Rust 1.85. So `EXPR`
+/// has to be an array or a slice of `Sized` elements, and a string is checked
+/// by its bytes, e.g. `*NAME.as_bytes()`.
+///
+/// Associated consts are values like any other, given by the path of the
+/// implementing type, e.g. `<Crc16 as Table>::TABLE` or `Crc16::TABLE`. The
+/// check is an item evaluated in const context, which cannot see the generic
+/// parameters of the code around it, so `T::TABLE` of a generic `T` cannot be
+/// checked; check the associated const for each implementing type instead.
+///
+/// # Examples
+///
+/// Success (byte array and a slice of words):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// const MAGIC: [u8; 4] = *b"\x7fELF";
+/// const CRC_TABLE: &[u16] = &[0x0000, 0x1021, 0x2042, 0x3063];
+///
+/// assert_size_of_val!(MAGIC, 4);
+/// assert_size_of_val!(*CRC_TABLE, 8);
+/// ```
+///
+/// Success (associated const arrays of a trait and of a type):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// trait Table {
+///     const TABLE: &'static [u16];
+/// }
+///
+/// struct Crc16;
+///
+/// impl Table for Crc16 {
+///     const TABLE: &'static [u16] = &[0x0000, 0x1021, 0x2042, 0x3063];
+/// }
+///
+/// impl Crc16 {
+///     const NAME: &'static str = "CRC-16/XMODEM";
+///     const POLYNOMIALS: [u32; 2] = [0x1021, 0x8408];
+/// }
+///
+/// assert_size_of_val!(*<Crc16 as Table>::TABLE, 8);
+/// assert_size_of_val!(Crc16::POLYNOMIALS, 8);
+/// assert_size_of_val!(*Crc16::NAME.as_bytes(), 13);
+/// ```
+///
+/// Failure (associated const of a generic parameter, not visible to the check):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// trait Table {
+///     const TABLE: [u16; 4];
+/// }
+///
+/// fn checked<T: Table>() {
+///     assert_size_of_val!(T::TABLE, 8);
+/// }
+/// ```
+///
+/// Failure (real size is 4 bytes, expected is 8 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// const MAGIC: [u8; 4] = *b"\x7fELF";
+///
+/// assert_size_of_val!(MAGIC, 8);
+/// ```
+#[proc_macro]
+pub fn assert_size_of_val(input: TokenStream) -> TokenStream {
+    let ValueSize { expr, size } = parse_macro_input!(input as ValueSize);
+
+    let actual = quote!(SIZE);
+    let check = emit_check(
+        size.span(),
+        quote!(#actual == #size),
+        &size.message(Comparison::Eq.message(&tokens_to_string(&expr), &size, "", actual.clone())),
+    );
+
+    TokenStream::from(quote! {
+        const _: () = {
+            const fn size_of_elements<T>(elements: &[T]) -> usize {
+                elements.len() * ::core::mem::size_of::<T>()
+            }
+            const SIZE: usize = size_of_elements(&#expr);
+
+            #check
+        };
+    })
+}
+
+/// The macro `layout_assert!(TYPE: CLAUSE, ...)` performs **compile-time**
+/// checks of the layout of the given type, described by a list of clauses:
+///
+/// - `size == USIZE`, `size <= USIZE` or `size >= USIZE` compares the size, as
+///   `assert_type_size!`,
+/// - `align == USIZE`, `align <= USIZE` or `align >= USIZE` compares the
+///   alignment,
+/// - `offset(FIELD) == USIZE` (or `<=`, `>=`) compares the offset of a field,
+/// - `no_padding(FIELD, ...)` checks the size equals the sum of sizes of the
+///   listed fields, i.e. that there are no padding bytes between or after them.
+///
+/// It is a single entry point for the checks of the attributes, usable on types
+/// defined elsewhere. As the macro does not see the definition of the type,
+/// `no_padding` needs all its fields listed; a field left out is reported as
+/// padding. Every clause is checked by a separate anonymous const, reported at
+/// the expected value (or at the field list of `no_padding`).
+///
+/// Clause `size == N` is equivalent to
+/// ```
+/// # struct TypeName{}
+/// # const ExpectedSize: usize=0;
+/// #
+/// const _: () = assert!(
+///    core::mem::size_of::<TypeName>() == ExpectedSize,
+///    "size of 'TypeName' is not ExpectedSize bytes (actual size: ActualSize bytes, \
+///     next power of two: RoundedSize bytes)"
+/// );
+/// ```
+///
+/// # Examples
+///
+/// Success (size, alignment, offset and padding of a header):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Header {
+///     id: u64,
+///     len: u32,
+///     flags: u32,
+/// }
+///
+/// layout_assert!(Header: size == 16, align == 8, offset(len) == 8, no_padding(id, len, flags));
+/// ```
+///
+/// Success (bounds of a type defined elsewhere):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// layout_assert!(Option<Box<u8>>: size <= 8, align >= 4);
+/// ```
+///
+/// Failure (3 bytes of padding after `tag`):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Record {
+///     tag: u8,
+///     value: u32,
+/// }
+///
+/// layout_assert!(Record: size == 8, no_padding(tag, value));
+/// ```
+///
+/// Failure (real alignment is 4 bytes, expected is 8 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// layout_assert!(u32: size == 4, align == 8);
+/// ```
+#[proc_macro]
+pub fn layout_assert(input: TokenStream) -> TokenStream {
+    let LayoutAssert { ty, clauses } = parse_macro_input!(input as LayoutAssert);
+    let name = tokens_to_string(&ty);
+
+    let checks = clauses.iter().map(|clause| match clause {
+        LayoutClause::Size(op, size) => emit_assert(&name, &ty, *op, size, None, ""),
+        LayoutClause::Align(op, align) => {
+            let actual = quote!(::core::mem::align_of::<#ty>());
+            emit_check(
+                align.span(),
+                quote!(#actual #op #align),
+                &[
+                    MessagePart::Text(format!(
+                        "alignment of '{}' {} {} bytes (actual alignment: ",
+                        name,
+                        op.verb(),
+                        align
+                    )),
+                    MessagePart::Value(actual),
+                    MessagePart::Text(" bytes)".into()),
+                ],
+            )
+        }
+        LayoutClause::Offset(member, op, offset) => {
+            let actual = emit_offset_of(&ty, member);
+            emit_check(
+                offset.span(),
+                quote!(#actual #op #offset),
+                &[
+                    MessagePart::Text(format!(
+                        "offset of '{}::{}' {} {} bytes (actual offset: ",
+                        name,
+                        tokens_to_string(member),
+                        op.verb(),
+                        offset
+                    )),
+                    MessagePart::Value(actual),
+                    MessagePart::Text(" bytes)".into()),
+                ],
+            )
+        }
+        LayoutClause::NoPadding(fields) => {
+            let actual = quote!(::core::mem::size_of::<#ty>());
+            let sizes = fields
+                .iter()
+                .map(|member| emit_field_size(&ty, member))
+                .collect::<Vec<_>>();
+            let field_sum = quote!(0 #(+ #sizes)*);
+
+            let mut message = vec![
+                MessagePart::Text(format!("'{}' has padding bytes (size: ", name)),
+                MessagePart::Value(actual.clone()),
+                MessagePart::Text(" bytes, sum of field sizes: ".into()),
+                MessagePart::Value(field_sum.clone()),
+                MessagePart::Text(" bytes".into()),
+            ];
+            for (index, (member, size)) in fields.iter().zip(&sizes).enumerate() {
+                let separator = if index == 0 { "; fields: " } else { ", " };
+                message.extend([
+                    MessagePart::Text(format!("{}{} (", separator, tokens_to_string(member))),
+                    MessagePart::Value(size.clone()),
+                    MessagePart::Text(" bytes at offset ".into()),
+                    MessagePart::Value(emit_offset_of(&ty, member)),
+                    MessagePart::Text(")".into()),
+                ]);
+            }
+            message.push(MessagePart::Text(")".into()));
+
+            emit_check(fields.span(), quote!(#actual == #field_sum), &message)
+        }
+    });
+
+    TokenStream::from(quote!(#(#checks)*))
+}
+
+/// The macro `assert_size_print!(TYPE)` is a **developer tool**: it always
+/// aborts the build, reporting the size and alignment of given type.
+///
+/// It is meant for quick iterations on a layout, without running the program.
+/// Remove it once the layout is settled, replacing it with a regular check,
+/// e.g. `assert_type_size!`.
+///
+/// The build aborts with
+/// `size of 'TYPE' is ActualSize bytes (alignment: ActualAlign bytes)`.
+///
+/// # Examples
+///
+/// Reporting the size of a structure, the build aborts with
+/// `size of 'Header' is 12 bytes (alignment: 4 bytes)`:
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[repr(C)]
+/// struct Header {
+///     tag: u8,
+///     len: u32,
+///     crc: u16,
+/// }
+///
+/// assert_size_print!(Header);
+/// ```
+#[proc_macro]
+pub fn assert_size_print(input: TokenStream) -> TokenStream {
+    let ExpType { ty } = parse_macro_input!(input as ExpType);
+
+    let size = quote!(::core::mem::size_of::<#ty>());
+    let align = quote!(::core::mem::align_of::<#ty>());
+    let check = emit_check(
+        ty.span(),
+        quote!(false),
+        &[
+            MessagePart::Text(format!("size of '{}' is ", tokens_to_string(&ty))),
+            MessagePart::Value(size),
+            MessagePart::Text(" bytes (alignment: ".into()),
+            MessagePart::Value(align),
+            MessagePart::Text(" bytes)".into()),
+        ],
+    );
+
+    TokenStream::from(check)
+}
+
+/// The derive `#[derive(AssertLayout)]` performs **compile-time** checks of the
+/// layout of the structure it is attached to, described by `#[layout(...)]`
+/// helper attributes placed next to the definition:
+///
+/// * `#[layout(size = USIZE, align = USIZE)]` on the structure checks its size
+///   and alignment,
+/// * `#[layout(offset = USIZE, size = USIZE)]` on a field checks its offset and
+///   size.
+///
+/// Every key is optional. All checks of the structure are expanded to a single
+/// anonymous const, holding the same checks as generated by `assert_size`,
+/// `assert_align` and `assert_field_offset`, with the size of a field
+/// reported as `size of 'StructName::field' is not ExpectedSize bytes (actual
+/// size: ActualSize bytes)`.
+///
+/// # Examples
+///
+/// Success (structure and field layout matches expected):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[derive(AssertLayout)]
+/// #[layout(size = 16, align = 8)]
+/// #[repr(C)]
+/// struct Header {
+///     #[layout(offset = 0, size = 1)]
+///     tag: u8,
+///     #[layout(offset = 4)]
+///     len: u32,
+///     #[layout(offset = 8, size = 8)]
+///     id: u64,
+/// }
+/// ```
+///
+/// Success (tuple structure):
+///
+/// ```
+/// # use pakr_assert_size::*;
+///
+/// #[derive(AssertLayout)]
+/// #[layout(size = 8)]
+/// #[repr(C)]
+/// struct Pair(#[layout(offset = 0)] u16, #[layout(offset = 4, size = 4)] u32);
+/// ```
+///
+/// Failure (real offset of `len` is 4 bytes, expected is 1 byte):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[derive(AssertLayout)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u8,
+///     #[layout(offset = 1)]
+///     len: u32,
+/// }
+/// ```
+///
+/// Failure (real size of `id` is 8 bytes, expected is 4 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[derive(AssertLayout)]
+/// #[repr(C)]
+/// struct Header {
+///     #[layout(size = 4)]
+///     id: u64,
+/// }
+/// ```
+///
+/// Failure (real size of the structure is 16 bytes, expected is 12 bytes):
+/// ```compile_fail
+/// # use pakr_assert_size::*;
+///
+/// #[derive(AssertLayout)]
+/// #[layout(size = 12)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     id: u64,
+/// }
+/// ```
+#[proc_macro_derive(AssertLayout, attributes(layout))]
+pub fn derive_assert_layout(input: TokenStream) -> TokenStream {
+    let struct_item = parse_macro_input!(input as ItemStruct);
+    let id = &struct_item.ident;
+    let ty = match self_type(id, &struct_item.generics) {
+        Ok(ty) => ty,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let mut checks = TokenStream2::new();
+    let struct_args = match layout_args(&struct_item.attrs, &["size", "align"]) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    for LayoutArg { key, value } in &struct_args {
+        let (actual, what) = if key == "size" {
+            (quote!(::core::mem::size_of::<#ty>()), "size")
+        } else {
+            (quote!(::core::mem::align_of::<#ty>()), "alignment")
+        };
+        checks.extend(emit_check(
+            value.span(),
+            quote!(#actual == #value),
+            &[
+                MessagePart::Text(format!(
+                    "{} of '{}' is not {} bytes (actual {}: ",
+                    what,
+                    qualified(id),
+                    value,
+                    what
+                )),
+                MessagePart::Value(actual),
+                MessagePart::Text(" bytes)".into()),
+            ],
+        ));
+    }
+
+    for (index, field) in struct_item.fields.iter().enumerate() {
+        let field_args = match layout_args(&field.attrs, &["offset", "size"]) {
+            Ok(args) => args,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+        let field_ty = static_lifetimes(&field.ty, &struct_item.generics);
+        for LayoutArg { key, value } in &field_args {
+            let (actual, what) = if key == "offset" {
+                (emit_offset_of(&ty, &member), "offset")
+            } else {
+                (quote!(::core::mem::size_of::<#field_ty>()), "size")
+            };
+            checks.extend(emit_check(
+                value.span(),
+                quote!(#actual == #value),
+                &[
+                    MessagePart::Text(format!(
+                        "{} of '{}::{}' is not {} bytes (actual {}: ",
+                        what,
+                        qualified(id),
+                        tokens_to_string(&member),
+                        value,
+                        what
+                    )),
+                    MessagePart::Value(actual),
+                    MessagePart::Text(" bytes)".into()),
+                ],
+            ));
+        }
+    }
+
+    let checker = quote! {
+        const _: () = {
+            #checks
+        };
+    };
+
+    TokenStream::from(checker)
+}
+
+/// The derive `#[derive(AssertSize)]` performs **compile-time** check, if the
+/// real size of the type it is attached to is equal to the one given in its
+/// helper attribute `#[assert_size(USIZE)]`.
+///
+/// It is the derive form of the attribute `#[assert_size]` for the plain exact
+/// size, with an optional `msg = "..."`. A derive does not replace the item, it
+/// only adds the check next to it, so it may be listed with other derives
+/// without regard to the order of other attribute macros. The helper attribute
+/// is read off the derive input and has to follow the `#[derive]`, where it
+/// takes precedence over the attribute macro `#[assert_size]` imported with
+/// the crate; above it the name is reported as ambiguous.
+///
+/// The derive can be attached to a `struct`, an `enum` or a `union`.
+///
+/// # Examples
+///
+/// Success (real size matches expected):
+///
+/// ```
+/// use pakr_assert_size::AssertSize;
+///
+/// #[derive(Clone, AssertSize)]
+/// #[assert_size(16)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+///     id: u64,
+/// }
+/// ```
+///
+/// Failure (real size is 16 bytes, expected is 12 bytes):
+/// ```compile_fail
+/// use pakr_assert_size::AssertSize;
+///
+/// #[derive(Clone, AssertSize)]
+/// #[assert_size(12)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     id: u64,
+/// }
+/// ```
+#[proc_macro_derive(AssertSize, attributes(assert_size))]
+pub fn derive_assert_size(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+    derive_size(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Expands `#[derive(AssertSize)]`, checking the size of `item` given in its
+/// `#[assert_size]` helper attribute.
+fn derive_size(item: &Item) -> syn::Result<TokenStream2> {
+    let (id, ty) = item_type(item)?;
+    let attrs = match item {
+        Item::Struct(item) => &item.attrs,
+        Item::Enum(item) => &item.attrs,
+        Item::Union(item) => &item.attrs,
+        _ => unreachable!("checked by `item_type`"),
+    };
+
+    let mut helpers = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("assert_size"));
+    let helper = helpers.next().ok_or_else(|| {
+        syn::Error::new(
+            id.span(),
+            format!(
+                "'{}' derives `AssertSize` without the attribute `#[assert_size(SIZE)]`",
+                id
+            ),
+        )
+    })?;
+    if let Some(duplicate) = helpers.next() {
+        return Err(syn::Error::new_spanned(
+            duplicate,
+            "duplicate attribute `#[assert_size]`",
+        ));
+    }
+    let ExpSize { size, msg } = helper.parse_args::<ExpSize>()?;
+
+    Ok(emit_assert(
+        &qualified(id),
+        &ty,
+        Comparison::Eq,
+        &size,
+        msg.as_ref(),
+        "",
+    ))
+}
+
+/// The derive `#[derive(AssertSized)]` checks that the blanket implementation of
+/// the `AssertSized` trait, recording the size of every sized type in the
+/// associated const `SIZE`, applies to the type it is attached to. For a type
+/// without generic parameters `SIZE` is evaluated at compile time; an unsized
+/// type, e.g. one ending in a slice, is a compile error.
+///
+/// Unlike the attributes it supports generic types, as the size is recorded
+/// separately for every instantiation. The check itself is left to the user:
+/// `Self::SIZE` may be compared in const context, or `Self::assert_size(n)`
+/// may be called at runtime.
+///
+/// # Examples
+///
+/// ```
+/// use pakr_assert_size::AssertSized;
+///
+/// #[derive(AssertSized)]
+/// #[repr(C)]
+/// struct Pair<T> {
+///     first: T,
+///     second: T,
+/// }
+///
+/// const _: () = assert!(Pair::<u32>::SIZE == 8);
+/// Pair::<u64>::assert_size(16);
+/// ```
+///
+/// Failure (real size is 8 bytes, expected is 16 bytes at runtime):
+/// ```should_panic
+/// use pakr_assert_size::AssertSized;
+///
+/// #[derive(AssertSized)]
+/// #[repr(C)]
+/// struct Pair<T> {
+///     first: T,
+///     second: T,
+/// }
+///
+/// Pair::<u32>::assert_size(16);
+/// ```
+#[proc_macro_derive(AssertSized)]
+pub fn derive_assert_sized(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+    let (id, generics) = match &item {
+        Item::Struct(item) => (&item.ident, &item.generics),
+        Item::Enum(item) => (&item.ident, &item.generics),
+        Item::Union(item) => (&item.ident, &item.generics),
+        _ => {
+            return syn::Error::new_spanned(&item, "expected struct, enum or union")
+                .to_compile_error()
+                .into()
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // The blanket implementation records the size, only a type without
+    // parameters can be evaluated here.
+    let check = if generics.params.is_empty() {
+        quote! {
+            const _: usize = <#id as ::pakr_assert_size::AssertSized>::SIZE;
+        }
+    } else {
+        quote! {
+            const _: () = {
+                #[allow(dead_code)]
+                fn size #impl_generics () -> usize #where_clause {
+                    <#id #ty_generics as ::pakr_assert_size::AssertSized>::SIZE
+                }
+            };
+        }
+    };
+
+    TokenStream::from(check)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header() -> Ident {
+        Ident::new("Header", Span::call_site())
+    }
+
+    #[test]
+    fn emit_runtime_check_formats_values() {
+        let check = emit_runtime_check(
+            quote!(a == 8),
+            &[
+                MessagePart::Text("'{Header}' is ".into()),
+                MessagePart::Value(quote!(a)),
+                MessagePart::Text(" bytes".into()),
+            ],
+        );
+        assert_eq!(
+            check.to_string(),
+            quote!(::core::assert!(a == 8, "'{{Header}}' is {} bytes", a);).to_string()
+        );
+    }
+
+    /// Checks, if `tokens` contain the identifier `ident` at any depth.
+    fn contains_ident(tokens: TokenStream2, ident: &str) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(found) => found == ident,
+            TokenTree::Group(group) => contains_ident(group.stream(), ident),
+            _ => false,
+        })
+    }
+
+    #[test]
+    fn emit_check_is_private_and_anonymous() {
+        let check = emit_check(
+            Span::call_site(),
+            quote!(a == 8),
+            &[
+                MessagePart::Text("'Header' is ".into()),
+                MessagePart::Value(quote!(a)),
+                MessagePart::Text(" bytes".into()),
+            ],
+        );
+        let head = check
+            .clone()
+            .into_iter()
+            .take(2)
+            .map(|token| token.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(head, ["const", "_"]);
+        assert!(!contains_ident(check, "pub"));
+    }
+
+    /// The note of an audit is the message up to the first value.
+    #[test]
+    fn audit_note_ends_before_values() {
+        let message = Comparison::Eq.message(&qualified(&quote!(Header)), &8, "", quote!(a));
+        let audit = emit_audit(Span::call_site(), quote!(a == 8), &message).to_string();
+        assert!(audit.contains(
+            &quote!(#[deprecated(note = "size of 'Header' is not 8 bytes")]).to_string()
+        ));
+        assert!(!audit.contains("panic"));
+    }
+
+    /// Qualified names are written with the `module_path!()` of the check, at
+    /// compile time as well as at runtime.
+    #[test]
+    fn qualified_names_splice_module_path() {
+        let message = [
+            MessagePart::Text(format!("size of '{}' is ", qualified(&quote!(Header)))),
+            MessagePart::Value(quote!(a)),
+            MessagePart::Text(" bytes".into()),
+        ];
+        let check = emit_check(Span::call_site(), quote!(a == 8), &message).to_string();
+        assert!(check.contains(&quote!(::core::module_path!().as_bytes()).to_string()));
+        assert!(check.contains(&quote!(b"::Header' is ").to_string()));
+        assert!(!check.contains(MODULE_PATH));
+
+        let check = emit_runtime_check(quote!(a == 8), &message).to_string();
+        let format = quote!(::core::concat!(
+            "size of '",
+            ::core::module_path!(),
+            "::Header' is {} bytes"
+        ));
+        assert!(check.contains(&format.to_string()));
+    }
+
+    /// Expansion of `#[assert_size(8)]` on a documented structure, as shown by
+    /// `cargo expand`, matches the snapshot in `tests/expand`.
+    #[test]
+    fn expansion_is_readable() {
+        let item: Item = syn::parse_quote! {
+            /// Header of a frame.
+            #[derive(Clone, Copy)]
+            #[repr(C)]
+            pub struct Header {
+                /// Kind of the frame.
+                pub tag: u32,
+                pub len: u32,
+            }
+        };
+        let size: SizeExpr = syn::parse_quote!(8);
+        let check = emit_assert("Header", &header(), Comparison::Eq, &size, None, "");
+        let file = syn::parse2::<syn::File>(quote!(#check #item)).unwrap();
+        assert_eq!(
+            prettyplease::unparse(&file),
+            include_str!("../tests/expand/assert_size.expanded.rs")
+        );
+    }
+
+    /// Compares `file`, pretty-printed, with the snapshot `name` in
+    /// `tests/expand`, or overwrites the snapshot if `EXPAND=overwrite` is set.
+    #[cfg(not(feature = "runtime-assert"))]
+    fn assert_expansion(name: &str, file: &syn::File) {
+        let path = format!("{}/tests/expand/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let expanded = prettyplease::unparse(file);
+        if std::env::var_os("EXPAND").is_some_and(|mode| mode == "overwrite") {
+            std::fs::write(&path, expanded).unwrap();
+            return;
+        }
+        let snapshot = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            expanded == snapshot,
+            "expansion differs from `{}`, rerun with `EXPAND=overwrite` to accept it:\n{}",
+            name,
+            expanded
+        );
+    }
+
+    /// Complete expansions of `#[assert_size]` and `#[assert_size_fits]` on a
+    /// plain structure match their snapshots in `tests/expand`, so that every
+    /// change of the generated code is reviewed. The registrations of
+    /// `collect` and `json-report`, enabled by the dev-dependency on the
+    /// facade, are part of them, the tests of `runtime-assert` are not.
+    #[cfg(not(feature = "runtime-assert"))]
+    #[test]
+    fn complete_expansions_match_snapshots() {
+        let item: Item = syn::parse_quote! {
+            #[repr(C)]
+            struct Header {
+                tag: u32,
+                len: u32,
+            }
+        };
+        for (name, attr, op) in [
+            (
+                "assert_size_complete.expanded.rs",
+                quote!(8),
+                Comparison::Eq,
+            ),
+            ("assert_size_fits.expanded.rs", quote!(16), Comparison::Le),
+        ] {
+            let expanded = expand_size(attr, item.clone(), op).unwrap();
+            assert_expansion(name, &syn::parse2(expanded).unwrap());
+        }
+    }
+
+    #[test]
+    fn snake_case_words_of_sizes() {
+        assert_eq!(snake_case_words("16"), "16");
+        assert_eq!(snake_case_words("4 * size_of::<u64>()"), "4_size_of_u64");
+        assert_eq!(snake_case_words("24_8"), "24_8");
+    }
+
+    #[test]
+    fn tokens_to_string_of_paths() {
+        assert_eq!(
+            tokens_to_string(&quote!(2 * ::core::mem::size_of::<usize>())),
+            "2 * ::core::mem::size_of::<usize>()"
+        );
+        assert_eq!(
+            tokens_to_string(&quote!(<Modbus as Codec>::Frame)),
+            "<Modbus as Codec>::Frame"
+        );
+    }
+
+    #[test]
+    fn tokens_to_string_of_prefix_operators() {
+        assert_eq!(tokens_to_string(&quote!(*CRC_TABLE)), "*CRC_TABLE");
+        assert_eq!(tokens_to_string(&quote!(4 * -OFFSET)), "4 * -OFFSET");
+        assert_eq!(tokens_to_string(&quote!(TABLE[2 - 1])), "TABLE[2 - 1]");
+        assert_eq!(tokens_to_string(&quote!(r#struct<r#type>)), "struct<type>");
+    }
+
+    /// Parses arguments given with and without a trailing comma.
+    fn parse_both<T: Parse>(with: TokenStream2, without: TokenStream2) -> (T, T) {
+        (
+            syn::parse2(with).expect("with trailing comma"),
+            syn::parse2(without).expect("without trailing comma"),
+        )
+    }
+
+    #[test]
+    fn trailing_comma_after_size() {
+        for (with, without) in [
+            (quote!(8,), quote!(8)),
+            (quote!(8, msg = "header",), quote!(8, msg = "header")),
+        ] {
+            let (with, without) = parse_both::<ExpSize>(with, without);
+            assert_eq!(with.size.value, without.size.value);
+            assert_eq!(
+                with.msg.map(|msg| msg.value()),
+                without.msg.map(|msg| msg.value())
+            );
+        }
+    }
+
+    #[test]
+    fn multi_segment_path_size() {
+        let size = syn::parse2::<ExactSize>(quote!(crate::generated::sizes::HEADER_SIZE)).unwrap();
+        match size.size {
+            CfgSize::Single(size) => {
+                assert_eq!(size.size.value, None);
+                assert_eq!(size.to_string(), "crate::generated::sizes::HEADER_SIZE");
+            }
+            _ => panic!("expected a single size"),
+        }
+    }
+
+    #[test]
+    fn type_name_before_size() {
+        for (tokens, name) in [
+            (quote!(Header = 8), Some("Header")),
+            (quote!(self = 8, msg = "header"), Some("self")),
+            (quote!(Self = 4 | 8), Some("Self")),
+            (quote!(HEADER_LEN), None),
+            (quote!(8, msg = "header"), None),
+        ] {
+            let size = syn::parse2::<ExactSize>(tokens.clone()).unwrap();
+            assert_eq!(
+                size.name.map(|name| name.to_string()).as_deref(),
+                name,
+                "{}",
+                tokens
+            );
+        }
+    }
+
+    /// Runtime checks with a single expected size hand mismatches over to the
+    /// handler registered in the facade.
+    #[cfg(feature = "runtime-assert")]
+    #[test]
+    fn runtime_checks_report_mismatches() {
+        let expand = |attr: TokenStream2| {
+            let item = syn::parse2::<Item>(quote!(
+                #[repr(C)]
+                struct Header(u64);
+            ))
+            .unwrap();
+            expand_size(attr, item, Comparison::Eq).unwrap()
+        };
+        assert!(contains_ident(expand(quote!(8)), "reported"));
+        assert!(!contains_ident(expand(quote!(8 | 16)), "reported"));
+    }
+
+    /// `paranoid` generates the runtime check of `runtime-assert` on its own;
+    /// without the feature it does not consult the mismatch handler.
+    #[test]
+    fn paranoid_checks_are_repeated_at_runtime() {
+        let expand = |attr: TokenStream2| {
+            let item = syn::parse2::<Item>(quote!(
+                #[repr(C)]
+                struct Header(u64);
+            ))
+            .unwrap();
+            expand_size(attr, item, Comparison::Eq).unwrap()
+        };
+        let paranoid = expand(quote!(8, paranoid));
+        assert!(contains_ident(paranoid.clone(), "header_assert_size_8"));
+        assert_eq!(
+            contains_ident(paranoid, "reported"),
+            cfg!(feature = "runtime-assert")
+        );
+        assert_eq!(
+            contains_ident(expand(quote!(8)), "header_assert_size_8"),
+            cfg!(feature = "runtime-assert")
+        );
+    }
+
+    /// Every check of `test_only` is preceded by `#[cfg(test)]`, while the
+    /// structure itself is kept for all builds.
+    #[test]
+    fn test_only_checks_are_gated() {
+        fn gated(attr: TokenStream2) -> (usize, usize) {
+            let item = syn::parse2::<Item>(quote!(
+                #[repr(C)]
+                struct Header(u64);
+            ))
+            .unwrap();
+            let file =
+                syn::parse2::<syn::File>(expand_size(attr, item, Comparison::Eq).unwrap()).unwrap();
+            let cfg_test = |attrs: &[Attribute]| {
+                attrs
+                    .iter()
+                    .any(|attr| attr.path.is_ident("cfg") && attr.tokens.to_string() == "(test)")
+            };
+            let consts = file
+                .items
+                .iter()
+                .filter_map(|item| match item {
+                    Item::Const(item) => Some(cfg_test(&item.attrs)),
+                    Item::Struct(item) => {
+                        assert!(!cfg_test(&item.attrs));
+                        None
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            (consts.len(), consts.iter().filter(|gated| **gated).count())
+        }
+
+        assert_eq!(gated(quote!(8, test_only)), (1, 1));
+        assert_eq!(gated(quote!(8)), (1, 0));
+        assert_eq!(
+            gated(quote!(
+                target_pointer_width = "64" => 8,
+                target_pointer_width = "32" => 8,
+                test_only,
+            )),
+            (2, 2)
+        );
+    }
+
+    #[test]
+    fn flags_among_cfg_arms() {
+        let size = syn::parse2::<ExactSize>(quote!(
+            target_pointer_width = "64" => 16,
+            allow_default_repr,
+            export = HEADER_SIZE,
+            target_pointer_width = "32" => 8,
+        ))
+        .unwrap();
+        assert!(size.allow_default_repr);
+        assert_eq!(size.export.unwrap(), "HEADER_SIZE");
+        match size.size {
+            CfgSize::Arms(arms) => assert_eq!(arms.arms.len(), 2),
+            _ => panic!("expected cfg arms"),
+        }
+    }
+
+    #[test]
+    fn phantom_fill_instantiates_parameters() {
+        let item: Item = syn::parse_quote! {
+            struct Array<'a, T, const N: usize>([&'a T; N]);
+        };
+        for (tokens, ty) in [
+            (quote!(8, phantom_fill = "u64"), None),
+            (
+                quote!(8, phantom_fill(T = u32, N = 3)),
+                Some("Array<'static, u32, 3>"),
+            ),
+            (
+                quote!(
+                    8,
+                    allow_default_repr,
+                    phantom_fill(N = { 2 * 2 }, T = [u8; 2])
+                ),
+                Some("Array<'static, [u8; 2], { 2 * 2 }>"),
+            ),
+        ] {
+            let size = syn::parse2::<ExactSize>(tokens.clone()).unwrap();
+            let fill = size.phantom_fill.expect("phantom_fill");
+            let instantiated = instantiated_type(&item, &fill).ok();
+            assert_eq!(
+                instantiated.map(|(_, ty)| tokens_to_string(&ty)).as_deref(),
+                ty,
+                "{}",
+                tokens
+            );
+        }
+    }
+
+    #[test]
+    fn layout_assert_clauses() {
+        let LayoutAssert { ty, clauses } = syn::parse2::<LayoutAssert>(quote!(
+            proto::Header<'static>: size == 16, align >= 8, offset(1) <= 4, no_padding(id, len),
+        ))
+        .unwrap();
+        assert_eq!(tokens_to_string(&ty), "proto::Header<'static>");
+        let clauses = clauses.into_iter().collect::<Vec<_>>();
+        assert!(matches!(
+            clauses.as_slice(),
+            [
+                LayoutClause::Size(Comparison::Eq, _),
+                LayoutClause::Align(Comparison::Ge, _),
+                LayoutClause::Offset(Member::Unnamed(_), Comparison::Le, _),
+                LayoutClause::NoPadding(fields),
+            ] if fields.len() == 2
+        ));
+
+        assert!(syn::parse2::<LayoutAssert>(quote!(Header:)).is_err());
+        assert!(syn::parse2::<LayoutAssert>(quote!(Header: no_padding())).is_err());
+    }
+
+    #[test]
+    fn allow_default_repr_flag() {
+        for (tokens, allowed) in [
+            (quote!(8), false),
+            (quote!(8, allow_default_repr), true),
+            (quote!(8, msg = "header", allow_default_repr,), true),
+            (quote!(8, allow_default_repr, msg = "header"), true),
+            (
+                quote!(target_os = "linux" => 8, target_os = "windows" => 16, allow_default_repr),
+                true,
+            ),
+            (
+                quote!(target_os = "linux" => 8, target_os = "windows" => 16,),
+                false,
+            ),
+        ] {
+            let size = syn::parse2::<ExactSize>(tokens.clone()).unwrap();
+            assert_eq!(size.allow_default_repr, allowed, "{}", tokens);
+        }
+    }
+
+    #[test]
+    fn trailing_comma_after_fits_size() {
+        let (with, without) = parse_both::<FitsSize>(
+            quote!(32, warn_at = 16, msg = "budget", align = 8,),
+            quote!(32, warn_at = 16, msg = "budget", align = 8),
+        );
+        assert_eq!(with.size.size.value, without.size.size.value);
+        assert_eq!(
+            with.size.msg.map(|msg| msg.value()),
+            without.size.msg.map(|msg| msg.value())
+        );
+        assert_eq!(
+            with.warn_at.and_then(|warn_at| warn_at.value),
+            without.warn_at.and_then(|warn_at| warn_at.value)
+        );
+        assert_eq!(
+            with.align.and_then(|align| align.value),
+            without.align.and_then(|align| align.value)
+        );
+    }
+
+    #[test]
+    fn trailing_comma_after_range() {
+        let (with, without) = parse_both::<RangeSize>(quote!(8, 16,), quote!(8, 16));
+        assert_eq!((with.min, with.max), (without.min, without.max));
+    }
+
+    #[test]
+    fn trailing_comma_after_type_size() {
+        let (with, without) = parse_both::<TypeSize>(
+            quote!(u32 <= 4, msg = "word",),
+            quote!(u32 <= 4, msg = "word"),
+        );
+        assert_eq!(with.op, without.op);
+        assert_eq!(with.size.size.value, without.size.size.value);
+    }
+
+    /// Sizes are bounded by `isize::MAX` of a 64-bit target, whatever the host.
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn sizes_up_to_isize_max() {
+        let size = |lit: &str| lit_size(&LitInt::new(lit, Span::call_site())).ok();
+        assert_eq!(size("9223372036854775807"), Some(i64::MAX as usize));
+        assert_eq!(size("8589934592GiB"), None);
+        assert_eq!(size("8589934591GiB"), Some(8589934591 << 30));
+        assert_eq!(size("18446744073709551616"), None);
+    }
+
+    /// Integer type suffixes are ignored, unless the value does not fit the type.
+    #[test]
+    fn type_suffixes_are_ignored() {
+        let size = |lit: &str| lit_size(&LitInt::new(lit, Span::call_site())).ok();
+        assert_eq!(size("16"), Some(16));
+        assert_eq!(size("16usize"), Some(16));
+        assert_eq!(size("16u32"), Some(16));
+        assert_eq!(size("16i64"), Some(16));
+        assert_eq!(size("0x10u8"), Some(16));
+        assert_eq!(size("255u8"), Some(255));
+        assert_eq!(size("256u8"), None);
+        assert_eq!(size("128i8"), None);
+        assert_eq!(size("1u128"), Some(1));
+    }
+
+    /// Field types `#[assert_transparent]` knows to be zero-sized.
+    #[test]
+    fn known_zero_sized_types() {
+        let zst = |ty: TokenStream2| known_zst(&syn::parse2(ty).unwrap());
+        assert!(zst(quote!(())));
+        assert!(zst(quote!(PhantomData<u64>)));
+        assert!(zst(quote!(core::marker::PhantomData<*mut u8>)));
+        assert!(zst(quote!(::core::marker::PhantomPinned)));
+        assert!(zst(quote!([u64; 0])));
+        assert!(zst(quote!([(); 16])));
+        assert!(zst(quote!((PhantomPinned, [u8; 0]))));
+        assert!(!zst(quote!(u8)));
+        assert!(!zst(quote!(Marker)));
+        assert!(!zst(quote!([u8; N])));
+        assert!(!zst(quote!((PhantomPinned, u8))));
+        assert!(!zst(quote!(<T as Trait>::PhantomData)));
+    }
+
+    /// Arguments of `#[assert_size]` in one of its forms, whose errors are not
+    /// followed by the accepted forms.
+    #[test]
+    fn assert_size_forms() {
+        let form = ExactSize::has_form;
+        assert!(form(quote!(16)));
+        assert!(form(quote!(16, mgs = "typo")));
+        assert!(form(quote!(Header = 16)));
+        assert!(form(quote!(256u8)));
+        assert!(form(quote!(unix => 16, windows => 8)));
+        assert!(form(quote!(lock)));
+        assert!(!form(quote!()));
+        assert!(!form(quote!(@)));
+        assert!(!form(quote!(16 bytes)));
+        assert!(!form(quote!(Header = )));
+    }
+
+    /// Binary units appended to large literal sizes in failure messages.
+    #[test]
+    fn friendly_sizes() {
+        assert_eq!(friendly_size(1023), None);
+        assert_eq!(friendly_size(1024).as_deref(), Some("1 KiB"));
+        assert_eq!(friendly_size(1536).as_deref(), Some("1.5 KiB"));
+        assert_eq!(friendly_size(1365).as_deref(), Some("~1.33 KiB"));
+        assert_eq!(friendly_size(1048575).as_deref(), Some("~1024 KiB"));
+        assert_eq!(friendly_size(1 << 20).as_deref(), Some("1 MiB"));
+        assert_eq!(friendly_size(3 << 30).as_deref(), Some("3 GiB"));
+        assert_eq!(friendly_size(u64::MAX).as_deref(), Some("~17179869184 GiB"));
+
+        let text =
+            |size: TokenStream2| match &Comparison::Eq.message("Arena", &size, "", quote!(a))[0] {
+                MessagePart::Text(text) => text.clone(),
+                MessagePart::Value(_) => unreachable!(),
+            };
+        assert_eq!(
+            text(quote!(1048576)),
+            "size of 'Arena' is not 1048576 bytes (1 MiB) (actual size: "
+        );
+        assert_eq!(
+            text(quote!(1024 * KIB)),
+            "size of 'Arena' is not 1024 * KIB bytes (actual size: "
+        );
+    }
+
+    /// Hints of `repr` attributes as compared by `#[assert_repr]`.
+    #[test]
+    fn repr_hints_of_combined_attributes() {
+        let hints = |item: TokenStream2| {
+            let item = syn::parse2::<ItemStruct>(item).unwrap();
+            reprs(&item.attrs)
+                .map(|nested| repr_hint(&nested))
+                .collect::<Vec<_>>()
+        };
+        let hint = |name: &str, argument| (name.to_owned(), argument);
+
+        assert_eq!(
+            hints(quote!(
+                #[repr(C, align(16))]
+                struct Block([u8; 64]);
+            )),
+            [hint("C", None), hint("align", Some(16))]
+        );
+        assert_eq!(
+            hints(quote!(
+                #[repr(packed(2))]
+                struct Pair(u8, u32);
+            )),
+            [hint("packed", Some(2))]
+        );
+        assert_eq!(
+            hints(quote!(
+                #[repr(C)]
+                #[doc = "pair"]
+                #[repr(align(0x8))]
+                struct Pair(u8, u32);
+            )),
+            [hint("C", None), hint("align", Some(8))]
+        );
+        assert_eq!(
+            hints(quote!(
+                #[repr(C)]
+                #[repr(packed)]
+                struct Pair(u8, u32);
+            )),
+            [hint("C", None), hint("packed", Some(1))]
+        );
+    }
+
+    /// Arms are keyed by any `cfg` predicate, including bare options.
+    #[test]
+    fn cfg_arms_take_any_predicate() {
+        let size = syn::parse2::<ExactSize>(quote!(
+            any(target_os = "linux", target_os = "android") => 144,
+            target_os = "macos" => 16,
+            unix => 8,
+            not(windows) => 4,
+            test_only,
+        ))
+        .unwrap();
+        match size.size {
+            CfgSize::Arms(arms) => {
+                let cfgs = arms
+                    .arms
+                    .iter()
+                    .map(|arm| tokens_to_string(&arm.cfg))
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    cfgs,
+                    [
+                        "any(target_os = \"linux\", target_os = \"android\")",
+                        "target_os = \"macos\"",
+                        "unix",
+                        "not(windows)"
+                    ]
+                );
+            }
+            _ => panic!("expected `cfg` arms"),
+        }
+        assert!(size.test_only);
+    }
+
+    #[test]
+    fn trailing_comma_after_lists() {
+        let (with, without) = parse_both::<ExactSize>(
+            quote!(target_pointer_width = "64" => 16, target_pointer_width = "32" => 8,),
+            quote!(target_pointer_width = "64" => 16, target_pointer_width = "32" => 8),
+        );
+        match (with.size, without.size) {
+            (CfgSize::Arms(with), CfgSize::Arms(without)) => {
+                assert_eq!(with.arms.len(), without.arms.len())
+            }
+            _ => panic!("expected `cfg` arms"),
+        }
+
+        let (with, without) =
+            parse_both::<FieldSizes>(quote!(tag = 0, len = 4,), quote!(tag = 0, len = 4));
+        assert_eq!(with.entries.len(), without.entries.len());
+
+        let (with, without) = parse_both::<ExpTypes>(quote!(u8, u16,), quote!(u8, u16));
+        assert_eq!(with.types.len(), without.types.len());
+
+        let (with, without) =
+            parse_both::<ReprNames>(quote!("C", "packed",), quote!("C", "packed"));
+        assert_eq!(with.names.len(), without.names.len());
+
+        let parser = Punctuated::<LayoutArg, Token![,]>::parse_terminated;
+        let with = parser.parse2(quote!(size = 16, align = 8,)).unwrap();
+        let without = parser.parse2(quote!(size = 16, align = 8)).unwrap();
+        assert_eq!(with.len(), without.len());
+
+        let parser = Punctuated::<TypeEq, Token![,]>::parse_terminated;
+        let with = parser.parse2(quote!(u8 = 1, u16 = 2,)).unwrap();
+        let without = parser.parse2(quote!(u8 = 1, u16 = 2)).unwrap();
+        assert_eq!(with.len(), without.len());
+    }
+
+    #[test]
+    fn emit_assert_compares_with_operator() {
+        let size: SizeExpr = syn::parse_quote!(16);
+
+        for (op, expected) in [
+            (
+                Comparison::Eq,
+                quote!(::core::mem::size_of::<Header>() == 16),
+            ),
+            (
+                Comparison::Le,
+                quote!(::core::mem::size_of::<Header>() <= 16),
+            ),
+            (
+                Comparison::Ge,
+                quote!(::core::mem::size_of::<Header>() >= 16),
+            ),
+        ] {
+            let check = emit_assert("Header", &header(), op, &size, None, "").to_string();
+            assert!(check.contains(&expected.to_string()), "{:?}: {}", op, check);
+        }
+    }
+
+    #[test]
+    fn emit_assert_renders_message() {
+        let size: SizeExpr = syn::parse_quote!(4 * 4);
+
+        let check = emit_assert("Header", &header(), Comparison::Le, &size, None, " on unix");
+        let check = check.to_string();
+        assert!(check.contains(r#"b"'Header' does not fit in 4 * 4 bytes on unix (actual size: ""#));
+        assert!(check.contains("<= (4 * 4)"));
+
+        let msg: LitStr = syn::parse_quote!("must match the header");
+        let check = emit_assert("Header", &header(), Comparison::Eq, &size, Some(&msg), "");
+        let check = check.to_string();
+        assert!(check.contains(r#"b"must match the header""#));
+        assert!(!check.contains("is not"));
+    }
+
+    #[test]
+    fn screaming_snake_case_of_type_names() {
+        assert_eq!(screaming_snake_case("Header"), "HEADER");
+        assert_eq!(screaming_snake_case("HttpHeader"), "HTTP_HEADER");
+        assert_eq!(screaming_snake_case("HTTPHeader"), "HTTP_HEADER");
+        assert_eq!(screaming_snake_case("HeaderV2"), "HEADER_V2");
+        assert_eq!(screaming_snake_case("Ipv4Addr"), "IPV4_ADDR");
+        assert_eq!(screaming_snake_case("Raw_Header"), "RAW_HEADER");
+    }
+
+    #[test]
+    fn emit_assert_is_an_item() {
+        let size: SizeExpr = syn::parse_quote!(8);
+
+        let check = emit_assert("Header", &header(), Comparison::Eq, &size, None, "");
+        assert!(syn::parse2::<Item>(check).is_ok());
+    }
+
+    #[test]
+    fn macros_in_place_of_fields_are_found() {
+        let found = |item: TokenStream2| field_macro(&item).map(|err| err.to_string());
+        assert!(found(quote!(struct S { include!("fields.rs") }))
+            .unwrap()
+            .starts_with("fields cannot come from `include!`"));
+        assert!(found(quote!(enum E { A, #[doc(hidden)] variants!() })).is_some());
+        // Macros in types of fields and in `impl` blocks are valid Rust.
+        assert!(found(quote!(
+            struct S {
+                a: u32,
+                b: bytes!(4),
+            }
+        ))
+        .is_none());
+        assert!(found(quote!(impl S { consts!(); })).is_none());
+    }
+
+    #[test]
+    fn align_arms_parse_like_size_arms() {
+        let arms = quote!(target_pointer_width = "64" => 8, unix => 4,);
+        let size = match syn::parse2::<ExactSize>(arms.clone()).unwrap().size {
+            CfgSize::Arms(arms) => arms.into_sizes(),
+            _ => panic!("expected `cfg` arms"),
+        };
+        let align = match syn::parse2::<ExpAlign>(arms).unwrap() {
+            ExpAlign::Arms(arms) => arms.into_sizes(),
+            ExpAlign::Single(_) => panic!("expected `cfg` arms"),
+        };
+        let render = |arms: Vec<(Option<Meta>, ExpSize)>| {
+            arms.iter()
+                .map(|(cfg, size)| format!("{} => {}", tokens_to_string(cfg), size))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(render(size), render(align));
+        assert!(matches!(
+            syn::parse2::<ExpAlign>(quote!(8, msg = "DMA")),
+            Ok(ExpAlign::Single(_))
+        ));
+        // Flags belong to `#[assert_size]` only.
+        assert!(syn::parse2::<ExpAlign>(quote!(unix => 8, test_only)).is_err());
+    }
+
+    #[test]
+    fn doc_states_expected_size() {
+        fn docs(attr: TokenStream2) -> Vec<String> {
+            let item = syn::parse2::<Item>(quote!(
+                /// Header of a frame.
+                #[repr(C)]
+                struct Header(u64);
+            ))
+            .unwrap();
+            let file =
+                syn::parse2::<syn::File>(expand_size(attr, item, Comparison::Eq).unwrap()).unwrap();
+            file.items
+                .iter()
+                .find_map(|item| match item {
+                    Item::Struct(item) => Some(item),
+                    _ => None,
+                })
+                .unwrap()
+                .attrs
+                .iter()
+                .filter_map(|attr| match attr.parse_meta().unwrap() {
+                    Meta::NameValue(syn::MetaNameValue {
+                        lit: Lit::Str(text),
+                        ..
+                    }) => Some(text.value()),
+                    Meta::List(list) if list.path.is_ident("cfg_attr") => {
+                        match (&list.nested[0], &list.nested[1]) {
+                            (
+                                NestedMeta::Meta(cfg),
+                                NestedMeta::Meta(Meta::NameValue(syn::MetaNameValue {
+                                    lit: Lit::Str(text),
+                                    ..
+                                })),
+                            ) => Some(format!("{}: {}", tokens_to_string(cfg), text.value())),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+                .collect()
+        }
+
+        assert_eq!(
+            docs(quote!(8, doc)),
+            [
+                " Header of a frame.",
+                "",
+                "Size: 8 bytes, checked by `#[assert_size]`."
+            ]
+        );
+        assert_eq!(
+            docs(quote!(unix => 8, windows => HEADER_SIZE, doc))[2..],
+            [
+                "unix: Size: 8 bytes, checked by `#[assert_size]`.",
+                "windows: Size: `HEADER_SIZE` bytes, checked by `#[assert_size]`."
+            ]
+        );
+        assert_eq!(
+            docs(quote!(8, doc, abi = "1.2"))[2],
+            "Size: 8 bytes, checked by `#[assert_size]`. Part of ABI 1.2."
+        );
+        assert_eq!(docs(quote!(8)), [" Header of a frame."]);
+        let stable =
+            "Size is the same in debug and release builds, checked by `#[assert_size]` in both.";
+        assert_eq!(
+            docs(quote!(8, stable_across_opt)),
+            [" Header of a frame.", "", stable]
+        );
+        assert_eq!(docs(quote!(unix => 8, stable_across_opt))[2], stable);
+        assert_eq!(docs(quote!(8, doc, stable_across_opt))[4], stable);
+    }
+
+    #[test]
+    fn non_exhaustive_is_kept() {
+        let item = syn::parse2::<Item>(quote!(
+            #[non_exhaustive]
+            #[repr(C)]
+            pub struct Header(u64);
+        ))
+        .unwrap();
+        let file = syn::parse2::<syn::File>(expand_size(quote!(8), item, Comparison::Eq).unwrap())
+            .unwrap();
+        let attrs = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Item::Struct(item) => Some(&item.attrs),
+                _ => None,
+            })
+            .unwrap();
+        assert!(attrs[0].path.is_ident("non_exhaustive"));
+        assert!(attrs[1].path.is_ident("repr"));
+    }
+}
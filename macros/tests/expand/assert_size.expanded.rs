@@ -0,0 +1,27 @@
+const _: () = if !(::core::mem::size_of::<Header>() == 8) {
+    let (bytes, len) = ::pakr_assert_size::__private::format::<
+        120,
+    >(
+        &[
+            ::pakr_assert_size::__private::Part::Text(
+                b"size of 'Header' is not 8 bytes (actual size: ",
+            ),
+            ::pakr_assert_size::__private::Part::Value(::core::mem::size_of::<Header>()),
+            ::pakr_assert_size::__private::Part::Text(b" bytes, next power of two: "),
+            ::pakr_assert_size::__private::Part::Value(
+                ::core::mem::size_of::<Header>().next_power_of_two(),
+            ),
+            ::pakr_assert_size::__private::Part::Text(b" bytes)"),
+        ],
+    );
+    let message = ::pakr_assert_size::__private::message(&bytes, len);
+    ::pakr_assert_size::__private::panic!("{}", message);
+};
+/// Header of a frame.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct Header {
+    /// Kind of the frame.
+    pub tag: u32,
+    pub len: u32,
+}
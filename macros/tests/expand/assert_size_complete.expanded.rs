@@ -0,0 +1,36 @@
+const _: () = if !(::core::mem::size_of::<Header>() == 8) {
+    let (bytes, len) = ::pakr_assert_size::__private::format::<
+        { 122 + 1 * ::core::module_path!().len() },
+    >(
+        &[
+            ::pakr_assert_size::__private::Part::Text(b"size of '"),
+            ::pakr_assert_size::__private::Part::Text(::core::module_path!().as_bytes()),
+            ::pakr_assert_size::__private::Part::Text(
+                b"::Header' is not 8 bytes (actual size: ",
+            ),
+            ::pakr_assert_size::__private::Part::Value(::core::mem::size_of::<Header>()),
+            ::pakr_assert_size::__private::Part::Text(b" bytes, next power of two: "),
+            ::pakr_assert_size::__private::Part::Value(
+                ::core::mem::size_of::<Header>().next_power_of_two(),
+            ),
+            ::pakr_assert_size::__private::Part::Text(b" bytes)"),
+        ],
+    );
+    let message = ::pakr_assert_size::__private::message(&bytes, len);
+    ::pakr_assert_size::__private::panic!("{}", message);
+};
+::pakr_assert_size::__private::inventory::submit! {
+    ::pakr_assert_size::AssertedSize { type_name :
+    ::core::concat!(::core::module_path!(), "::", "Header"), size :
+    ::core::mem::size_of:: < Header > (), }
+}
+::pakr_assert_size::__private::inventory::submit! {
+    ::pakr_assert_size::LayoutCheck { type_name : ::core::concat!(::core::module_path!(),
+    "::", "Header"), kind : "assert_size", expected : { const EXPECTED : & [usize] = &
+    [8]; EXPECTED }, actual : ::core::mem::size_of:: < Header >, }
+}
+#[repr(C)]
+struct Header {
+    tag: u32,
+    len: u32,
+}
@@ -0,0 +1,37 @@
+const _: () = if !(::core::mem::size_of::<Header>() <= 16) {
+    let (bytes, len) = ::pakr_assert_size::__private::format::<
+        { 157 + 1 * ::core::module_path!().len() },
+    >(
+        &[
+            ::pakr_assert_size::__private::Part::Text(b"'"),
+            ::pakr_assert_size::__private::Part::Text(::core::module_path!().as_bytes()),
+            ::pakr_assert_size::__private::Part::Text(
+                b"::Header' does not fit in 16 bytes (actual size: ",
+            ),
+            ::pakr_assert_size::__private::Part::Value(::core::mem::size_of::<Header>()),
+            ::pakr_assert_size::__private::Part::Text(b" bytes, "),
+            ::pakr_assert_size::__private::Part::Value(
+                ::core::mem::size_of::<Header>().saturating_sub(16),
+            ),
+            ::pakr_assert_size::__private::Part::Text(
+                b" bytes over, next power of two: ",
+            ),
+            ::pakr_assert_size::__private::Part::Value(
+                ::core::mem::size_of::<Header>().next_power_of_two(),
+            ),
+            ::pakr_assert_size::__private::Part::Text(b" bytes)"),
+        ],
+    );
+    let message = ::pakr_assert_size::__private::message(&bytes, len);
+    ::pakr_assert_size::__private::panic!("{}", message);
+};
+::pakr_assert_size::__private::inventory::submit! {
+    ::pakr_assert_size::LayoutCheck { type_name : ::core::concat!(::core::module_path!(),
+    "::", "Header"), kind : "assert_size_fits", expected : { const EXPECTED : & [usize] =
+    & [16]; EXPECTED }, actual : ::core::mem::size_of:: < Header >, }
+}
+#[repr(C)]
+struct Header {
+    tag: u32,
+    len: u32,
+}
@@ -1,166 +1,1395 @@
+#![cfg_attr(not(test), no_std)]
 
-use proc_macro::TokenStream;
-use quote::quote;
-use syn::parse::{Parse, ParseStream};
-use syn::{parse_macro_input,  ItemStruct, LitInt};
+#[cfg(test)]
+extern crate self as pakr_assert_size;
 
-#[derive(Debug)]
-struct ExpSize {
-    size: usize,
-}
+#[cfg(feature = "json-report")]
+extern crate alloc;
+
+pub use pakr_assert_size_macros::*;
 
-impl Parse for ExpSize {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let value = input.parse::<LitInt>()?.base10_parse::<usize>()?;
-        Ok(ExpSize { size: value })
+/// Size of a type, available to generic code receiving the expected size at
+/// runtime, where the attributes cannot help.
+///
+/// Implemented for every sized type, recording `core::mem::size_of::<Self>()`
+/// as `SIZE`. `#[derive(AssertSized)]` additionally checks at compile time that
+/// the implementation applies to the type it is attached to.
+///
+/// # Examples
+///
+/// ```
+/// use pakr_assert_size::AssertSized;
+///
+/// #[derive(AssertSized)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// // Compile-time, in const context
+/// const _: () = assert!(Header::SIZE == 8);
+///
+/// // Runtime, e.g. with a size read from a file
+/// fn check<T: AssertSized>(expected: usize) {
+///     T::assert_size(expected);
+/// }
+/// check::<Header>(8);
+/// ```
+pub trait AssertSized {
+    /// Size of the type in bytes.
+    const SIZE: usize;
+
+    /// Panics, if the size of the type is not `expected` bytes.
+    fn assert_size(expected: usize) {
+        assert!(
+            Self::SIZE == expected,
+            "size of '{}' is not {} bytes (actual size: {} bytes)",
+            core::any::type_name::<Self>(),
+            expected,
+            Self::SIZE
+        );
     }
 }
 
-/// The attribute `#[assert_size(USIZE)]` performs **compile-time** check, if the
-/// structure it is attached to has the exact size in bytes.
+impl<T> AssertSized for T {
+    const SIZE: usize = core::mem::size_of::<T>();
+}
+
+/// Panics, if the size of `T` is not `expected` bytes.
+///
+/// Meant for composing checks in `const fn`s of your own, where no item can be
+/// annotated. Called in const position, e.g. in `const _: () = ...;`, a failing
+/// check is a compile error; called at runtime, it is a regular panic.
 ///
-/// It uses the newly stabilized usage of `panic!` in const context to perform
-/// check and early bailout.
+/// # Examples
 ///
-/// Check does not pollute namespace, it is expanded to
 /// ```
-/// # struct StructName{}
-/// # const ExpectedSize: usize=0;
-/// #
-/// const _: () = assert!(
-///    std::mem::size_of::<StructName>() == ExpectedSize,
-///    "size of 'StructName' is not ExpectedSize bytes"
-/// );
+/// use pakr_assert_size::assert_size_eq;
+///
+/// const fn check_header<T>() {
+///     assert_size_eq::<T>(8);
+/// }
+///
+/// const _: () = check_header::<[u32; 2]>();
 /// ```
 ///
+/// ```compile_fail
+/// use pakr_assert_size::assert_size_eq;
+///
+/// const _: () = assert_size_eq::<u64>(4);
+/// ```
+pub const fn assert_size_eq<T>(expected: usize) {
+    assert!(
+        core::mem::size_of::<T>() == expected,
+        "size of type is not equal to expected size"
+    );
+}
+
+/// Panics, if the size of `T` exceeds `max` bytes.
+///
+/// Counterpart of [`assert_size_eq`] checking an upper bound only. Called in
+/// const position, a failing check is a compile error.
+///
 /// # Examples
 ///
-/// Success (real size matches expected):
+/// ```
+/// use pakr_assert_size::assert_size_le;
 ///
+/// const _: () = assert_size_le::<u32>(8);
 /// ```
-/// # use pakr_assert_size::*;
 ///
-/// #[repr(C, packed)]
-/// #[assert_size(16)]
-/// struct A {
-///     field1: u64,
-///     field2: u64,
-/// }
+/// ```compile_fail
+/// use pakr_assert_size::assert_size_le;
+///
+/// const _: () = assert_size_le::<[u8; 16]>(8);
 /// ```
+pub const fn assert_size_le<T>(max: usize) {
+    assert!(
+        core::mem::size_of::<T>() <= max,
+        "size of type exceeds maximum size"
+    );
+}
+
+/// Checks the size of a type at compile time, like `assert_type_size!`, but
+/// declaratively, without expanding a procedural macro.
 ///
-/// Success (real size matches expected):
+/// Meant for build-time-sensitive code and for expansions of other
+/// `macro_rules!`, where a procedural macro is not wanted. The size is compared
+/// with `, == USIZE` (also written as just `, USIZE`), `, <= USIZE` or
+/// `, >= USIZE`; the comma is required, as a type cannot be followed by a
+/// comparison operator in `macro_rules!`. The failure message quotes the type
+/// and the expected size as written, without the actual size.
+///
+/// # Examples
 ///
 /// ```
-/// # use pakr_assert_size::*;
+/// use pakr_assert_size::assert_size_const;
 ///
-/// #[assert_size(24)]
-/// #[repr(C, packed)]
-/// struct B {
-///     field1: u64,
-///     field2: u64,
-///     field3: u64,
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
 /// }
+///
+/// assert_size_const!(Header, 8);
+/// assert_size_const!(Header, == 8);
+/// assert_size_const!(Header, <= 16);
+/// assert_size_const!([Header; 2], >= 16);
+///
+/// const HEADER_LEN: usize = {
+///     assert_size_const!(Header, <= 8);
+///     core::mem::size_of::<Header>()
+/// };
+/// # assert_eq!(HEADER_LEN, 8);
 /// ```
 ///
-/// Failure (real size is 24 bytes, expected is 32 bytes):
+/// Failure, the build aborts with `size of 'u64' is not 4 bytes`:
 /// ```compile_fail
-/// # use pakr_assert_size::*;
-///
-/// #[assert_size(32)]
-/// #[repr(C, packed)]
-/// struct C {
-///     field1: u64,
-///     field2: u64,
-///     field3: u64,
-/// }
+/// use pakr_assert_size::assert_size_const;
+///
+/// assert_size_const!(u64, 4);
 /// ```
-#[proc_macro_attribute]
-pub fn assert_size(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let size = parse_macro_input!(attr as ExpSize);
-    let size = size.size;
-
-    let struct_item = parse_macro_input!(item as ItemStruct);
-    let id = struct_item.ident.clone();
-
-    let message = format!("size of '{}' is not {} bytes", id, size);
-
-    let checker = quote! {
-        const _: () = assert!(std::mem::size_of::<#id>() == #size, #message);
-        #struct_item
+///
+/// ```compile_fail
+/// use pakr_assert_size::assert_size_const;
+///
+/// assert_size_const!([u8; 16], <= 8);
+/// ```
+#[macro_export]
+macro_rules! assert_size_const {
+    ($ty:ty, == $size:expr $(,)?) => {
+        const _: () = ::core::assert!(
+            ::core::mem::size_of::<$ty>() == $size,
+            ::core::concat!(
+                "size of '",
+                ::core::stringify!($ty),
+                "' is not ",
+                ::core::stringify!($size),
+                " bytes"
+            )
+        );
+    };
+    ($ty:ty, <= $size:expr $(,)?) => {
+        const _: () = ::core::assert!(
+            ::core::mem::size_of::<$ty>() <= $size,
+            ::core::concat!(
+                "'",
+                ::core::stringify!($ty),
+                "' does not fit in ",
+                ::core::stringify!($size),
+                " bytes"
+            )
+        );
+    };
+    ($ty:ty, >= $size:expr $(,)?) => {
+        const _: () = ::core::assert!(
+            ::core::mem::size_of::<$ty>() >= $size,
+            ::core::concat!(
+                "'",
+                ::core::stringify!($ty),
+                "' is smaller than ",
+                ::core::stringify!($size),
+                " bytes"
+            )
+        );
+    };
+    ($ty:ty, $size:expr $(,)?) => {
+        $crate::assert_size_const!($ty, == $size);
     };
-
-    TokenStream::from(checker)
 }
 
-/// The attribute `#[assert_size_fits(USIZE)]` performs **compile-time** check, if the
-/// structure it is attached is not bigger than given amount of bytes.
+/// Size of a type checked by `#[assert_size]`, registered with the `collect`
+/// feature.
+///
+/// # Examples
+///
+/// Writing a manifest of all checked sizes, e.g. in a test:
 ///
-/// Check does not pollute namespace, it is expanded to
 /// ```
-/// # struct StructName{}
-/// # const ExpectedSize: usize=0;
-/// #
-/// const _: () = assert!(
-///    std::mem::size_of::<StructName>() <= ExpectedSize,
-///    "'StructName' does not fit in ExpectedSize bytes"
-/// );
+/// use pakr_assert_size::*;
+///
+/// #[assert_size(8)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
+/// }
+///
+/// for entry in asserted_sizes() {
+///     println!("{} = {}", entry.type_name, entry.size);
+/// }
+/// # assert!(asserted_sizes().any(|entry| entry.type_name.ends_with("::Header") && entry.size == 8));
 /// ```
+#[cfg(feature = "collect")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssertedSize {
+    /// Path of the type, e.g. `my_crate::proto::Header`.
+    pub type_name: &'static str,
+    /// Size of the type in bytes, as verified by the check.
+    pub size: usize,
+}
+
+#[cfg(feature = "collect")]
+inventory::collect!(AssertedSize);
+
+/// Returns the sizes of all types checked by `#[assert_size]` in the program,
+/// in no particular order.
+#[cfg(feature = "collect")]
+pub fn asserted_sizes() -> impl Iterator<Item = &'static AssertedSize> {
+    inventory::iter::<AssertedSize>.into_iter()
+}
+
+/// Layout check registered with the `json-report` feature, listed by
+/// `layout_report()`.
+///
+/// Registered by `#[assert_size]`, `#[assert_size_fits]`,
+/// `#[assert_size_at_least]` and `#[assert_align]`.
+#[cfg(feature = "json-report")]
+#[derive(Debug, Clone, Copy)]
+pub struct LayoutCheck {
+    /// Path of the type, e.g. `my_crate::proto::Header`.
+    pub type_name: &'static str,
+    /// Name of the attribute performing the check, e.g. `assert_size_fits`.
+    pub kind: &'static str,
+    /// Expected values in bytes: the bound of the check, or all alternatives
+    /// accepted by it, e.g. `[4, 8]` for `#[assert_size(4 | 8)]`.
+    pub expected: &'static [usize],
+    /// Returns the actual value in bytes, e.g. `core::mem::size_of::<Header>`.
+    pub actual: fn() -> usize,
+}
+
+#[cfg(feature = "json-report")]
+inventory::collect!(LayoutCheck);
+
+/// Returns all layout checks registered in the program as a JSON array, one
+/// object per check with the keys `type`, `kind`, `expected` (an array) and
+/// `actual`, sorted by type and kind so reports of different builds can be
+/// compared line by line.
 ///
 /// # Examples
 ///
-/// Success (real size matches expected):
+/// Writing the report for a CI dashboard tracking the ABI, e.g. in a test:
 ///
 /// ```
-/// # use pakr_assert_size::*;
+/// use pakr_assert_size::*;
 ///
-/// #[repr(C, packed)]
-/// #[assert_size_fits(16)]
-/// struct A {
-///     field1: u64,
-///     field2: u64,
+/// #[assert_size(8)]
+/// #[assert_align(4)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
 /// }
+///
+/// let report = layout_report();
+/// println!("{}", report);
+/// # assert!(report.contains(r#"::Header", "kind": "assert_size", "expected": [8], "actual": 8}"#));
+/// # assert!(report.contains(r#"::Header", "kind": "assert_align", "expected": [4], "actual": 4}"#));
 /// ```
+#[cfg(feature = "json-report")]
+pub fn layout_report() -> alloc::string::String {
+    use core::fmt::Write;
+
+    let mut checks = inventory::iter::<LayoutCheck>
+        .into_iter()
+        .collect::<alloc::vec::Vec<_>>();
+    checks.sort_by_key(|check| (check.type_name, check.kind));
+
+    let mut out = alloc::string::String::from("[");
+    for (index, check) in checks.iter().enumerate() {
+        out.push_str(if index == 0 { "\n  " } else { ",\n  " });
+        out.push_str("{\"type\": ");
+        write_json_str(&mut out, check.type_name);
+        out.push_str(", \"kind\": ");
+        write_json_str(&mut out, check.kind);
+        out.push_str(", \"expected\": [");
+        for (index, expected) in check.expected.iter().enumerate() {
+            if index > 0 {
+                out.push_str(", ");
+            }
+            let _ = write!(out, "{}", expected);
+        }
+        let _ = write!(out, "], \"actual\": {}}}", (check.actual)());
+    }
+    out.push_str(if checks.is_empty() { "]\n" } else { "\n]\n" });
+    out
+}
+
+/// Appends `value` to `out` as a JSON string literal.
+#[cfg(feature = "json-report")]
+fn write_json_str(out: &mut alloc::string::String, value: &str) {
+    use core::fmt::Write;
+
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Receiver of size mismatches found by the runtime checks of the
+/// `runtime-assert` feature, called with the path of the type, the expected size
+/// and the actual size.
+#[cfg(feature = "runtime-assert")]
+pub type MismatchHandler = fn(type_name: &'static str, expected: usize, actual: usize);
+
+/// Handler registered with `set_mismatch_handler()`, null until then.
+#[cfg(feature = "runtime-assert")]
+static MISMATCH_HANDLER: core::sync::atomic::AtomicPtr<()> =
+    core::sync::atomic::AtomicPtr::new(core::ptr::null_mut());
+
+/// Registers `handler` to receive the size mismatches of the runtime checks,
+/// instead of panicking, e.g. to log them on targets which must not panic. The
+/// last registered handler is used.
 ///
-/// Success (real size is less than expected):
+/// # Examples
 ///
 /// ```
-/// # use pakr_assert_size::*;
+/// use pakr_assert_size::{report_mismatch, set_mismatch_handler};
 ///
-/// #[assert_size_fits(32)]
-/// #[repr(C, packed)]
-/// struct B {
-///     field1: u64,
-///     field2: u64,
-///     field3: u64,
+/// fn log_mismatch(type_name: &'static str, expected: usize, actual: usize) {
+///     eprintln!("{}: expected {} bytes, found {} bytes", type_name, expected, actual);
 /// }
+///
+/// set_mismatch_handler(log_mismatch);
+/// report_mismatch("my_crate::Header", 8, 12);
 /// ```
+#[cfg(feature = "runtime-assert")]
+pub fn set_mismatch_handler(handler: MismatchHandler) {
+    MISMATCH_HANDLER.store(handler as *mut (), core::sync::atomic::Ordering::Release);
+}
+
+/// Reports that `type_name` is `actual` bytes instead of `expected` bytes to the
+/// handler registered with `set_mismatch_handler()`, panicking if there is none.
 ///
-/// Failure (real size is 24 bytes, exceeding maximum of 16 bytes):
-/// ```compile_fail
-/// # use pakr_assert_size::*;
-///
-/// #[assert_size_fits(16)]
-/// #[repr(C, packed)]
-/// struct C {
-///     field1: u64,
-///     field2: u64,
-///     field3: u64,
+/// Called by the runtime checks of the `runtime-assert` feature, with `expected`
+/// being the bound of the check for `#[assert_size_fits]` and
+/// `#[assert_size_at_least]`.
+#[cfg(feature = "runtime-assert")]
+pub fn report_mismatch(type_name: &'static str, expected: usize, actual: usize) {
+    if !__private::reported(type_name, expected, actual) {
+        panic!(
+            "size of '{}' is not {} bytes (actual size: {} bytes)",
+            type_name, expected, actual
+        );
+    }
+}
+
+/// Everything the crate provides, for a single glob import.
+///
+/// # Examples
+///
+/// ```
+/// use pakr_assert_size::prelude::*;
+///
+/// #[derive(AssertSized)]
+/// #[assert_size(8)]
+/// #[repr(C)]
+/// struct Header {
+///     tag: u32,
+///     len: u32,
 /// }
+///
+/// layout_assert!(Header: size == 8, align == 4, offset(len) == 4, no_padding(tag, len));
+/// assert_size_const!(Header, == 8);
+/// const _: () = assert_size_eq::<Header>(Header::SIZE);
 /// ```
-#[proc_macro_attribute]
-pub fn assert_size_fits(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let size = parse_macro_input!(attr as ExpSize);
-    let size = size.size;
+pub mod prelude {
+    pub use crate::{assert_size_const, assert_size_eq, assert_size_le, AssertSized};
+    #[cfg(feature = "collect")]
+    pub use crate::{asserted_sizes, AssertedSize};
+    #[cfg(feature = "json-report")]
+    pub use crate::{layout_report, LayoutCheck};
+    #[cfg(feature = "runtime-assert")]
+    pub use crate::{report_mismatch, set_mismatch_handler, MismatchHandler};
+    pub use pakr_assert_size_macros::*;
+}
 
-    let struct_item = parse_macro_input!(item as ItemStruct);
-    let id = struct_item.ident.clone();
+/// Not a public API, used by the generated code.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "collect")]
+    pub use inventory;
 
-    let message = format!("'{}' does not fit in {} bytes", id, size);
+    /// `core::panic!` of the checks, which are spanned at the annotated code,
+    /// where `::core` names an item at the root of a crate of the 2015 edition.
+    pub use core::panic;
 
-    let checker = quote! {
-        const _: () = assert!(std::mem::size_of::<#id>() <= #size, #message);
-        #struct_item
-    };
+    /// Part of the failure message of a check.
+    pub enum Part {
+        /// Text known at expansion time.
+        Text(&'static [u8]),
+        /// Value formatted in decimal.
+        Value(usize),
+    }
+
+    /// Assembles the failure message from `parts` into a buffer of `CAP` bytes,
+    /// returning the buffer and the length of the message.
+    ///
+    /// The capacity is computed at expansion time, allowing 20 digits, the
+    /// length of `u64::MAX`, for every value, so no constant depending on the
+    /// values has to be evaluated by passing checks.
+    pub const fn format<const CAP: usize>(parts: &[Part]) -> ([u8; CAP], usize) {
+        let mut buf = [0u8; CAP];
+        let mut pos = 0;
+        let mut i = 0;
+        while i < parts.len() {
+            match parts[i] {
+                Part::Text(text) => {
+                    let mut j = 0;
+                    while j < text.len() {
+                        buf[pos] = text[j];
+                        pos += 1;
+                        j += 1;
+                    }
+                }
+                Part::Value(value) => {
+                    let mut div = 1;
+                    while value / div >= 10 {
+                        div *= 10;
+                    }
+                    while div > 0 {
+                        buf[pos] = b'0' + (value / div % 10) as u8;
+                        pos += 1;
+                        div /= 10;
+                    }
+                }
+            }
+            i += 1;
+        }
+        (buf, pos)
+    }
+
+    /// Passes the mismatch to the registered handler, returning `false` when
+    /// there is none, so the caller panics with its own message.
+    #[cfg(feature = "runtime-assert")]
+    pub fn reported(type_name: &'static str, expected: usize, actual: usize) -> bool {
+        let handler = super::MISMATCH_HANDLER.load(core::sync::atomic::Ordering::Acquire);
+        if handler.is_null() {
+            return false;
+        }
+        // SAFETY: only `set_mismatch_handler()` stores a pointer, cast from a
+        // `MismatchHandler`.
+        let handler = unsafe { core::mem::transmute::<*mut (), super::MismatchHandler>(handler) };
+        handler(type_name, expected, actual);
+        true
+    }
+
+    /// Returns the greatest of `values`, or 1, the alignment of a type with no
+    /// fields, if there are none.
+    pub const fn max_align(values: &[usize]) -> usize {
+        let mut max = 1;
+        let mut i = 0;
+        while i < values.len() {
+            if values[i] > max {
+                max = values[i];
+            }
+            i += 1;
+        }
+        max
+    }
+
+    /// Returns the message of `len` bytes at the start of `bytes`.
+    pub const fn message(mut bytes: &[u8], len: usize) -> &str {
+        // Slicing with a range is not available in const context.
+        while bytes.len() > len {
+            if let [rest @ .., _] = bytes {
+                bytes = rest;
+            }
+        }
+        match core::str::from_utf8(bytes) {
+            Ok(message) => message,
+            Err(_) => "size assertion failed",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assert_size_eq, assert_size_le, AssertSized};
+
+    #[derive(AssertSized)]
+    #[repr(C)]
+    struct Header {
+        _tag: u32,
+        _len: u32,
+    }
+
+    #[derive(AssertSized)]
+    #[repr(C)]
+    struct Pair<T> {
+        _first: T,
+        _second: T,
+    }
+
+    #[test]
+    fn records_size() {
+        assert_eq!(Header::SIZE, 8);
+        assert_eq!(Pair::<u16>::SIZE, 4);
+        assert_eq!(Pair::<u64>::SIZE, 16);
+    }
+
+    #[test]
+    fn runtime_check_passes() {
+        Header::assert_size(8);
+        Pair::<u32>::assert_size(8);
+    }
+
+    #[test]
+    fn records_size_without_derive() {
+        assert_eq!(u64::SIZE, 8);
+        assert_eq!(<[u16; 3]>::SIZE, 6);
+        <(u8, u32)>::assert_size(8);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not 16 bytes (actual size: 8 bytes)")]
+    fn runtime_check_fails() {
+        Header::assert_size(16);
+    }
+
+    #[test]
+    fn max_align_of_fields() {
+        use super::__private::max_align;
+
+        const _: () = assert!(max_align(&[4, 16, 8]) == 16);
+        assert_eq!(max_align(&[]), 1);
+        assert_eq!(max_align(&[1, 2]), 2);
+    }
+
+    #[test]
+    fn failure_message_is_assembled() {
+        use super::__private::{format, message, Part};
+
+        const PARTS: [Part; 4] = [
+            Part::Text(b"size: "),
+            Part::Value(0),
+            Part::Text(b", max: "),
+            Part::Value(usize::MAX),
+        ];
+        let (bytes, len) = format::<{ 13 + 2 * 20 }>(&PARTS);
+        assert_eq!(
+            message(&bytes, len),
+            format!("size: 0, max: {}", usize::MAX)
+        );
+    }
+
+    #[test]
+    fn const_helpers_pass() {
+        const _: () = assert_size_eq::<Header>(8);
+        const _: () = assert_size_le::<Header>(8);
+        const _: () = assert_size_le::<Pair<u8>>(8);
+    }
+
+    #[test]
+    fn declarative_checks_pass() {
+        crate::assert_size_const!(Header, 8);
+        crate::assert_size_const!(Header, == 2 * 4,);
+        crate::assert_size_const!(Pair<u8>, <= 2);
+        crate::assert_size_const!(Pair<u64>, >= 16);
+    }
+
+    #[test]
+    #[should_panic(expected = "size of type is not equal to expected size")]
+    fn const_helper_eq_fails_at_runtime() {
+        assert_size_eq::<Header>(4);
+    }
+
+    #[test]
+    #[should_panic(expected = "size of type exceeds maximum size")]
+    fn const_helper_le_fails_at_runtime() {
+        assert_size_le::<Header>(4);
+    }
+
+    /// Checks applied with `cfg_attr`, only when the predicate holds, several
+    /// of them on one type. Compiling is the test.
+    mod gated {
+        use crate::{assert_size, assert_size_at_least, assert_size_fits};
 
-    TokenStream::from(checker)
+        #[cfg_attr(all(), assert_size(8))]
+        #[cfg_attr(all(), assert_size_fits(16))]
+        #[cfg_attr(all(), assert_size_at_least(4))]
+        #[cfg_attr(any(), assert_size(1))]
+        #[cfg_attr(feature = "collect", assert_size(4 | 8))]
+        #[cfg_attr(not(feature = "collect"), assert_size(2 * 4))]
+        #[repr(C)]
+        struct Header {
+            _tag: u32,
+            _len: u32,
+        }
+    }
+
+    /// Structures removed by `#[cfg]`, above or below the attributes, take their
+    /// checks along: the checks would fail, and the consts named like the ones
+    /// `export` and `lock` generate would collide.
+    mod removed {
+        // Invoked by path, as imports of attributes never expanded are unused.
+        #[cfg(any())]
+        #[crate::assert_size(16, export = ABOVE_SIZE)]
+        #[crate::assert_field_offset(_missing = 4)]
+        struct Above(u8);
+
+        #[crate::assert_size(16, doc, export = BELOW_SIZE)]
+        #[crate::assert_size_fits(0)]
+        #[crate::assert_align(64)]
+        #[cfg(any())]
+        #[repr(C)]
+        struct Below(u8);
+
+        #[cfg_attr(all(), cfg(any()))]
+        #[crate::assert_size(lock)]
+        #[repr(C)]
+        struct Locked(u8);
+
+        const ABOVE_SIZE: usize = 1;
+        const BELOW_SIZE: usize = 2;
+        const LOCKED_SIZE: usize = 3;
+
+        #[test]
+        fn nothing_dangles() {
+            assert_eq!(ABOVE_SIZE + BELOW_SIZE + LOCKED_SIZE, 6);
+        }
+    }
+
+    /// Offset checks over the layouts the `legacy-offset` fallback has to agree
+    /// with `core::mem::offset_of!` on. Compiling with and without the feature
+    /// is the test.
+    mod offsets {
+        use crate::{
+            assert_field_offset, assert_layout_compatible, assert_no_overlap, assert_no_padding,
+            assert_offsets_ascending, AssertLayout,
+        };
+
+        #[assert_field_offset(_tag = 0, _len = 4, _id = 8)]
+        #[assert_offsets_ascending]
+        #[assert_no_overlap(_len, _tag)]
+        #[repr(C)]
+        struct Padded {
+            _tag: u8,
+            _len: u32,
+            _id: u64,
+        }
+
+        #[assert_field_offset(_tag = 0, _len = 1, _id = 5)]
+        #[assert_no_padding]
+        #[repr(C, packed)]
+        struct Packed {
+            _tag: u8,
+            _len: u32,
+            _id: u64,
+        }
+
+        #[assert_field_offset(0 = 0, 1 = 2, 2 = 4)]
+        #[assert_offsets_ascending]
+        #[repr(C)]
+        struct Tuple(u16, (), u32);
+
+        #[assert_no_overlap(_value, _marker)]
+        #[repr(C)]
+        union Marked {
+            _value: u32,
+            _marker: (),
+        }
+
+        #[assert_layout_compatible(Packed, _kind = _tag)]
+        #[repr(C, packed)]
+        struct PackedMirror {
+            _kind: u8,
+            _len: u32,
+            _id: u64,
+        }
+
+        #[assert_layout_compatible(Tuple)]
+        #[repr(C)]
+        struct TupleMirror(i16, (), f32);
+
+        #[derive(AssertLayout)]
+        #[repr(C)]
+        struct Nested {
+            #[layout(offset = 0)]
+            _header: Padded,
+            #[layout(offset = 16)]
+            _trailer: Packed,
+            #[layout(offset = 32)]
+            _tail: Tuple,
+        }
+    }
+
+    /// Visibility of checked structures and of the const recorded by `lock`
+    /// survives the expansion, so they can be re-exported.
+    mod visibility {
+        pub(crate) mod outer {
+            pub mod inner {
+                use crate::assert_size;
+
+                #[assert_size(4)]
+                #[repr(C)]
+                pub struct Public(pub u32);
+
+                #[assert_size(4)]
+                #[repr(C)]
+                pub(crate) struct Crate(pub u32);
+
+                #[assert_size(4)]
+                #[repr(C)]
+                pub(in crate::tests::visibility) struct Scoped(pub u32);
+
+                #[assert_size(4)]
+                #[repr(C)]
+                pub(super) struct Parent(pub u32);
+
+                #[assert_size(lock)]
+                #[repr(C)]
+                pub struct Locked(pub u32);
+            }
+
+            pub(crate) use inner::Crate;
+            pub use inner::{Locked, Public, LOCKED_SIZE};
+
+            pub fn parent() -> u32 {
+                let inner::Parent(parent) = inner::Parent(3);
+                parent
+            }
+        }
+
+        use outer::inner::Scoped;
+
+        #[test]
+        fn visibility_preserved() {
+            let outer::Public(public) = outer::Public(1);
+            let outer::Crate(krate) = outer::Crate(2);
+            let parent = outer::parent();
+            let Scoped(scoped) = Scoped(4);
+            let outer::Locked(locked) = outer::Locked(5);
+            assert_eq!((public, krate, parent, scoped, locked), (1, 2, 3, 4, 5));
+            assert_eq!(outer::LOCKED_SIZE, 4);
+        }
+    }
+
+    mod expansion_order {
+        use crate::{assert_no_padding, assert_size, AssertSized};
+
+        /// Appends a field, like a code generator wrapping the definition.
+        macro_rules! with_checksum {
+            ($(#[$attr:meta])* struct $name:ident { $($field:ident: $ty:ty),* $(,)? }) => {
+                $(#[$attr])*
+                struct $name {
+                    $($field: $ty,)*
+                    _checksum: u32,
+                }
+            };
+        }
+
+        with_checksum! {
+            #[derive(AssertSized)]
+            #[assert_size(8)]
+            #[assert_no_padding]
+            #[repr(C)]
+            struct Frame {
+                _len: u32,
+            }
+        }
+
+        #[derive(AssertSized)]
+        #[assert_size(8)]
+        #[repr(C)]
+        struct Derived {
+            _tag: u32,
+            _len: u32,
+        }
+
+        #[test]
+        fn checks_see_final_type() {
+            assert_eq!(Frame::SIZE, 8);
+            assert_eq!(Derived::SIZE, 8);
+        }
+    }
+
+    /// Expected sizes exported by `#[assert_size(.., export = NAME)]`, used by
+    /// code outside of the checked type.
+    mod export {
+        use crate::assert_size;
+
+        #[assert_size(8, export = HEADER_SIZE)]
+        #[repr(C)]
+        pub struct Header {
+            _tag: u32,
+            _len: u32,
+        }
+
+        #[assert_size(
+            target_pointer_width = "64" => 16,
+            target_pointer_width = "32" => 8,
+            export = SLICE_SIZE,
+        )]
+        #[repr(C)]
+        struct Slice {
+            _ptr: *const u8,
+            _len: usize,
+        }
+
+        fn header_buffer() -> [u8; HEADER_SIZE] {
+            [0; HEADER_SIZE]
+        }
+
+        static SLICES: [[u8; SLICE_SIZE]; 2] = [[0; SLICE_SIZE]; 2];
+
+        struct Buffer<const N: usize>([u8; N]);
+
+        #[test]
+        fn exported_sizes_are_usable() {
+            assert_eq!(header_buffer().len(), 8);
+            assert_eq!(core::mem::size_of_val(&SLICES), 2 * SLICE_SIZE);
+            assert_eq!(Buffer::<HEADER_SIZE>([0; HEADER_SIZE]).0.len(), 8);
+            assert_eq!(HEADER_SIZE, core::mem::size_of::<Header>());
+            assert_eq!(SLICE_SIZE, 2 * core::mem::size_of::<usize>());
+        }
+    }
+
+    /// Checks of `#[assert_size(.., test_only)]`, evaluated in this test build.
+    mod test_only {
+        use crate::assert_size;
+
+        #[assert_size(8, test_only)]
+        #[repr(C)]
+        struct Header {
+            _tag: u32,
+            _len: u32,
+        }
+
+        #[assert_size(8, test_only, export = FRAME_SIZE)]
+        #[repr(C)]
+        pub struct Frame(u64);
+
+        #[test]
+        fn export_is_kept() {
+            assert_eq!(FRAME_SIZE, core::mem::size_of::<Frame>());
+        }
+    }
+
+    /// `#[non_exhaustive]` changes no layout and is kept on the checked type,
+    /// wherever it stands among the attributes.
+    mod non_exhaustive {
+        use crate::assert_size;
+
+        #[assert_size(8)]
+        #[non_exhaustive]
+        #[repr(C)]
+        pub struct Header {
+            pub tag: u32,
+            pub len: u32,
+        }
+
+        #[non_exhaustive]
+        #[assert_size(4)]
+        #[repr(u32)]
+        pub enum Kind {
+            Data,
+        }
+
+        #[test]
+        fn types_are_usable() {
+            // Within the defining crate the attribute does not restrict anything.
+            let header = Header { tag: 1, len: 0 };
+            assert_eq!(header.tag + header.len, 1);
+            assert!(matches!(Kind::Data, Kind::Data));
+        }
+    }
+
+    /// Structures of the default layout, guarded by `allow_default_repr`
+    /// without a warning about the missing `#[repr]`.
+    mod default_repr {
+        #![deny(deprecated)]
+
+        use crate::{assert_size, assert_size_fits};
+
+        #[assert_size(8, allow_default_repr)]
+        struct Span {
+            _start: u32,
+            _end: u32,
+        }
+
+        #[assert_size(
+            target_pointer_width = "64" => 16,
+            target_pointer_width = "32" => 8,
+            allow_default_repr,
+        )]
+        struct Slice {
+            _ptr: *const u8,
+            _len: usize,
+        }
+
+        // Bounds do not depend on the exact layout, so need no flag.
+        #[assert_size_fits(16)]
+        struct Pair {
+            _tag: u8,
+            _value: u64,
+        }
+    }
+
+    /// Bounds of `assert_size_bound!`, evaluated for every instance built.
+    mod size_bound {
+        use crate::assert_size_bound;
+
+        trait Message: Sized {
+            fn encoded_len(&self) -> usize {
+                assert_size_bound!(Self: <= 16);
+                core::mem::size_of::<Self>()
+            }
+        }
+
+        impl Message for u64 {}
+        impl Message for [u32; 4] {}
+
+        fn stored_inline<T>(value: T) -> [T; 1] {
+            assert_size_bound!(T: >= 1, msg = "zero-sized values need no storage");
+            [value]
+        }
+
+        #[test]
+        fn bounds_hold_for_instances() {
+            assert_eq!(0u64.encoded_len(), 8);
+            assert_eq!([0u32; 4].encoded_len(), 16);
+            assert_eq!(stored_inline(7u8), [7]);
+        }
+    }
+
+    /// Deprecated names of the alignment checks keep working.
+    mod deprecated_names {
+        #![allow(deprecated)]
+
+        use crate::{assert_align_words, assert_max_align, assert_min_align};
+
+        #[assert_min_align(4)]
+        #[assert_max_align(8)]
+        #[assert_align_words(1)]
+        #[repr(C)]
+        struct Value {
+            _tag: u64,
+            _payload: u64,
+        }
+    }
+
+    /// Mismatches of `#[assert_size(.., audit)]` are warnings, allowed here.
+    mod audit {
+        #![allow(deprecated)]
+
+        use crate::assert_size;
+
+        #[assert_size(4, audit)]
+        #[repr(C)]
+        struct Header {
+            _tag: u32,
+            _len: u32,
+        }
+
+        #[assert_size(8, audit)]
+        #[repr(C)]
+        struct Frame(u64);
+
+        #[test]
+        fn mismatches_compile() {
+            assert_eq!(core::mem::size_of::<Header>(), 8);
+            assert_eq!(core::mem::size_of::<Frame>(), 8);
+        }
+    }
+
+    /// Raw identifiers are kept in the generated code, generated names are
+    /// derived from them without their prefix, e.g. `MATCH_SIZE`.
+    mod raw_identifiers {
+        use crate::{assert_field_offset, assert_size, assert_size_fits};
+
+        #[allow(non_camel_case_types)]
+        #[assert_size(8)]
+        #[assert_size_fits(8)]
+        #[assert_field_offset(r#type = 0, r#len = 4)]
+        #[repr(C)]
+        struct r#struct {
+            r#type: u32,
+            r#len: u32,
+        }
+
+        #[allow(non_camel_case_types)]
+        #[assert_size(lock)]
+        #[repr(C)]
+        pub struct r#match(u16);
+
+        #[test]
+        fn raw_identifiers_are_checked() {
+            let value = r#struct {
+                r#type: 1,
+                r#len: 2,
+            };
+            assert_eq!(value.r#type + value.r#len, 3);
+            assert_eq!(MATCH_SIZE, 2);
+        }
+    }
+
+    /// Generates `#[test]`s `header_assert_size_8` and `header_assert_size_fits_16`
+    /// with the `runtime-assert` feature.
+    #[cfg(feature = "runtime-assert")]
+    mod runtime {
+        use crate::{assert_size, assert_size_fits};
+
+        #[assert_size(8)]
+        #[assert_size_fits(16, msg = "must fit in {a} budget")]
+        #[repr(C)]
+        struct Header {
+            _tag: u32,
+            _len: u32,
+        }
+
+        #[assert_size(8)]
+        #[assert_size(8, msg = "word must stay a single word")]
+        #[assert_size_fits(16)]
+        #[assert_size_fits(16)]
+        #[repr(C)]
+        struct Word(u64);
+
+        #[test]
+        fn stacked_runtime_checks_are_distinct() {
+            word_assert_size_8_stacked_1();
+            word_assert_size_8();
+            word_assert_size_fits_16_stacked_1();
+            word_assert_size_fits_16();
+        }
+
+        /// Checks of items in a function body build without the warning allowed
+        /// on the function, even though their tests are never run.
+        #[allow(unnameable_test_items)]
+        #[test]
+        fn inner_items_are_checked_at_compile_time() {
+            #[assert_size(8)]
+            #[repr(C)]
+            struct Inner(u64);
+
+            assert_eq!(core::mem::size_of::<Inner>(), 8);
+        }
+
+        /// The only test registering a handler, as it is global.
+        #[test]
+        fn mismatch_reaches_handler() {
+            use crate::{report_mismatch, set_mismatch_handler};
+            use std::sync::Mutex;
+
+            static REPORTED: Mutex<Vec<(&str, usize, usize)>> = Mutex::new(Vec::new());
+
+            let panic = std::panic::catch_unwind(|| report_mismatch("a::Header", 8, 12));
+            let panic = panic.unwrap_err();
+            assert_eq!(
+                panic.downcast_ref::<String>().unwrap(),
+                "size of 'a::Header' is not 8 bytes (actual size: 12 bytes)"
+            );
+
+            set_mismatch_handler(|type_name, expected, actual| {
+                REPORTED.lock().unwrap().push((type_name, expected, actual));
+            });
+            report_mismatch("a::Header", 8, 12);
+            report_mismatch("a::Frame", 16, 24);
+            assert_eq!(
+                *REPORTED.lock().unwrap(),
+                [("a::Header", 8, 12), ("a::Frame", 16, 24)]
+            );
+        }
+    }
+
+    /// Generates the `#[test]`s `packet_assert_size_16`, and for equal sizes
+    /// stacked on one type `frame_assert_size_8_stacked_1` and
+    /// `frame_assert_size_8`, without the `runtime-assert` feature, called here
+    /// to confirm they exist.
+    mod paranoid {
+        use crate::assert_size;
+
+        #[assert_size(16, paranoid)]
+        #[repr(C)]
+        struct Packet {
+            _id: u64,
+            _len: u32,
+        }
+
+        #[assert_size(8, paranoid)]
+        #[assert_size(8, paranoid, msg = "frame must stay a single word")]
+        #[repr(C)]
+        struct Frame(u64);
+
+        #[cfg(debug_assertions)]
+        #[test]
+        fn runtime_check_is_generated() {
+            packet_assert_size_16();
+        }
+
+        #[cfg(debug_assertions)]
+        #[test]
+        fn stacked_runtime_checks_are_distinct() {
+            frame_assert_size_8_stacked_1();
+            frame_assert_size_8();
+        }
+    }
+
+    mod assoc_const {
+        use crate::assert_size_of_val;
+
+        trait Table {
+            const TABLE: &'static [u16];
+            const KEYS: [u8; 4];
+        }
+
+        struct Crc16;
+
+        impl Table for Crc16 {
+            const TABLE: &'static [u16] = &[0x0000, 0x1021, 0x2042];
+            const KEYS: [u8; 4] = *b"keys";
+        }
+
+        impl Crc16 {
+            const WIDE: [u64; 2] = [1, 2];
+        }
+
+        assert_size_of_val!(*<Crc16 as Table>::TABLE, 6);
+        assert_size_of_val!(<Crc16 as Table>::KEYS, 4);
+        assert_size_of_val!(Crc16::WIDE, 16);
+
+        #[test]
+        fn associated_consts_are_checked() {
+            assert_eq!(core::mem::size_of_val(<Crc16 as Table>::TABLE), 6);
+            assert_eq!(core::mem::size_of_val(&Crc16::WIDE), 16);
+        }
+    }
+
+    // Items are visible regardless of their order, so the checks may refer to
+    // consts declared below the annotated types.
+    mod forward_const {
+        use crate::{assert_align, assert_field_offset, assert_size, assert_size_fits};
+
+        #[assert_size(HEADER_LEN, export = HEADER_SIZE)]
+        #[assert_size_fits(MAX_LEN)]
+        #[assert_align(HEADER_ALIGN)]
+        #[assert_field_offset(_len = LEN_OFFSET)]
+        #[repr(C)]
+        struct Header {
+            _tag: u32,
+            _len: u32,
+        }
+
+        const HEADER_LEN: usize = 8;
+        const MAX_LEN: usize = 2 * HEADER_LEN;
+        const HEADER_ALIGN: usize = core::mem::align_of::<u32>();
+        const LEN_OFFSET: usize = 4;
+
+        // The tests of `runtime-assert` are not run for items in a function.
+        #[allow(unnameable_test_items)]
+        fn word_len() -> usize {
+            #[assert_size(WORD_LEN)]
+            #[repr(C)]
+            struct Word(u32);
+
+            const WORD_LEN: usize = 4;
+
+            core::mem::size_of_val(&Word(7))
+        }
+
+        #[test]
+        fn consts_below_are_used() {
+            assert_eq!(HEADER_SIZE, HEADER_LEN);
+            assert_eq!(core::mem::size_of::<Header>(), 8);
+            assert_eq!(word_len(), 4);
+        }
+    }
+
+    // Stand-in for `#[bitfield]` of `modular-bitfield`, which replaces the fields
+    // with a byte array of their bits and copies the other attributes over.
+    mod bitfield {
+        use crate::{assert_align, assert_size, assert_type_size};
+
+        macro_rules! bitfield {
+            ($(#[$attr:meta])* struct $name:ident { $($field:ident: $bits:literal),* $(,)? }) => {
+                $(#[$attr])*
+                #[repr(transparent)]
+                struct $name {
+                    _bytes: [u8; (0usize $(+ $bits)*).div_ceil(8)],
+                }
+            };
+        }
+
+        bitfield! {
+            #[assert_size(1)]
+            #[assert_align(1)]
+            struct Flags {
+                ready: 1,
+                mode: 3,
+                level: 4,
+            }
+        }
+
+        bitfield! {
+            struct Status {
+                code: 12,
+                retry: 4,
+                id: 16,
+            }
+        }
+
+        assert_type_size!(Status, 4);
+
+        #[test]
+        fn replaced_types_are_measured() {
+            assert_eq!(core::mem::size_of::<Flags>(), 1);
+            assert_eq!(core::mem::size_of::<Status>(), 4);
+        }
+    }
+
+    #[cfg(feature = "collect")]
+    mod collect {
+        use crate::{assert_size, asserted_sizes};
+
+        #[assert_size(8)]
+        #[repr(C)]
+        struct Header {
+            _tag: u32,
+            _len: u32,
+        }
+
+        #[assert_size(target_pointer_width = "64" => 16, target_pointer_width = "32" => 8)]
+        #[repr(C)]
+        struct Slice {
+            _ptr: *const u8,
+            _len: usize,
+        }
+
+        #[cfg_attr(all(), assert_size(4))]
+        #[repr(C)]
+        struct Enabled(u32);
+
+        // No check and no registration is generated, so nothing refers to it.
+        #[allow(dead_code)]
+        #[cfg_attr(any(), assert_size(4))]
+        #[repr(C)]
+        struct Disabled(u32);
+
+        #[test]
+        fn collects_checked_sizes() {
+            let mut entries = asserted_sizes()
+                .filter(|entry| entry.type_name.starts_with(module_path!()))
+                .map(|entry| (entry.type_name, entry.size))
+                .collect::<Vec<_>>();
+            entries.sort();
+            assert_eq!(
+                entries,
+                [
+                    ("pakr_assert_size::tests::collect::Enabled", 4),
+                    ("pakr_assert_size::tests::collect::Header", 8),
+                    (
+                        "pakr_assert_size::tests::collect::Slice",
+                        2 * core::mem::size_of::<usize>()
+                    ),
+                ]
+            );
+        }
+    }
+
+    #[cfg(feature = "json-report")]
+    mod json_report {
+        use crate::{
+            assert_align, assert_size, assert_size_at_least, assert_size_fits, layout_report,
+        };
+
+        #[assert_size(8)]
+        #[assert_align(4)]
+        #[repr(C)]
+        struct Header {
+            _tag: u32,
+            _len: u32,
+        }
+
+        #[assert_size_fits(32)]
+        #[assert_size_at_least(2)]
+        #[repr(C)]
+        struct Frame {
+            _header: Header,
+            _body: [u8; 8],
+        }
+
+        #[assert_size(4 | 8)]
+        #[repr(C)]
+        struct Word(usize);
+
+        #[test]
+        fn report_parses_back() {
+            let report = serde_json::from_str::<serde_json::Value>(&layout_report()).unwrap();
+            let entries = report
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|entry| entry["type"].as_str().unwrap().starts_with(module_path!()))
+                .map(|entry| {
+                    let expected = entry["expected"].as_array().unwrap();
+                    (
+                        entry["type"].as_str().unwrap(),
+                        entry["kind"].as_str().unwrap(),
+                        expected
+                            .iter()
+                            .map(|value| value.as_u64().unwrap())
+                            .collect::<Vec<_>>(),
+                        entry["actual"].as_u64().unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let word = core::mem::size_of::<usize>() as u64;
+            assert_eq!(
+                entries,
+                [
+                    (
+                        "pakr_assert_size::tests::json_report::Frame",
+                        "assert_size_at_least",
+                        vec![2],
+                        16
+                    ),
+                    (
+                        "pakr_assert_size::tests::json_report::Frame",
+                        "assert_size_fits",
+                        vec![32],
+                        16
+                    ),
+                    (
+                        "pakr_assert_size::tests::json_report::Header",
+                        "assert_align",
+                        vec![4],
+                        4
+                    ),
+                    (
+                        "pakr_assert_size::tests::json_report::Header",
+                        "assert_size",
+                        vec![8],
+                        8
+                    ),
+                    (
+                        "pakr_assert_size::tests::json_report::Word",
+                        "assert_size",
+                        vec![4, 8],
+                        word
+                    ),
+                ]
+            );
+        }
+
+        #[test]
+        fn strings_are_escaped() {
+            let mut out = String::new();
+            crate::write_json_str(&mut out, "a\"b\\c\n\u{1}");
+            assert_eq!(out, r#""a\"b\\c\n\u0001""#);
+            assert_eq!(
+                serde_json::from_str::<String>(&out).unwrap(),
+                "a\"b\\c\n\u{1}"
+            );
+        }
+    }
 }
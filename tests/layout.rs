@@ -0,0 +1,139 @@
+//! `#[assert_size]` and `#[assert_size_fits]` applied to structures of every
+//! representation. Compiling is the test; the `#[test]`s only confirm that the
+//! checked sizes are the real ones.
+
+use core::mem::size_of;
+use pakr_assert_size::*;
+
+#[assert_size(16)]
+#[assert_size_fits(16)]
+#[repr(C)]
+struct C {
+    tag: u8,
+    len: u32,
+    id: u64,
+}
+
+#[assert_size(13)]
+#[assert_size_fits(16)]
+#[repr(C, packed)]
+struct Packed {
+    tag: u8,
+    len: u32,
+    id: u64,
+}
+
+#[assert_size(10)]
+#[assert_size_fits(10)]
+#[repr(C, packed(2))]
+struct Packed2 {
+    tag: u8,
+    id: u64,
+}
+
+#[assert_size(64)]
+#[assert_size_fits(64)]
+#[repr(C, align(64))]
+struct CacheLine {
+    counter: u64,
+}
+
+#[assert_size(32)]
+#[assert_size_fits(128)]
+#[repr(align(16))]
+struct Aligned {
+    data: [u8; 17],
+}
+
+#[assert_size(8)]
+#[assert_size_fits(8)]
+#[repr(transparent)]
+struct Transparent(u64);
+
+#[assert_size(4)]
+#[assert_size_fits(8)]
+#[repr(transparent)]
+struct TransparentMarker(u32, core::marker::PhantomData<*const u8>);
+
+// The default representation may reorder fields, but not pad more than needed.
+#[assert_size(16, allow_default_repr)]
+#[assert_size_fits(24)]
+struct Default {
+    tag: u8,
+    id: u64,
+    len: u32,
+}
+
+#[assert_size(4)]
+#[assert_size_fits(4)]
+#[repr(u32)]
+enum Discriminant {
+    Zero,
+    One,
+}
+
+#[assert_size(8)]
+#[assert_size_fits(8)]
+#[repr(C)]
+union Word {
+    int: u64,
+    bytes: [u8; 8],
+}
+
+#[test]
+fn checked_sizes_are_real() {
+    assert_eq!(size_of::<C>(), 16);
+    assert_eq!(size_of::<Packed>(), 13);
+    assert_eq!(size_of::<Packed2>(), 10);
+    assert_eq!(size_of::<CacheLine>(), 64);
+    assert_eq!(size_of::<Aligned>(), 32);
+    assert_eq!(size_of::<Transparent>(), 8);
+    assert_eq!(size_of::<TransparentMarker>(), 4);
+    assert_eq!(size_of::<Default>(), 16);
+    assert_eq!(size_of::<Discriminant>(), 4);
+    assert_eq!(size_of::<Word>(), 8);
+}
+
+#[test]
+fn checked_types_keep_their_fields() {
+    let c = C {
+        tag: 1,
+        len: 2,
+        id: 3,
+    };
+    let packed = Packed {
+        tag: c.tag,
+        len: c.len,
+        id: c.id,
+    };
+    let packed2 = Packed2 { tag: 1, id: 2 };
+    let line = CacheLine { counter: 4 };
+    let aligned = Aligned { data: [0; 17] };
+    let default = Default {
+        tag: 5,
+        id: 6,
+        len: 7,
+    };
+    let word = Word { int: 8 };
+
+    assert_eq!({ packed.id }, 3);
+    assert_eq!({ packed2.id } + u64::from(packed2.tag), 3);
+    assert_eq!(line.counter + aligned.data.len() as u64, 21);
+    assert_eq!(Transparent(9).0, 9);
+    assert_eq!(TransparentMarker(10, core::marker::PhantomData).0, 10);
+    assert_eq!(
+        default.id + u64::from(default.tag) + u64::from(default.len),
+        18
+    );
+    assert_eq!(Discriminant::One as u32 - Discriminant::Zero as u32, 1);
+    assert_eq!(unsafe { word.bytes }.len(), 8);
+}
+
+/// Sizes of pointers only match the expectation on 64-bit targets, so there
+/// only the failures are known.
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn pointer_width_failures() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/layout/pointer_width_64.rs");
+}
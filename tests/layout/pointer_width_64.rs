@@ -0,0 +1,16 @@
+use pakr_assert_size::*;
+
+// Checked for 32-bit targets, but compiled for a 64-bit one.
+#[assert_size(8)]
+#[assert_size_fits(8)]
+#[repr(C)]
+struct Slice {
+    ptr: *const u8,
+    len: usize,
+}
+
+#[assert_size_fits(4)]
+#[repr(transparent)]
+struct Handle(*mut u8);
+
+fn main() {}
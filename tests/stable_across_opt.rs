@@ -0,0 +1,83 @@
+//! Sizes marked `stable_across_opt`. The checks are evaluated by rustc in the
+//! profile the test is built with, so run it in both, `cargo test --test
+//! stable_across_opt` and `cargo test --release --test stable_across_opt`; the
+//! `#[test]`s confirm the checked sizes and name the profile they ran in.
+
+use core::mem::size_of;
+use pakr_assert_size::*;
+
+#[assert_size(16, stable_across_opt)]
+#[repr(C)]
+struct Command {
+    id: u32,
+    flags: u32,
+    deadline: u64,
+}
+
+#[assert_size(
+    target_pointer_width = "64" => 16,
+    target_pointer_width = "32" => 8,
+    stable_across_opt,
+)]
+#[repr(C)]
+struct Buffer {
+    ptr: *const u8,
+    len: usize,
+}
+
+// Niches are filled the same in every profile.
+#[assert_size(size_of::<usize>(), stable_across_opt)]
+#[repr(C)]
+struct Handle {
+    ptr: Option<&'static u64>,
+}
+
+#[assert_size(2, stable_across_opt, allow_default_repr)]
+enum State {
+    Idle,
+    Busy(u8),
+    Failed(bool),
+}
+
+#[test]
+fn sizes_hold_in_this_profile() {
+    let profile = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    assert_eq!(size_of::<Command>(), 16, "{}", profile);
+    assert_eq!(size_of::<Buffer>(), 2 * size_of::<usize>(), "{}", profile);
+    assert_eq!(size_of::<Handle>(), size_of::<usize>(), "{}", profile);
+    assert_eq!(size_of::<State>(), 2, "{}", profile);
+}
+
+#[test]
+fn checked_types_keep_their_fields() {
+    static DEADLINE: u64 = 100;
+    let command = Command {
+        id: 1,
+        flags: 2,
+        deadline: DEADLINE,
+    };
+    let buffer = Buffer {
+        ptr: core::ptr::null(),
+        len: 0,
+    };
+    let handle = Handle {
+        ptr: Some(&DEADLINE),
+    };
+    let states = [
+        State::Idle,
+        State::Busy(command.flags as u8),
+        State::Failed(true),
+    ];
+    assert_eq!(command.id + command.flags, 3);
+    assert!(buffer.ptr.is_null() && buffer.len == 0);
+    assert_eq!(handle.ptr.copied(), Some(command.deadline));
+    assert!(states.iter().all(|state| match state {
+        State::Idle => true,
+        State::Busy(flags) => *flags == 2,
+        State::Failed(failed) => *failed,
+    }));
+}
@@ -0,0 +1,76 @@
+//! Many layout attributes stacked on one structure, and on several structures
+//! of one module. Every check is an anonymous `const _`, which never collides,
+//! and the named items some of them generate are named after the structure, so
+//! compiling is the test; the `#[test]` only confirms the generated consts.
+
+use core::mem::size_of;
+use pakr_assert_size::*;
+
+#[assert_size(16, export = FRAME_SIZE)]
+#[assert_size_fits(16)]
+#[assert_size_at_least(8)]
+#[assert_align(8)]
+#[assert_align_at_least(4)]
+#[assert_natural_align]
+#[assert_no_padding]
+#[assert_offsets_ascending]
+#[assert_field_offset(tag = 0, len = 4, id = 8)]
+#[assert_no_overlap(len, id)]
+#[assert_layout(size = 16, align = 8)]
+#[assert_repr("C")]
+#[derive(AssertLayout)]
+#[repr(C)]
+struct Frame {
+    #[layout(offset = 0, size = 4)]
+    tag: u32,
+    len: u32,
+    id: u64,
+}
+
+#[assert_size(lock)]
+#[assert_align(4)]
+#[assert_no_padding]
+#[repr(C)]
+struct Header {
+    tag: u32,
+    len: u32,
+}
+
+#[assert_size(lock)]
+#[assert_align(4)]
+#[assert_no_padding]
+#[repr(C)]
+struct Trailer {
+    crc: u32,
+}
+
+#[assert_size(4)]
+#[assert_size_fits(4)]
+#[assert_size_at_least(4)]
+#[assert_align(4)]
+#[assert_size_eq_discriminant_type]
+#[repr(u32)]
+enum Message {
+    Ping = 1,
+    Pong = 2,
+}
+
+#[test]
+fn generated_consts_are_distinct() {
+    assert_eq!(FRAME_SIZE, size_of::<Frame>());
+    assert_eq!(HEADER_SIZE, size_of::<Header>());
+    assert_eq!(TRAILER_SIZE, size_of::<Trailer>());
+    let frame = Frame {
+        tag: 1,
+        len: 2,
+        id: 3,
+    };
+    let header = Header { tag: 4, len: 5 };
+    let trailer = Trailer { crc: 6 };
+    assert_eq!(
+        frame.tag + frame.len + header.tag + header.len + trailer.crc,
+        18
+    );
+    assert_eq!(frame.id, 3);
+    assert_eq!(Message::Ping as u32 + Message::Pong as u32, 3);
+}
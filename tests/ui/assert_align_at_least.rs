@@ -0,0 +1,9 @@
+use pakr_assert_size::*;
+
+#[assert_align_at_least(64)]
+#[repr(C)]
+struct C {
+    counter: u64,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use pakr_assert_size::*;
+
+#[assert_align(target_pointer_width = "64" => 4, target_pointer_width = "32" => 4)]
+#[repr(C)]
+struct Node {
+    next: *const Node,
+    tag: u8,
+}
+
+#[assert_align(not(target_pointer_width = "64") => 64)]
+#[repr(C)]
+struct Other {
+    next: *const Other,
+}
+
+fn main() {}
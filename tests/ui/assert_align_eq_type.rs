@@ -0,0 +1,7 @@
+use pakr_assert_size::*;
+
+#[assert_align_eq_type(u16)]
+#[repr(C)]
+struct Bytes([u8; 4]);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use pakr_assert_size::*;
+
+#[assert_align_fits(8)]
+#[repr(C, align(16))]
+struct C {
+    field1: u64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use pakr_assert_size::*;
+
+#[assert_align_multiple_of(64)]
+#[repr(C, align(8))]
+struct DmaBuffer {
+    bytes: [u8; 256],
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use pakr_assert_size::*;
+
+#[assert_align(0)]
+#[repr(C)]
+struct Zero {
+    tag: u32,
+}
+
+#[assert_align(12)]
+#[repr(C)]
+struct Twelve {
+    tag: u32,
+}
+
+#[assert_align(unix => 4, windows => 6)]
+#[repr(C)]
+struct Arm {
+    tag: u32,
+}
+
+fn main() {}
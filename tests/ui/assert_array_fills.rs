@@ -0,0 +1,19 @@
+use pakr_assert_size::*;
+
+#[assert_array_fills(0 = 2, 1 = 8)]
+#[repr(C)]
+struct Short([u8; 2], [u16; 3]);
+
+#[assert_array_fills(words = 16)]
+#[repr(C)]
+struct Block {
+    words: [u32; 3],
+}
+
+#[assert_array_fills(words = 16)]
+#[repr(C)]
+struct Scalar {
+    words: u128,
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use pakr_assert_size::*;
+
+#[assert_array_size(count = 4, total = 52)]
+#[repr(C)]
+struct Record {
+    id: u64,
+    tag: u8,
+}
+
+#[assert_array_size(total = 24, count = 2 * 4)]
+#[repr(C)]
+struct Rgb {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+#[assert_array_size(count = 4)]
+struct NoTotal(u32);
+
+#[assert_array_size(count = 4, count = 8, total = 16)]
+struct Duplicate(u32);
+
+#[assert_array_size(count = 4, stride = 4)]
+struct Unknown(u32);
+
+fn main() {}
@@ -0,0 +1,22 @@
+use pakr_assert_size::*;
+
+#[assert_discriminant_overhead(4)]
+enum Shape {
+    Circle(f32),
+    Rect(f32, f32),
+}
+
+#[assert_discriminant_overhead(1)]
+#[repr(u64)]
+enum Tagged {
+    Byte(u8),
+    Empty,
+}
+
+#[assert_discriminant_overhead(0, msg = "slots must stay pointer-sized")]
+enum Slot {
+    Empty,
+    Full(u64),
+}
+
+fn main() {}
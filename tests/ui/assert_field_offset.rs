@@ -0,0 +1,10 @@
+use pakr_assert_size::*;
+
+#[assert_field_offset(tag = 0, len = 2)]
+#[repr(C)]
+struct C {
+    tag: u16,
+    len: u32,
+}
+
+fn main() {}
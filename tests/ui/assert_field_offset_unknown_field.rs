@@ -0,0 +1,10 @@
+use pakr_assert_size::*;
+
+#[assert_field_offset(length = 4)]
+#[repr(C)]
+struct D {
+    tag: u16,
+    len: u32,
+}
+
+fn main() {}
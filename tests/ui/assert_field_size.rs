@@ -0,0 +1,10 @@
+use pakr_assert_size::*;
+
+#[assert_field_size(header = 8, data = 32)]
+#[repr(C)]
+struct Block {
+    header: u64,
+    data: [u8; 16],
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use pakr_assert_size::*;
+
+type Page = [u8; 4096];
+
+#[repr(C)]
+struct Superblock {
+    magic: u64,
+    blocks: [u32; 1024],
+}
+
+#[repr(C)]
+struct Inode {
+    mode: u32,
+    size: u64,
+}
+
+assert_fits!(Superblock, Page);
+assert_fits!(Inode, Page,);
+
+fn main() {
+    assert_fits!(Inode, [u8; 8]);
+}
@@ -0,0 +1,15 @@
+use pakr_assert_size::*;
+
+#[assert_fits_in([u8; 16])]
+#[repr(C)]
+struct Aligned {
+    value: [u16; 2],
+}
+
+#[assert_fits_in([u16; 2])]
+#[repr(C)]
+struct Big {
+    value: [u8; 8],
+}
+
+fn main() {}
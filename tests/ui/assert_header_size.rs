@@ -0,0 +1,19 @@
+use pakr_assert_size::*;
+
+#[assert_header_size(6)]
+#[repr(C)]
+struct Packet {
+    kind: u16,
+    len: u64,
+    data: [u8; 0],
+}
+
+#[assert_header_size(4)]
+#[repr(C)]
+struct Tuple(u16, u16, [u32; 0]);
+
+#[assert_header_size(0)]
+#[repr(C)]
+struct Empty;
+
+fn main() {}
@@ -0,0 +1,13 @@
+use pakr_assert_size::*;
+
+#[derive(AssertLayout)]
+#[layout(size = 16)]
+#[repr(C)]
+struct Header {
+    #[layout(offset = 0, size = 2)]
+    tag: u8,
+    #[layout(offset = 1)]
+    len: u32,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use pakr_assert_size::*;
+
+#[assert_layout(size = 16, align = 16)]
+#[repr(C)]
+struct Header {
+    tag: u32,
+    id: u64,
+}
+
+#[assert_layout(size = 16, stride = 16)]
+#[repr(C)]
+struct Unknown {
+    id: u64,
+}
+
+#[assert_layout(size = 16, size = 8)]
+#[repr(C)]
+struct Duplicate {
+    id: u64,
+}
+
+fn main() {}
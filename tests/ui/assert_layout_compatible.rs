@@ -0,0 +1,39 @@
+use pakr_assert_size::*;
+
+#[repr(C)]
+struct Binding {
+    flags: u16,
+    kind: u16,
+    len: u32,
+}
+
+#[assert_layout_compatible(Binding)]
+#[repr(C)]
+struct Swapped {
+    kind: u16,
+    flags: u16,
+    len: u32,
+}
+
+#[repr(C)]
+struct Wide {
+    kind: u32,
+    len: u32,
+}
+
+#[assert_layout_compatible(Wide)]
+#[repr(C)]
+struct Narrow {
+    kind: u16,
+    len: u32,
+}
+
+#[assert_layout_compatible(Binding, tag = kind)]
+#[repr(C)]
+struct Renamed {
+    kind: u16,
+    flags: u16,
+    len: u32,
+}
+
+fn main() {}
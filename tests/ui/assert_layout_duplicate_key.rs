@@ -0,0 +1,11 @@
+use pakr_assert_size::*;
+
+#[derive(AssertLayout)]
+#[layout(size = 8)]
+#[layout(size = 8)]
+#[repr(C)]
+struct Header {
+    tag: u64,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use pakr_assert_size::*;
+
+#[derive(AssertLayout)]
+#[repr(C)]
+struct Header {
+    #[layout(offset = 0, align = 1)]
+    tag: u8,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use pakr_assert_size::*;
+
+#[assert_layout(@)]
+struct A(u8);
+
+#[assert_layout(16)]
+struct B(u8);
+
+#[assert_layout()]
+struct C(u8);
+
+#[assert_layout(size 16)]
+struct D(u8);
+
+fn main() {}
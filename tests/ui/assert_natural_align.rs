@@ -0,0 +1,23 @@
+use pakr_assert_size::*;
+
+#[assert_natural_align]
+#[repr(C, align(64))]
+struct OverAligned {
+    tag: u8,
+    id: u64,
+}
+
+#[assert_natural_align]
+#[repr(C, packed)]
+struct Packed {
+    tag: u8,
+    id: u64,
+}
+
+#[assert_natural_align(8)]
+#[repr(C)]
+struct WithArgument {
+    id: u64,
+}
+
+fn main() {}
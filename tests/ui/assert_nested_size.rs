@@ -0,0 +1,23 @@
+use pakr_assert_size::*;
+
+#[repr(C)]
+struct Body {
+    len: u32,
+    payload: [u8; 32],
+}
+
+#[assert_nested_size(body.len = 4, body.payload = 16)]
+#[assert_field_offset(body.payload = 8)]
+#[repr(C)]
+struct Frame {
+    tag: u64,
+    body: Body,
+}
+
+#[assert_nested_size(body.payload.len = 4)]
+#[repr(C)]
+struct Deep {
+    body: Body,
+}
+
+fn main() {}
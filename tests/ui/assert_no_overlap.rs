@@ -0,0 +1,28 @@
+use pakr_assert_size::*;
+
+#[assert_no_overlap(word, bytes)]
+#[repr(C)]
+union Overlapping {
+    word: u32,
+    bytes: [u8; 4],
+}
+
+#[assert_no_overlap(tag, tag)]
+#[repr(C)]
+struct Same {
+    tag: u16,
+}
+
+#[assert_no_overlap(tag, length)]
+#[repr(C)]
+struct Missing {
+    tag: u16,
+    len: u32,
+}
+
+#[assert_no_overlap(0, 1)]
+enum NotStruct {
+    A,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use pakr_assert_size::*;
+
+#[assert_no_padding]
+#[repr(C)]
+struct C {
+    field1: u64,
+    field2: u8,
+}
+
+#[assert_no_padding]
+#[repr(C)]
+struct Tagged(u8, u32);
+
+fn main() {}
@@ -0,0 +1,12 @@
+use pakr_assert_size::*;
+
+#[assert_offsets_ascending]
+struct Reordered {
+    tag: u8,
+    len: u32,
+}
+
+fn main() {
+    let r = Reordered { tag: 0, len: 0 };
+    let _ = (r.tag, r.len);
+}
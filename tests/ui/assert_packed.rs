@@ -0,0 +1,19 @@
+use pakr_assert_size::*;
+
+#[assert_packed(8)]
+#[repr(C)]
+struct Unpacked {
+    len: u16,
+    flags: u16,
+    id: u32,
+}
+
+#[assert_packed(8)]
+#[repr(C, packed)]
+struct Packed {
+    tag: u8,
+    len: u16,
+    id: u32,
+}
+
+fn main() {}
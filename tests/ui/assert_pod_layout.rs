@@ -0,0 +1,27 @@
+use pakr_assert_size::*;
+
+#[assert_pod_layout(16)]
+#[repr(C)]
+struct Padded {
+    tag: u8,
+    len: u32,
+    id: u64,
+}
+
+#[assert_pod_layout(16)]
+#[repr(C, packed)]
+struct Short {
+    tag: u8,
+    len: u32,
+    id: u64,
+}
+
+#[assert_pod_layout(13)]
+#[repr(C, packed)]
+struct Record {
+    tag: u8,
+    len: u32,
+    id: u64,
+}
+
+fn main() {}
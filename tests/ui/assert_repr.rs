@@ -0,0 +1,29 @@
+use pakr_assert_size::*;
+
+#[assert_repr("C", "packed")]
+#[repr(C)]
+struct Header {
+    tag: u8,
+    len: u32,
+}
+
+#[assert_repr("C", "packed")]
+#[repr(C)]
+#[repr(packed(2))]
+struct Pair {
+    tag: u8,
+    len: u32,
+}
+
+#[assert_repr("align(8)")]
+#[repr(C, align(16))]
+struct Block([u8; 64]);
+
+#[assert_repr("C", "align 16")]
+#[repr(C)]
+struct Invalid(u8);
+
+fn main() {
+    let _ = Header { tag: 0, len: 0 };
+    let _ = Pair { tag: 0, len: 0 };
+}
@@ -0,0 +1,14 @@
+use core::ptr::NonNull;
+use pakr_assert_size::*;
+
+#[assert_result_niche(u32)]
+struct Buffer {
+    data: NonNull<u8>,
+}
+
+#[assert_result_niche(())]
+struct Index {
+    value: u32,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use pakr_assert_size::*;
+
+#[repr(C, align(8))]
+struct Word([u8; 8]);
+
+#[assert_same_layout(Word)]
+#[repr(C)]
+struct Handle([u8; 8]);
+
+fn main() {
+    let _ = Word([0; 8]).0;
+}
@@ -0,0 +1,28 @@
+use pakr_assert_size::*;
+
+#[assert_size(16, abi = "1.2")]
+#[repr(C)]
+struct Header {
+    tag: u32,
+    len: u32,
+}
+
+#[assert_size(
+    target_pointer_width = "64" => 8,
+    target_pointer_width = "32" => 4,
+    abi = "2.0",
+)]
+#[repr(C)]
+struct Slice {
+    ptr: *const u8,
+    len: usize,
+}
+
+#[assert_size(8, abi = 1.2)]
+#[repr(C)]
+struct Unquoted {
+    tag: u32,
+    len: u32,
+}
+
+fn main() {}
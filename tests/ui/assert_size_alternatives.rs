@@ -0,0 +1,13 @@
+use pakr_assert_size::*;
+
+#[assert_size(8 | 16 | 32)]
+#[repr(C)]
+struct Handle {
+    id: [u32; 3],
+}
+
+#[assert_size(4 | 8, msg = "must be a word")]
+#[repr(C)]
+struct Word([u8; 2]);
+
+fn main() {}
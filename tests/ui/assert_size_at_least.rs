@@ -0,0 +1,10 @@
+use pakr_assert_size::*;
+
+#[assert_size_at_least(24)]
+#[repr(C, packed)]
+struct C {
+    field1: u64,
+    field2: u64,
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use pakr_assert_size::*;
+
+// Audited mismatches are warnings, the build fails on the other one only.
+#[assert_size(4, audit)]
+#[repr(C)]
+struct Audited {
+    tag: u32,
+    len: u32,
+}
+
+#[assert_size(8, audit)]
+#[repr(C)]
+struct Passing {
+    tag: u32,
+    len: u32,
+}
+
+#[assert_size(4)]
+#[repr(C)]
+struct Checked {
+    tag: u32,
+    len: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use pakr_assert_size::*;
+
+#[assert_size_between(32, 8)]
+#[repr(C, packed)]
+struct F {
+    field1: u64,
+    field2: u64,
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use pakr_assert_size::*;
+
+#[assert_size_between(
+    4,
+    8,
+)]
+#[repr(C, packed)]
+struct Large {
+    field1: u64,
+    field2: u64,
+}
+
+#[assert_size_between(
+    24,
+    32,
+)]
+#[repr(C, packed)]
+struct Small {
+    field1: u64,
+    field2: u64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use pakr_assert_size::*;
+
+trait Unsized {
+    fn check(&self) {
+        assert_size_bound!(Self: == 8);
+    }
+}
+
+fn main() {}
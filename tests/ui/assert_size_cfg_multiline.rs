@@ -0,0 +1,23 @@
+use pakr_assert_size::*;
+
+#[assert_size(
+    target_pointer_width = "64" => 8,
+    target_pointer_width = "32" => 8,
+)]
+#[repr(C)]
+struct Slice {
+    ptr: *const u8,
+    len: usize,
+}
+
+#[assert_align(
+    target_pointer_width = "64" => 4,
+    target_pointer_width = "32" => 4,
+)]
+#[repr(C)]
+struct Node {
+    next: *const Node,
+    tag: u8,
+}
+
+fn main() {}
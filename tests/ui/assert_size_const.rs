@@ -0,0 +1,13 @@
+use pakr_assert_size::*;
+
+mod proto {
+    pub const HEADER_LEN: usize = 20;
+}
+
+#[assert_size(proto::HEADER_LEN)]
+#[repr(C)]
+struct Header {
+    bytes: [u8; 24],
+}
+
+fn main() {}
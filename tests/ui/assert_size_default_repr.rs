@@ -0,0 +1,31 @@
+#![deny(deprecated)]
+
+use pakr_assert_size::*;
+
+#[assert_size(8)]
+struct Implicit {
+    tag: u32,
+    len: u32,
+}
+
+#[assert_size(8, allow_default_repr)]
+struct Allowed {
+    tag: u32,
+    len: u32,
+}
+
+#[assert_size(8)]
+#[repr(C)]
+struct Explicit {
+    tag: u32,
+    len: u32,
+}
+
+#[repr(C)]
+#[assert_size(8)]
+struct Preceding {
+    tag: u32,
+    len: u32,
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+use pakr_assert_size::*;
+
+#[assert_size(8, paranoid, paranoid)]
+#[repr(C)]
+struct Header {
+    tag: u32,
+    len: u32,
+}
+
+#[assert_size(8, msg = "header", msg = "wire header")]
+#[repr(C)]
+struct Frame {
+    tag: u32,
+    len: u32,
+}
+
+#[assert_size(8, export = FRAME_SIZE, export = PACKET_SIZE)]
+#[repr(C)]
+struct Packet {
+    tag: u32,
+    len: u32,
+}
+
+#[assert_size(8, abi = "1", abi = "2")]
+#[repr(C)]
+struct Record {
+    tag: u32,
+    len: u32,
+}
+
+#[assert_size(8, phantom_fill = "u32", phantom_fill = "u64")]
+#[repr(C)]
+struct Slot<T> {
+    tag: u32,
+    value: core::marker::PhantomData<T>,
+}
+
+fn main() {}
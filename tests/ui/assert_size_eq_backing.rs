@@ -0,0 +1,15 @@
+use pakr_assert_size::*;
+
+#[assert_size_eq_backing]
+#[repr(C, align(8))]
+struct Aligned(u8);
+
+#[assert_size_eq_backing]
+#[repr(C)]
+struct Pair(u8, u8);
+
+#[assert_size_eq_backing]
+#[repr(C)]
+struct Empty;
+
+fn main() {}
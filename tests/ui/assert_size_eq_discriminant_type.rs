@@ -0,0 +1,16 @@
+use pakr_assert_size::*;
+
+#[assert_size_eq_discriminant_type]
+#[repr(u16)]
+enum Opcode {
+    Nop,
+    Load(u64),
+}
+
+#[assert_size_eq_discriminant_type]
+enum Flag {
+    Off,
+    On,
+}
+
+fn main() {}
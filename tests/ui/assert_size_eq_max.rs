@@ -0,0 +1,12 @@
+use pakr_assert_size::*;
+
+#[assert_size_eq_max(u8, [u8; 4], u16)]
+#[repr(C)]
+union Value {
+    byte: u8,
+    bytes: [u8; 4],
+    word: u16,
+    long: u64,
+}
+
+fn main() {}
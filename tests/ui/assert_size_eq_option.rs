@@ -0,0 +1,8 @@
+use pakr_assert_size::*;
+
+#[assert_size_eq_option]
+struct Index {
+    value: u32,
+}
+
+fn main() {}
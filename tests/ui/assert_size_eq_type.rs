@@ -0,0 +1,7 @@
+use pakr_assert_size::*;
+
+#[assert_size_eq_type(u32)]
+#[repr(C)]
+struct Handle(u64);
+
+fn main() {}
@@ -0,0 +1,15 @@
+use pakr_assert_size::*;
+
+#[assert_size(8 | 16, export = HANDLE_SIZE)]
+#[repr(C)]
+struct Handle {
+    id: u64,
+}
+
+#[assert_size(4, export = WORD_SIZE)]
+#[repr(C)]
+struct Word(u64);
+
+const _: [u8; WORD_SIZE] = [0; 4];
+
+fn main() {}
@@ -0,0 +1,14 @@
+use pakr_assert_size::*;
+
+#[assert_size_fields(each = 4)]
+#[repr(C)]
+struct Pair {
+    first: u32,
+    second: [u8; 8],
+}
+
+#[assert_size_fields(count = 4)]
+#[repr(C)]
+struct Quad(u8, u8, u8, u8);
+
+fn main() {}
@@ -0,0 +1,11 @@
+use pakr_assert_size::*;
+
+#[assert_size_fits(16)]
+#[repr(C, packed)]
+struct C {
+    field1: u64,
+    field2: u64,
+    field3: u64,
+}
+
+fn main() {}
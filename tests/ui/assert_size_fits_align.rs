@@ -0,0 +1,15 @@
+use pakr_assert_size::*;
+
+#[assert_size_fits(64, align = 16)]
+#[repr(C, align(32))]
+struct Task {
+    id: u64,
+}
+
+#[assert_size_fits(64, align = 8)]
+#[repr(C)]
+struct Fits {
+    id: u64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use pakr_assert_size::*;
+
+#[assert_size_fits(1ptr)]
+#[repr(C)]
+struct Key {
+    bytes: [u8; 32],
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+#![deny(deprecated)]
+
+use pakr_assert_size::*;
+
+#[assert_size_fits(32, warn_at = 16)]
+#[repr(C)]
+struct Below {
+    bytes: [u8; 8],
+}
+
+#[assert_size_fits(32, warn_at = 16)]
+#[repr(C)]
+struct Between {
+    bytes: [u8; 24],
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use pakr_assert_size::*;
+
+#[assert_size_fits(16, warn_at = 32)]
+#[repr(C)]
+struct Hot {
+    bytes: [u8; 8],
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+#![deny(deprecated)]
+
+use pakr_assert_size::*;
+
+#[assert_size_fits(0)]
+#[repr(C)]
+struct Marker {}
+
+#[assert_size_fits(4)]
+#[repr(C)]
+struct Word(u32);
+
+fn main() {}
@@ -0,0 +1,11 @@
+use pakr_assert_size::*;
+
+#[repr(C)]
+struct Pair<T> {
+    first: T,
+    second: T,
+}
+
+assert_size_for!(Pair<u32> = 8, Pair<u16> = 8);
+
+fn main() {}
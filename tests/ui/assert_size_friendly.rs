@@ -0,0 +1,23 @@
+use pakr_assert_size::*;
+
+const KIB: usize = 1024;
+
+#[assert_size(1048576)]
+#[repr(C)]
+struct Arena {
+    bytes: [u8; 1048576 + 64],
+}
+
+#[assert_size(1024 * KIB)]
+#[repr(C)]
+struct Computed {
+    bytes: [u8; 1048576 + 64],
+}
+
+#[assert_size_fits(1536)]
+#[repr(C)]
+struct Page {
+    bytes: [u8; 2048],
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use pakr_assert_size::*;
+
+#[assert_size(8)]
+struct Wrapper<T> {
+    value: T,
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+use pakr_assert_size::*;
+
+const HEADER_V1_SIZE: usize = 16;
+
+#[assert_size_grows_by(base = HEADER_V1_SIZE, delta = 8)]
+#[repr(C, packed)]
+struct Grown {
+    magic: u64,
+    len: u64,
+    checksum: u32,
+}
+
+#[assert_size_grows_by(delta = -8, base = 4)]
+#[repr(C)]
+struct Shrunk {
+    id: u32,
+}
+
+#[assert_size_grows_by(base = 16, delta = 0)]
+#[repr(C)]
+struct Unchanged {
+    id: u32,
+}
+
+#[assert_size_grows_by(base = 16)]
+#[repr(C)]
+struct NoDelta {
+    id: u32,
+}
+
+#[assert_size_grows_by(base = 16, step = 4)]
+#[repr(C)]
+struct UnknownKey {
+    id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use pakr_assert_size::*;
+
+#[assert_size(16)]
+#[repr(C)]
+struct Request {
+    id: u64,
+    len: u64,
+}
+
+#[assert_size(16)]
+#[repr(C)]
+struct Response {
+    id: u64,
+    status: u32,
+    len: u64,
+}
+
+#[assert_size(16)]
+#[repr(C)]
+struct Event {
+    id: u64,
+    kind: u64,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use pakr_assert_size::*;
+
+trait Codec {
+    type Frame;
+}
+
+struct Modbus;
+
+#[assert_size(Self::Frame = 4)]
+impl Codec for Modbus {
+    type Frame = [u16; 4];
+}
+
+struct Can;
+
+#[assert_size(Self::Packet = 8)]
+impl Codec for Can {
+    type Frame = [u8; 8];
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use pakr_assert_size::*;
+
+#[assert_size_in_words(2)]
+#[repr(C)]
+struct Value {
+    tag: u64,
+    payload: [u64; 2],
+}
+
+#[assert_size_in_words(2, word = 4)]
+#[repr(C)]
+struct Narrow([u8; 12]);
+
+#[assert_align_in_words(1)]
+#[repr(C)]
+struct Unaligned([u8; 8]);
+
+#[assert_size_in_words(1, word = 0)]
+struct Zero;
+
+#[assert_size_in_words(1, bytes = 8)]
+struct Unknown;
+
+fn main() {}
@@ -0,0 +1,19 @@
+use pakr_assert_size::*;
+
+#[assert_size(16, mgs = "typo")]
+#[repr(C)]
+struct Header {
+    tag: u64,
+    len: u64,
+}
+
+impl Header {
+    fn len(&self) -> u64 {
+        self.len
+    }
+}
+
+fn main() {
+    let header = Header { tag: 0, len: 0 };
+    let _ = (header.tag, header.len());
+}
@@ -0,0 +1,13 @@
+use core::marker::PhantomData;
+use pakr_assert_size::*;
+
+#[assert_size(8)]
+#[assert_no_padding]
+#[assert_field_offset(len = 0)]
+#[repr(C)]
+struct Ref<'a> {
+    len: u32,
+    _marker: PhantomData<&'a u8>,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use pakr_assert_size::*;
+
+macro_rules! register {
+    ($name:ident, $ty:ty, $size:expr) => {
+        #[assert_size($size)]
+        #[repr(C)]
+        struct $name {
+            value: $ty,
+        }
+    };
+}
+
+register!(Control, u32, 4);
+register!(Window, [u8; 512], 1KiB);
+
+fn main() {}
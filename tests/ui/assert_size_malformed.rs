@@ -0,0 +1,17 @@
+use pakr_assert_size::*;
+
+#[assert_size_fits(16, warn = 8)]
+#[repr(C)]
+struct Header {
+    tag: u64,
+}
+
+#[assert_size_at_least(16 16)]
+#[repr(C)]
+struct Trailer {
+    crc: u64,
+}
+
+fn main() {
+    let _ = (Header { tag: 0 }.tag, Trailer { crc: 0 }.crc);
+}
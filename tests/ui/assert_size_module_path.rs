@@ -0,0 +1,23 @@
+mod net {
+    use pakr_assert_size::*;
+
+    pub mod frame {
+        use pakr_assert_size::*;
+
+        #[assert_size(16)]
+        #[repr(C)]
+        pub struct Header {
+            tag: u32,
+            len: u32,
+        }
+    }
+
+    #[assert_field_offset(len = 2)]
+    #[repr(C)]
+    pub struct Header {
+        tag: u32,
+        len: u32,
+    }
+}
+
+fn main() {}
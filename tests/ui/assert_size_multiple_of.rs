@@ -0,0 +1,10 @@
+use pakr_assert_size::*;
+
+#[assert_size_multiple_of(16)]
+#[repr(C)]
+struct Vertex {
+    position: [f32; 4],
+    uv: [f32; 2],
+}
+
+fn main() {}
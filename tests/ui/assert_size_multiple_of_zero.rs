@@ -0,0 +1,17 @@
+use pakr_assert_size::*;
+
+#[assert_size_multiple_of(0)]
+#[repr(C)]
+struct Header {
+    tag: u32,
+    len: u32,
+}
+
+#[assert_align_multiple_of(0)]
+#[repr(C)]
+struct Aligned {
+    tag: u32,
+    len: u32,
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use pakr_assert_size::*;
+
+#[assert_size(Header = 8)]
+#[repr(C)]
+struct Header {
+    tag: u32,
+    len: u32,
+}
+
+#[assert_size(self = 8, msg = "copied from Header")]
+#[repr(C)]
+struct Trailer {
+    crc: u32,
+    len: u32,
+}
+
+#[assert_size(Header = 8)]
+#[repr(C)]
+struct Footer {
+    crc: u32,
+    len: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use pakr_assert_size::*;
+
+#[assert_size_not(12)]
+#[repr(C, packed)]
+struct Record {
+    id: u64,
+    flags: u32,
+}
+
+fn main() {}
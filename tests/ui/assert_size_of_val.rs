@@ -0,0 +1,11 @@
+use pakr_assert_size::*;
+
+const MAGIC: [u8; 4] = *b"\x7fELF";
+const CRC_TABLE: &[u16] = &[0x0000, 0x1021, 0x2042];
+
+assert_size_of_val!(MAGIC, 4);
+assert_size_of_val!(MAGIC, 8);
+assert_size_of_val!(*CRC_TABLE, 8);
+assert_size_of_val!(*CRC_TABLE, 8, msg = "CRC table must have 4 entries");
+
+fn main() {}
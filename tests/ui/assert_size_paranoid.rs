@@ -0,0 +1,15 @@
+use pakr_assert_size::*;
+
+#[assert_size(8, paranoid, audit)]
+#[repr(C)]
+struct Header {
+    tag: u64,
+}
+
+#[assert_size(4, paranoid)]
+#[repr(C)]
+struct Frame {
+    tag: u64,
+}
+
+fn main() {}
@@ -0,0 +1,41 @@
+use core::marker::PhantomData;
+use pakr_assert_size::*;
+
+#[assert_size(16, phantom_fill = "u64")]
+#[repr(C)]
+struct Pair<T> {
+    first: T,
+    second: T,
+}
+
+#[assert_size(12, phantom_fill(T = u32, N = 3))]
+#[repr(C)]
+struct Array<'a, T, const N: usize> {
+    items: [T; N],
+    _ref: PhantomData<&'a T>,
+}
+
+#[assert_size(16, phantom_fill(T = u8))]
+#[repr(C)]
+struct Bad<T> {
+    first: T,
+}
+
+#[assert_size(16, phantom_fill = "u8")]
+#[repr(C)]
+struct NoConst<const N: usize>([u8; N]);
+
+#[assert_size(16, phantom_fill(U = u8))]
+#[repr(C)]
+struct Unknown<T>(T);
+
+#[assert_size(16, phantom_fill = "u8")]
+#[repr(C)]
+struct Plain(u8);
+
+fn main() {
+    let _ = Pair {
+        first: 1u8,
+        second: 2,
+    };
+}
@@ -0,0 +1,22 @@
+use pakr_assert_size::*;
+
+#[assert_size_pow2_bucket(5)]
+#[repr(C)]
+struct Fits([u8; 24]);
+
+#[assert_size_pow2_bucket(5)]
+#[repr(C)]
+struct Above([u8; 40]);
+
+#[assert_size_pow2_bucket(5)]
+#[repr(C)]
+struct Below([u8; 16]);
+
+#[assert_size_pow2_bucket(0)]
+struct Empty;
+
+#[assert_size_pow2_bucket(64)]
+#[repr(C)]
+struct Huge(u8);
+
+fn main() {}
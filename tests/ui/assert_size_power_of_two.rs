@@ -0,0 +1,10 @@
+use pakr_assert_size::*;
+
+#[assert_size(16)]
+#[assert_size_fits(16)]
+#[repr(C, packed)]
+struct Odd {
+    bytes: [u8; 17],
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use pakr_assert_size::*;
+
+#[repr(C)]
+struct Header {
+    tag: u8,
+    len: u32,
+    crc: u16,
+}
+
+assert_size_print!(Header);
+
+fn main() {}
@@ -0,0 +1,43 @@
+use pakr_assert_size::*;
+
+#[repr(C)]
+struct Lane {
+    x: f32,
+    y: f32,
+}
+
+#[assert_size_ratio(Lane, times = 2)]
+#[repr(C)]
+struct Lane2 {
+    lo: Lane,
+    hi: f32,
+}
+
+const HEADER: usize = 8;
+
+#[assert_size_ratio(Lane, times = 4, plus = HEADER)]
+#[repr(C)]
+struct Packet {
+    header: u32,
+    lanes: [Lane; 4],
+}
+
+#[assert_size_ratio(Lane)]
+#[repr(C)]
+struct Same {
+    lane: Lane,
+}
+
+#[assert_size_ratio(Lane, times = 2, times = 3)]
+#[repr(C)]
+struct Twice {
+    lanes: [Lane; 2],
+}
+
+#[assert_size_ratio(Lane, twice)]
+#[repr(C)]
+struct Unknown {
+    lanes: [Lane; 2],
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use pakr_assert_size::*;
+
+#[assert_size(4)]
+#[repr(C)]
+struct Header {
+    tag: u32,
+    len: u32,
+}
+
+#[assert_size(8)]
+#[repr(C)]
+struct Frame {
+    header: Header,
+    crc: u32,
+}
+
+mod nested {
+    use pakr_assert_size::*;
+
+    #[assert_size(2)]
+    #[assert_size_fits(1)]
+    #[repr(C)]
+    pub struct Trailer {
+        crc: u32,
+    }
+}
+
+fn main() {}
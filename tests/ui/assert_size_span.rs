@@ -0,0 +1,14 @@
+use pakr_assert_size::*;
+
+#[assert_size(8)]
+#[assert_size_fits(16)]
+#[assert_size_at_least(32)]
+#[assert_align(8)]
+#[repr(C)]
+struct Header {
+    id: u64,
+}
+
+assert_size_for!(u16 = 2, u32 = 8, u64 = 8);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use pakr_assert_size::*;
+
+#[assert_size(256u8)]
+#[repr(C)]
+struct Block {
+    bytes: [u8; 256],
+}
+
+fn main() {}
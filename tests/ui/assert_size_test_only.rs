@@ -0,0 +1,18 @@
+use pakr_assert_size::*;
+
+// Outside of test builds, only the check without `test_only` is evaluated.
+#[assert_size(4, test_only)]
+#[repr(C)]
+struct Skipped {
+    tag: u32,
+    len: u32,
+}
+
+#[assert_size(4)]
+#[repr(C)]
+struct Checked {
+    tag: u32,
+    len: u32,
+}
+
+fn main() {}
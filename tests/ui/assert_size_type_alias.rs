@@ -0,0 +1,15 @@
+use pakr_assert_size::*;
+
+#[assert_size(8)]
+type Handle = u64;
+
+#[assert_size_fits(16)]
+type Pair = (u64, u64);
+
+#[assert_size(target_pointer_width = "64" => 8, target_pointer_width = "32" => 4)]
+type Word = usize;
+
+#[assert_align(8)]
+type Aligned = u64;
+
+fn main() {}
@@ -0,0 +1,10 @@
+use pakr_assert_size::*;
+
+#[assert_size(8, message = "must match on-wire protocol header")]
+#[repr(C)]
+struct Header {
+    tag: u32,
+    len: u32,
+}
+
+fn main() {}
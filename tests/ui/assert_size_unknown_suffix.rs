@@ -0,0 +1,9 @@
+use pakr_assert_size::*;
+
+#[assert_size(4Kib)]
+#[repr(C)]
+struct Page {
+    bytes: [u8; 4096],
+}
+
+fn main() {}
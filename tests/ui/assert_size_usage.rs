@@ -0,0 +1,12 @@
+use pakr_assert_size::*;
+
+#[assert_size(@)]
+struct A(u8);
+
+#[assert_size()]
+struct B(u8);
+
+#[assert_size(16 bytes)]
+struct C(u8);
+
+fn main() {}
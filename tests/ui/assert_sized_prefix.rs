@@ -0,0 +1,32 @@
+use pakr_assert_size::*;
+
+#[assert_sized_prefix(4)]
+#[repr(C)]
+struct Msg {
+    len: u32,
+    data: [u64],
+}
+
+#[assert_sized_prefix(2, msg = "name must follow the length directly")]
+#[repr(C)]
+struct Name<'a> {
+    owner: &'a u8,
+    len: u16,
+    name: str,
+}
+
+#[assert_sized_prefix(5)]
+#[repr(C, packed)]
+struct Packed(u8, u32, [u16]);
+
+#[assert_sized_prefix(4)]
+#[repr(C)]
+struct Sized {
+    len: u32,
+    data: [u8; 0],
+}
+
+#[assert_sized_prefix(0)]
+struct Empty {}
+
+fn main() {}
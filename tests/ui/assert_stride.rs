@@ -0,0 +1,10 @@
+use pakr_assert_size::*;
+
+#[assert_stride(5)]
+#[repr(C)]
+struct Sample {
+    value: u32,
+    flags: u8,
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+use core::marker::PhantomData;
+use pakr_assert_size::*;
+
+pub struct Metric;
+
+#[assert_transparent]
+#[repr(C)]
+pub struct Tagged {
+    value: u32,
+    tag: u8,
+}
+
+#[assert_transparent]
+pub struct Pair(u32, Metric, PhantomData<u8>);
+
+#[assert_transparent]
+pub struct Empty(PhantomData<u8>, ());
+
+#[assert_transparent]
+#[repr(C, align(8))]
+pub struct Aligned {
+    value: u32,
+    _unit: PhantomData<Metric>,
+}
+
+#[assert_transparent]
+#[repr(C)]
+pub struct Padded {
+    value: u32,
+    _align: [u64; 0],
+}
+
+#[assert_transparent(u32)]
+pub struct Argument(u32);
+
+fn main() {}
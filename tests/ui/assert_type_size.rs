@@ -0,0 +1,7 @@
+use pakr_assert_size::*;
+
+assert_type_size!(u16, 4);
+assert_type_size!(u64 <= 4);
+assert_type_size!(u8 >= 2);
+
+fn main() {}
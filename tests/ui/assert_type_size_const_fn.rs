@@ -0,0 +1,21 @@
+use pakr_assert_size::*;
+
+const fn expected(words: usize) -> usize {
+    words * 4
+}
+
+fn not_const() -> usize {
+    8
+}
+
+#[repr(C)]
+struct Header {
+    tag: u32,
+    len: u32,
+}
+
+assert_type_size!(Header, expected(2));
+assert_type_size!(Header, expected(3));
+assert_type_size!(Header, not_const());
+
+fn main() {}
@@ -0,0 +1,17 @@
+use pakr_assert_size::*;
+
+trait Shape {
+    fn area(&self) -> f64;
+}
+
+assert_type_size!(&dyn Shape, 1ptr);
+assert_type_size!(Box<dyn Shape + Send>, 8);
+
+assert_type_size!(dyn Shape, 16);
+const_assert_size_eq!((dyn Shape + Send), 16);
+size_assert_all! {
+    &dyn Shape = 16,
+    dyn Shape = 16,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use pakr_assert_size::*;
+
+#[repr(C)]
+struct Vec2 {
+    x: f64,
+    y: f64,
+}
+
+#[repr(C)]
+struct Vec3f {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+assert_types_size!((Vec2, Vec3f, [u64; 2]) == 16);
+assert_types_size!((u32, u64), 4);
+assert_types_size!((u16, u32) >= 4, msg = "registers are at least a word");
+assert_types_size!(() == 0);
+
+fn main() {}
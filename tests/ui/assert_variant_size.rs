@@ -0,0 +1,17 @@
+use pakr_assert_size::*;
+
+#[assert_variant_size(Ping = 0, Data = 12, Pong = 0)]
+#[repr(C, u8)]
+enum Message {
+    Ping,
+    Data { id: u32, len: u32, offset: u64 },
+}
+
+#[assert_variant_size(Data = 12)]
+#[repr(C, u8)]
+enum Reply {
+    Ping,
+    Data { id: u32, len: u32, offset: u64 },
+}
+
+fn main() {}
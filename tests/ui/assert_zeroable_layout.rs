@@ -0,0 +1,17 @@
+use pakr_assert_size::*;
+
+#[assert_zeroable_layout(8)]
+#[repr(C)]
+pub struct Sample {
+    flag: u8,
+    value: u32,
+}
+
+#[assert_zeroable_layout(16, msg = "must match the GPU vertex format")]
+#[repr(C)]
+pub struct Vertex {
+    position: [f32; 2],
+    color: u32,
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use pakr_assert_size::*;
+
+#[assert_zerocopy(16)]
+#[repr(C)]
+struct Padded {
+    kind: u8,
+    len: u32,
+    seq: u64,
+}
+
+#[assert_zerocopy(16)]
+#[repr(C, packed)]
+struct Packed {
+    kind: u8,
+    len: u32,
+    seq: u64,
+}
+
+#[assert_zerocopy(16)]
+struct Unspecified {
+    magic: u32,
+    len: u32,
+    seq: u64,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use pakr_assert_size::*;
+
+#[assert_zst]
+struct Marker {
+    flag: u8,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use core::mem::size_of;
+use pakr_assert_size::*;
+
+const_assert_size_eq!(u16, 4);
+const_assert_size_eq!([u8; 3], size_of::<u32>(),);
+
+fn main() {
+    const_assert_size_eq!(u64, 4);
+}
@@ -0,0 +1,14 @@
+#![deny(deprecated)]
+
+use pakr_assert_size::*;
+
+#[assert_min_align(4)]
+#[assert_max_align(8)]
+#[assert_align_words(1)]
+#[repr(C)]
+struct Value {
+    tag: u64,
+    payload: u64,
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+use pakr_assert_size::*;
+
+#[derive(Clone, AssertSize)]
+#[assert_size(12)]
+#[repr(C)]
+struct Header {
+    tag: u32,
+    id: u64,
+}
+
+#[derive(AssertSize)]
+#[assert_size(4, msg = "tag must stay a single word")]
+struct Tag(u64);
+
+#[derive(AssertSize)]
+#[repr(C)]
+struct Missing {
+    tag: u32,
+}
+
+#[derive(AssertSize)]
+#[assert_size(4)]
+#[assert_size(4)]
+struct Duplicate {
+    tag: u32,
+}
+
+#[derive(Clone, AssertSize)]
+#[assert_size(4)]
+enum Ordinary {
+    A(u32),
+    B,
+}
+
+fn main() {}
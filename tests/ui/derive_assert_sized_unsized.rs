@@ -0,0 +1,9 @@
+use pakr_assert_size::AssertSized;
+
+#[derive(AssertSized)]
+struct Packet {
+    len: u32,
+    data: [u8],
+}
+
+fn main() {}
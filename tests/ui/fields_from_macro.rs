@@ -0,0 +1,17 @@
+use pakr_assert_size::*;
+
+#[assert_size(8)]
+#[repr(C)]
+struct Generated {
+    include!("fields.rs")
+}
+
+#[assert_no_padding]
+#[repr(C)]
+struct Partly {
+    tag: u32,
+    #[doc(hidden)]
+    pub(crate) include!("rest.rs"),
+}
+
+fn main() {}
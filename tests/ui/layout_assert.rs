@@ -0,0 +1,15 @@
+use pakr_assert_size::prelude::*;
+
+#[repr(C)]
+struct Record {
+    tag: u8,
+    value: u32,
+}
+
+layout_assert!(Record: size == 8, align == 4, offset(value) == 4);
+layout_assert!(Record: size <= 4, align >= 8, offset(value) == 1);
+layout_assert!(Record: no_padding(tag, value));
+layout_assert!(Record: no_padding);
+layout_assert!(Record: stride == 8);
+
+fn main() {}
@@ -0,0 +1,22 @@
+use pakr_assert_size::*;
+
+// Raw identifiers are written without their prefix in the messages.
+#[assert_size(4)]
+#[assert_field_offset(r#type = 4)]
+#[assert_field_size(r#type = 2)]
+#[repr(C)]
+struct r#struct {
+    r#type: u32,
+    r#len: u32,
+}
+
+#[assert_no_padding]
+#[repr(C)]
+struct r#enum {
+    r#tag: u8,
+    r#type: u32,
+}
+
+layout_assert!(r#struct: offset(r#len) == 0);
+
+fn main() {}
@@ -0,0 +1,16 @@
+use pakr_assert_size::*;
+
+#[repr(C)]
+struct Header {
+    tag: u32,
+    len: u32,
+}
+
+size_assert_all! {
+    Header = 8,
+    Header <= 4,
+    Header >= 16,
+    u64 = 4,
+}
+
+fn main() {}
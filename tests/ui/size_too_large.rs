@@ -0,0 +1,17 @@
+use pakr_assert_size::*;
+
+#[assert_size(99999999999999999999999999999999999999999)]
+#[repr(C)]
+struct Digits(u8);
+
+#[assert_size(9223372036854775808)]
+#[repr(C)]
+struct BeyondIsize(u8);
+
+#[assert_size_fits(10000000000GB)]
+#[repr(C)]
+struct WithUnit(u8);
+
+assert_type_size!(u8, 0x1_0000_0000_0000_0000);
+
+fn main() {}